@@ -3,19 +3,21 @@
 //! A Rust CLI tool that extracts game data from a GameCube ISO
 //! and exports it to CSV files, or imports CSV data back into the ISO.
 
-use alx::csv::{CsvExporter, CsvImporter};
-use alx::game::GameRoot;
-use clap::Parser;
+use alx::csv::{CsvExporter, CsvImporter, ExportProgress, ProgressTracker};
+use alx::entries::EntryKind;
+use alx::game::{GameData, GameRoot, Offsets};
+use alx::io::{EnemyFileKind, EnpDumpStats};
+use clap::{Parser, ValueEnum};
+use indicatif::{ProgressBar, ProgressStyle};
 use std::fs::{self, File};
 use std::io::{BufReader, Write};
 use std::path::{Path, PathBuf};
 
 macro_rules! export_csv {
-    ($game:expr, $output_dir:expr, $name:expr, $read_fn:ident, $export_fn:ident, $filename:expr) => {{
-        print!("Exporting {}...", $name);
+    ($game:expr, $path:expr, $read_fn:ident, $export_fn:ident) => {{
         let data = $game.$read_fn()?;
-        CsvExporter::$export_fn(&data, File::create($output_dir.join($filename))?)?;
-        println!(" {} entries", data.len());
+        CsvExporter::$export_fn(&data, File::create($path)?)?;
+        data.len()
     }};
 }
 
@@ -26,10 +28,12 @@ macro_rules! export_csv {
 #[command(about = "Exports/imports Skies of Arcadia game data to/from CSV files", long_about = None)]
 struct Args {
     /// Path to the GameCube ISO file
-    #[arg(value_name = "ISO_FILE")]
-    iso_path: PathBuf,
+    #[arg(value_name = "ISO_FILE", required_unless_present_any = ["decompress", "compress", "emit_schema"])]
+    iso_path: Option<PathBuf>,
 
-    /// Output directory for CSV files (export mode), or output ISO path (import mode)
+    /// Output directory for CSV files (export mode), or output ISO path (import mode).
+    /// In import mode an existing directory, or a path ending in a separator,
+    /// gets <ISO stem>-randomized.iso written into it
     #[arg(short, long, value_name = "PATH")]
     output: Option<PathBuf>,
 
@@ -38,21 +42,163 @@ struct Args {
     #[arg(short, long, value_name = "IMPORT_DIR")]
     import: Option<PathBuf>,
 
+    /// Check the CSV and JSON files in a folder the way import would, without
+    /// writing anything, and print every problem found
+    #[arg(long, value_name = "IMPORT_DIR", conflicts_with = "import")]
+    validate: Option<PathBuf>,
+
+    /// Randomize mode: randomize the ISO and save it to --output
+    #[arg(long, requires = "output", conflicts_with_all = ["import", "validate"])]
+    randomize: bool,
+
+    /// Randomizer settings as JSON (a RandomizerConfig). Without it every
+    /// pass that can be saved runs at its most extreme setting
+    #[arg(long, value_name = "FILE", requires = "randomize")]
+    config: Option<PathBuf>,
+
+    /// Randomizer seed, overriding the one in --config
+    /// A random seed is picked and printed if neither sets one
+    #[arg(long, value_name = "N", requires = "randomize")]
+    seed: Option<u64>,
+
+    /// Write the randomizer spoiler log (every changed field) to a file
+    #[arg(long, value_name = "FILE", requires = "randomize")]
+    spoiler: Option<PathBuf>,
+
+    /// Print a short code that matches for identical randomized games
+    #[arg(long, requires = "randomize")]
+    verify_string: bool,
+
     /// Dump an ENP file's structure to JSON for debugging
     /// Example: --dump-enp a101b_ep.enp
     #[arg(long, value_name = "ENP_FILE")]
     dump_enp: Option<String>,
 
-    /// Dump the EVP file's structure to JSON for debugging
-    /// Example: --dump-evp
+    /// Dump an EVP file's structure to JSON for debugging
+    /// (epevent.evp if no file is given; a file needs the `=` form, so a
+    /// following ISO path isn't read as one)
+    /// Example: --dump-evp=epevent.evp
+    #[arg(
+        long,
+        value_name = "EVP_FILE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "epevent.evp"
+    )]
+    dump_evp: Option<String>,
+
+    /// List every ENP file with its compressed/decompressed sizes and
+    /// enemy count
+    #[arg(long)]
+    list_enp: bool,
+
+    /// Print a quick summary of the ISO (version, entry counts, DOL CRC)
+    /// without exporting anything
     #[arg(long)]
-    dump_evp: bool,
+    examine: bool,
+
+    /// Write an IPS patch of ISO_FILE's Start.dol against a pristine ISO
+    /// Writes to --output, or <ISO_FILE>.ips by default
+    #[arg(long, value_name = "PRISTINE_ISO")]
+    make_patch: Option<PathBuf>,
+
+    /// Decompress an AKLZ file on disk (no ISO needed)
+    /// Writes to --output, or <FILE>.dec by default
+    #[arg(long, value_name = "FILE", conflicts_with = "compress")]
+    decompress: Option<PathBuf>,
+
+    /// Compress a file on disk with AKLZ (no ISO needed)
+    /// Writes to --output, or <FILE>.aklz by default
+    #[arg(long, value_name = "FILE")]
+    compress: Option<PathBuf>,
+
+    /// Write a JSON Schema for each entry type into a directory (no ISO needed)
+    /// Example: --emit-schema ui/schema
+    #[arg(long, value_name = "DIR")]
+    emit_schema: Option<PathBuf>,
+
+    /// Read data offsets from a TOML file instead of using the built-in
+    /// table for the detected region, e.g. for a fan build
+    #[arg(long, value_name = "FILE")]
+    offsets: Option<PathBuf>,
+
+    /// Write ENP/EVP JSON dumps minified instead of pretty-printed
+    #[arg(long)]
+    compact_json: bool,
+
+    /// Also write one un-deduplicated CSV per ENP/EVP/DAT file into enemies/
+    #[arg(long)]
+    enemies_by_file: bool,
+
+    /// Write only the encounters of each ENP file into encounters/, instead
+    /// of the full enemy dumps in enp/
+    #[arg(long)]
+    encounters_only: bool,
+
+    /// Folder structure of the export. Import and validate detect it
+    #[arg(long, value_enum, default_value_t = ExportLayout::Flat)]
+    layout: ExportLayout,
+
+    /// Print per-file ENP compression ratios and totals during export
+    #[arg(short, long)]
+    verbose: bool,
 
     /// Skip confirmation prompts (auto-confirm overwrites)
     #[arg(short = 'y', long = "yes")]
     yes: bool,
 }
 
+/// What an export writes and how, from the export flags.
+#[derive(Debug, Clone, Copy)]
+struct ExportOptions {
+    /// Print ENP dump statistics
+    verbose: bool,
+    /// Write ENP/EVP JSON dumps minified
+    compact_json: bool,
+    /// Also write one un-deduplicated CSV per ENP/EVP/DAT file
+    enemies_by_file: bool,
+    /// Write only the encounters of each ENP file
+    encounters_only: bool,
+    layout: ExportLayout,
+}
+
+/// Folder structure of an export folder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+enum ExportLayout {
+    /// CSVs at the top, JSON dumps in enp/, evp/ and encounters/
+    #[default]
+    Flat,
+    /// CSVs in csv/, JSON dumps in json/enp/, json/evp/ and json/encounters/
+    Nested,
+}
+
+impl ExportLayout {
+    /// Guess the layout of an existing export folder.
+    fn detect(dir: &Path) -> Self {
+        if dir.join("csv").is_dir() {
+            ExportLayout::Nested
+        } else {
+            ExportLayout::Flat
+        }
+    }
+
+    /// Get the folder holding the CSV files.
+    fn csv_dir(&self, root: &Path) -> PathBuf {
+        match self {
+            ExportLayout::Flat => root.to_path_buf(),
+            ExportLayout::Nested => root.join("csv"),
+        }
+    }
+
+    /// Get the folder holding the enp/, evp/ and encounters/ JSON folders.
+    fn json_dir(&self, root: &Path) -> PathBuf {
+        match self {
+            ExportLayout::Flat => root.to_path_buf(),
+            ExportLayout::Nested => root.join("json"),
+        }
+    }
+}
+
 /// Prompt user for confirmation to overwrite
 fn confirm_overwrite() -> Result<bool, Box<dyn std::error::Error>> {
     print!("Are you sure you want to continue? [y/N]: ");
@@ -75,36 +221,316 @@ fn main() {
 fn run() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
+    // AKLZ utilities work on loose files and don't need an ISO
+    if let Some(input) = args.decompress {
+        return run_aklz_file(&input, args.output.as_deref(), false);
+    }
+    if let Some(input) = args.compress {
+        return run_aklz_file(&input, args.output.as_deref(), true);
+    }
+
+    if let Some(dir) = args.emit_schema {
+        return run_emit_schema(&dir);
+    }
+
+    let iso_path = args.iso_path.ok_or("ISO file is required")?;
+
     // Validate ISO path
-    if !args.iso_path.exists() {
-        return Err(format!("ISO file not found: {}", args.iso_path.display()).into());
+    if !iso_path.exists() {
+        return Err(format!("ISO file not found: {}", iso_path.display()).into());
+    }
+
+    let offsets = match &args.offsets {
+        Some(path) => Some(Offsets::from_toml(File::open(path)?)?),
+        None => None,
+    };
+    let offsets = offsets.as_ref();
+
+    // Check if we're in examine mode
+    if args.examine {
+        return run_examine(&iso_path, offsets);
+    }
+
+    // Check if we're in list-enp mode
+    if args.list_enp {
+        return run_list_enp(&iso_path, offsets);
     }
 
     // Check if we're in dump-enp mode
     if let Some(enp_name) = args.dump_enp {
-        return run_dump_enp(&args.iso_path, &enp_name, args.output.as_deref());
+        return run_dump_enp(
+            &iso_path,
+            &enp_name,
+            args.output.as_deref(),
+            args.compact_json,
+            offsets,
+        );
     }
 
     // Check if we're in dump-evp mode
-    if args.dump_evp {
-        return run_dump_evp(&args.iso_path, args.output.as_deref());
+    if let Some(evp_name) = args.dump_evp {
+        return run_dump_evp(
+            &iso_path,
+            &evp_name,
+            args.output.as_deref(),
+            args.compact_json,
+            offsets,
+        );
+    }
+
+    // Check if we're in make-patch mode
+    if let Some(pristine) = args.make_patch {
+        return run_make_patch(&iso_path, &pristine, args.output.as_deref(), offsets);
+    }
+
+    // Check if we're in validate mode
+    if let Some(import_dir) = args.validate {
+        return run_validate(&iso_path, &import_dir, offsets);
+    }
+
+    // Check if we're in randomize mode
+    if args.randomize {
+        let output = args.output.as_deref().ok_or("--randomize needs --output")?;
+        return run_randomize(
+            &iso_path,
+            output,
+            args.config.as_deref(),
+            args.seed,
+            args.spoiler.as_deref(),
+            args.verify_string,
+            args.yes,
+            offsets,
+        );
     }
 
     // Check if we're in import mode
     if let Some(import_dir) = args.import {
         return run_import(
-            &args.iso_path,
+            &iso_path,
             &import_dir,
             args.output.as_deref(),
             args.yes,
+            offsets,
         );
     }
 
     // Export mode
-    run_export(&args.iso_path, args.output)
+    let options = ExportOptions {
+        verbose: args.verbose,
+        compact_json: args.compact_json,
+        enemies_by_file: args.enemies_by_file,
+        encounters_only: args.encounters_only,
+        layout: args.layout,
+    };
+    run_export(&iso_path, args.output, &options, offsets)
 }
 
-fn run_export(iso_path: &Path, output: Option<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+/// Open an ISO with the `--offsets` table, if one was given, instead of the
+/// built-in one.
+fn open_game(path: &Path, offsets: Option<&Offsets>) -> alx::Result<GameRoot> {
+    match offsets {
+        Some(offsets) => GameRoot::open_with_offsets(path, offsets.clone()),
+        None => GameRoot::open(path),
+    }
+}
+
+/// Open an ISO read-only, like [`open_game`].
+fn open_game_readonly(path: &Path, offsets: Option<&Offsets>) -> alx::Result<GameRoot> {
+    match offsets {
+        Some(offsets) => GameRoot::open_readonly_with_offsets(path, offsets.clone()),
+        None => GameRoot::open_readonly(path),
+    }
+}
+
+/// Serialize a dump to JSON, pretty-printed unless `compact` is set.
+fn to_json<T: serde::Serialize>(value: &T, compact: bool) -> serde_json::Result<String> {
+    if compact {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    }
+}
+
+/// Write an IPS patch turning the pristine ISO's Start.dol into the modified one's.
+fn run_make_patch(
+    iso_path: &Path,
+    pristine_path: &Path,
+    output: Option<&Path>,
+    offsets: Option<&Offsets>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !pristine_path.exists() {
+        return Err(format!("Pristine ISO not found: {}", pristine_path.display()).into());
+    }
+
+    let mut modified = open_game_readonly(iso_path, offsets)?;
+    let mut pristine = open_game_readonly(pristine_path, offsets)?;
+    if modified.version().product_id != pristine.version().product_id {
+        return Err(format!(
+            "Version mismatch: {} is {}, but {} is {}",
+            iso_path.display(),
+            modified.version().product_id,
+            pristine_path.display(),
+            pristine.version().product_id
+        )
+        .into());
+    }
+
+    let patch = alx::patch::make_ips(pristine.load_dol()?, modified.load_dol()?)?;
+
+    let output_path = match output {
+        Some(path) => path.to_path_buf(),
+        None => iso_path.with_extension("ips"),
+    };
+    fs::write(&output_path, &patch)?;
+
+    println!(
+        "Wrote {} ({} bytes) from {}",
+        output_path.display(),
+        patch.len(),
+        pristine_path.display()
+    );
+    Ok(())
+}
+
+/// Print a summary of an ISO without writing anything.
+fn run_examine(
+    iso_path: &Path,
+    offsets: Option<&Offsets>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use alx::game::dump_quality::dol_crc32;
+
+    let mut game = open_game_readonly(iso_path, offsets)?;
+    let version = game.version().clone();
+
+    println!("ISO: {}", iso_path.display());
+    println!("Version: {}", version);
+    println!("Region: {} ({})", version.region, version.platform);
+
+    let dol = game.load_dol()?;
+    println!("DOL: {} bytes, CRC32 {:08X}", dol.len(), dol_crc32(dol));
+    println!("Dump: {}", game.dump_quality());
+    println!("ENP files: {}", game.list_enp_files()?.len());
+
+    let mut counts: Vec<(String, usize)> = game.entry_counts()?.into_iter().collect();
+    counts.sort();
+    println!("\nEntry counts:");
+    for (name, count) in counts {
+        println!("  {}: {}", name, count);
+    }
+    Ok(())
+}
+
+/// Print each ENP file's sizes and enemy count.
+fn run_list_enp(
+    iso_path: &Path,
+    offsets: Option<&Offsets>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use alx::io::{decompress_aklz, split_enp_enemy_records};
+
+    let mut game = open_game_readonly(iso_path, offsets)?;
+    let entries = game.enp_file_entries()?;
+
+    println!(
+        "{:<20} {:>12} {:>14} {:>8}",
+        "File", "Compressed", "Decompressed", "Enemies"
+    );
+    for (name, entry) in &entries {
+        let raw_data = game.read_file_direct(entry)?;
+        let data = decompress_aklz(&raw_data)?;
+        println!(
+            "{:<20} {:>12} {:>14} {:>8}",
+            name,
+            raw_data.len(),
+            data.len(),
+            split_enp_enemy_records(&data).len()
+        );
+    }
+    println!("\n{} ENP files", entries.len());
+    Ok(())
+}
+
+/// Write `<kind>.schema.json` for every entry kind, plus
+/// `game_data.schema.json`, into `dir`.
+fn run_emit_schema(dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(dir)?;
+
+    for kind in EntryKind::ALL {
+        let path = dir.join(format!("{}.schema.json", kind.name()));
+        fs::write(&path, serde_json::to_string_pretty(&kind.json_schema())?)?;
+    }
+
+    fs::write(
+        dir.join("game_data.schema.json"),
+        serde_json::to_string_pretty(&alx::game::GameData::json_schema())?,
+    )?;
+
+    println!(
+        "Wrote {} schemas to {}",
+        EntryKind::ALL.len() + 1,
+        dir.display()
+    );
+    Ok(())
+}
+
+/// Compress or decompress a loose AKLZ file on disk.
+fn run_aklz_file(
+    input: &Path,
+    output: Option<&Path>,
+    compress: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use alx::io::{compress_aklz, decompress_aklz, is_aklz};
+
+    if !input.exists() {
+        return Err(format!("File not found: {}", input.display()).into());
+    }
+
+    let data = fs::read(input)?;
+
+    let (result, default_ext) = if compress {
+        if is_aklz(&data) {
+            println!("Warning: {} is already AKLZ compressed", input.display());
+        }
+        (compress_aklz(&data), "aklz")
+    } else {
+        if !is_aklz(&data) {
+            return Err(format!("Not an AKLZ file: {}", input.display()).into());
+        }
+        (decompress_aklz(&data)?, "dec")
+    };
+
+    let output_path = match output {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let mut name = input.as_os_str().to_os_string();
+            name.push(".");
+            name.push(default_ext);
+            PathBuf::from(name)
+        }
+    };
+
+    fs::write(&output_path, &result)?;
+    println!(
+        "{} {} ({} bytes) -> {} ({} bytes)",
+        if compress {
+            "Compressed"
+        } else {
+            "Decompressed"
+        },
+        input.display(),
+        data.len(),
+        output_path.display(),
+        result.len()
+    );
+
+    Ok(())
+}
+
+fn run_export(
+    iso_path: &Path,
+    output: Option<PathBuf>,
+    options: &ExportOptions,
+    offsets: Option<&Offsets>,
+) -> Result<(), Box<dyn std::error::Error>> {
     // Determine output directory
     let output_dir = match output {
         Some(path) => path,
@@ -126,7 +552,7 @@ fn run_export(iso_path: &Path, output: Option<PathBuf>) -> Result<(), Box<dyn st
 
     // Open the game
     println!("Loading game data...");
-    let mut game = GameRoot::open(iso_path)?;
+    let mut game = open_game(iso_path, offsets)?;
 
     println!(
         "Detected: {} ({})",
@@ -140,7 +566,7 @@ fn run_export(iso_path: &Path, output: Option<PathBuf>) -> Result<(), Box<dyn st
     println!();
 
     // Export all data types
-    export_all(&mut game, &output_dir)?;
+    export_all(&mut game, &output_dir, options)?;
 
     println!();
     println!("Export complete!");
@@ -152,6 +578,8 @@ fn run_dump_enp(
     iso_path: &Path,
     enp_name: &str,
     output_path: Option<&Path>,
+    compact_json: bool,
+    offsets: Option<&Offsets>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use alx::io::{decompress_aklz, dump_enp_editable};
 
@@ -161,7 +589,7 @@ fn run_dump_enp(
     println!("ENP: {}", enp_name);
 
     // Load ISO
-    let mut game = GameRoot::open(iso_path)?;
+    let mut game = open_game(iso_path, offsets)?;
     println!("Detected: {:?}", game.version());
 
     // Build item database for item name lookups
@@ -184,7 +612,7 @@ fn run_dump_enp(
             .map(|s| s.to_string_lossy().to_string())
             .unwrap_or_default();
 
-        if !filename.ends_with(".enp") {
+        if !EnemyFileKind::classify(&filename).is_some_and(EnemyFileKind::is_enp) {
             continue;
         }
 
@@ -202,10 +630,11 @@ fn run_dump_enp(
         println!("  Decompressed size: {} bytes", data.len());
 
         // Dump the structure using simplified editable format
-        let dump = dump_enp_editable(&data, &filename, game.version(), &item_db)?;
+        let mut dump = dump_enp_editable(&data, &filename, game.version(), &item_db)?;
+        dump.meta.compressed_size = Some(raw_data.len());
 
         // Convert to JSON
-        let json = serde_json::to_string_pretty(&dump)?;
+        let json = to_json(&dump, compact_json)?;
 
         // Output
         if let Some(output) = output_path {
@@ -233,27 +662,37 @@ fn run_dump_enp(
 
 fn run_dump_evp(
     iso_path: &Path,
+    evp_name: &str,
     output_path: Option<&Path>,
+    compact_json: bool,
+    offsets: Option<&Offsets>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use alx::io::{decompress_aklz, dump_evp_editable};
 
     println!("ALX_RS - EVP File Dumper");
     println!("========================");
     println!("ISO: {}", iso_path.display());
+    println!("EVP: {}", evp_name);
 
     // Load ISO
-    let mut game = GameRoot::open(iso_path)?;
+    let mut game = open_game(iso_path, offsets)?;
     println!("Detected: {:?}", game.version());
 
     // Build item database for item name lookups
     let item_db = game.build_item_database()?;
 
     // Find the EVP file
-    let matching_files = game.iso_mut().list_files_matching("epevent.evp")?;
+    let matching_files = game.iso_mut().list_files_matching(evp_name)?;
 
     let entry = matching_files
-        .first()
-        .ok_or("EVP file (epevent.evp) not found")?;
+        .iter()
+        .find(|entry| {
+            entry
+                .path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("evp"))
+        })
+        .ok_or_else(|| format!("EVP file not found: {}", evp_name))?;
 
     let filename = entry
         .path
@@ -275,7 +714,7 @@ fn run_dump_evp(
     println!("  Events: {}", dump.events.len());
 
     // Convert to JSON
-    let json = serde_json::to_string_pretty(&dump)?;
+    let json = to_json(&dump, compact_json)?;
 
     // Output
     if let Some(output) = output_path {
@@ -298,14 +737,19 @@ fn run_import(
     import_dir: &Path,
     output_iso: Option<&Path>,
     auto_confirm: bool,
+    offsets: Option<&Offsets>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Validate import directory
     if !import_dir.exists() {
         return Err(format!("Import directory not found: {}", import_dir.display()).into());
     }
 
-    // Determine the target ISO path
-    let target_iso = if let Some(output_path) = output_iso {
+    if let Some(output) = output_iso {
+        let output_path = &output_iso_path(iso_path, output);
+        if let Some(dir) = output_path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+
         // Check if output already exists
         if output_path.exists() && !auto_confirm {
             println!("Output file already exists: {}", output_path.display());
@@ -315,7 +759,6 @@ fn run_import(
             }
         }
 
-        // Copy the original ISO to the output path first
         println!("ALX_RS - Skies of Arcadia Data Importer");
         println!("========================================");
         println!("Source ISO: {}", iso_path.display());
@@ -323,15 +766,19 @@ fn run_import(
         println!("Import from: {}", import_dir.display());
         println!();
 
-        println!("Copying ISO to output path...");
-        fs::copy(iso_path, output_path)?;
-        println!(
-            "  Copy complete ({:.1} GB)",
-            fs::metadata(output_path)?.len() as f64 / 1_000_000_000.0
-        );
-        println!();
+        // Work on a temporary sibling and only rename it over the output once
+        // everything succeeded, so an interrupted run never leaves a broken ISO
+        alx::io::write_atomically(output_path, |temp_iso| {
+            println!("Copying ISO to output path...");
+            fs::copy(iso_path, temp_iso)?;
+            println!(
+                "  Copy complete ({:.1} GB)",
+                fs::metadata(temp_iso)?.len() as f64 / 1_000_000_000.0
+            );
+            println!();
 
-        output_path.to_path_buf()
+            import_into_iso(temp_iso, import_dir, offsets)
+        })?;
     } else {
         // Modifying original ISO - require confirmation
         println!("ALX_RS - Skies of Arcadia Data Importer");
@@ -353,386 +800,365 @@ fn run_import(
             println!();
         }
 
-        iso_path.to_path_buf()
-    };
-
-    // Open the game
-    println!("Loading game data...");
-    let mut game = GameRoot::open(&target_iso)?;
-
-    println!(
-        "Detected: {} ({})",
-        game.version().region,
-        if game.version().is_gc() {
-            "GameCube"
-        } else {
-            "Unknown"
+        let mut game = open_import_target(iso_path, offsets)?;
+        if let Err(e) = import_into_game(&mut game, import_dir) {
+            if game.has_written_iso() {
+                eprintln!("The ISO may have been partly written. Restore it from a backup,");
+                eprintln!("and use --output to write to a copy instead.");
+            }
+            return Err(e);
         }
-    );
-    println!();
-
-    // Import all data types
-    import_all(&mut game, import_dir)?;
-
-    // Save changes to ISO
-    println!();
-    println!("Saving changes to ISO...");
-    game.save_dol()?;
-    game.save_level()?;
+    }
 
     println!("Import complete!");
 
     Ok(())
 }
 
-/// Import a CSV file if it exists, returning the parsed data.
-/// This version doesn't need existing data (for types where CSV has all fields).
-macro_rules! import_csv {
-    ($import_dir:expr, $filename:expr, $import_fn:ident, $type_name:expr) => {{
-        let path = $import_dir.join($filename);
-        if path.exists() {
-            print!("Importing {}...", $type_name);
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let result = CsvImporter::$import_fn(reader);
-            match result {
-                Ok(data) => {
-                    println!(" {} entries", data.len());
-                    Some(data)
-                }
-                Err(e) => {
-                    println!(" ERROR: {}", e);
-                    return Err(format!("Failed to import {}: {}", $type_name, e).into());
-                }
-            }
-        } else {
-            println!("Skipping {} (file not found)", $type_name);
-            None
-        }
-    }};
+/// Get the ISO path an import writes to for `--output`.
+///
+/// An existing directory, or a path ending in a path separator, is taken as
+/// the folder to write `<source stem>-randomized.iso` into. Anything else is
+/// the ISO path itself, with or without an extension.
+fn output_iso_path(source: &Path, output: &Path) -> PathBuf {
+    let is_dir = output.is_dir()
+        || output
+            .as_os_str()
+            .to_string_lossy()
+            .ends_with(std::path::is_separator);
+    if is_dir {
+        let stem = source.file_stem().unwrap_or_default().to_string_lossy();
+        output.join(format!("{}-randomized.iso", stem))
+    } else {
+        output.to_path_buf()
+    }
 }
 
-fn import_all(game: &mut GameRoot, import_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    // Ensure DOL is loaded before any writes
-    game.load_dol()?;
-
-    // Import accessories
-    if let Some(data) = import_csv!(
-        import_dir,
-        "accessory.csv",
-        import_accessories,
-        "accessories"
-    ) {
-        game.write_accessories(&data)?;
-    }
-
-    // Import armors
-    if let Some(data) = import_csv!(import_dir, "armor.csv", import_armors, "armors") {
-        game.write_armors(&data)?;
-    }
-
-    // Import weapons
-    if let Some(data) = import_csv!(import_dir, "weapon.csv", import_weapons, "weapons") {
-        game.write_weapons(&data)?;
-    }
-
-    // Import usable items (merge with existing)
-    {
-        let path = import_dir.join("usableitem.csv");
-        if path.exists() {
-            print!("Importing usable items...");
-            let existing = game.read_usable_items()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_usable_items(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_usable_items(&data)?;
-        } else {
-            println!("Skipping usable items (file not found)");
-        }
+/// Randomize the ISO at `iso_path` and save it to `output`.
+#[allow(clippy::too_many_arguments)]
+fn run_randomize(
+    iso_path: &Path,
+    output: &Path,
+    config_path: Option<&Path>,
+    seed: Option<u64>,
+    spoiler_path: Option<&Path>,
+    verify_string: bool,
+    auto_confirm: bool,
+    offsets: Option<&Offsets>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use alx::randomizer::RandomizerConfig;
+
+    let mut config = match config_path {
+        Some(path) => serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| format!("Invalid config {}: {}", path.display(), e))?,
+        None => RandomizerConfig {
+            seed: None,
+            ..RandomizerConfig::chaos(0)
+        },
+    };
+    if seed.is_some() {
+        config.seed = seed;
     }
 
-    // Import special items
-    if let Some(data) = import_csv!(
-        import_dir,
-        "specialitem.csv",
-        import_special_items,
-        "special items"
-    ) {
-        game.write_special_items(&data)?;
-    }
-
-    // Import characters (merge with existing - CSV only has subset of fields)
-    {
-        let path = import_dir.join("character.csv");
-        if path.exists() {
-            print!("Importing characters...");
-            let existing = game.read_characters()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_characters(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_characters(&data)?;
-        } else {
-            println!("Skipping characters (file not found)");
+    if output.exists() && !auto_confirm {
+        println!("Output file already exists: {}", output.display());
+        if !confirm_overwrite()? {
+            println!("Aborted.");
+            return Ok(());
         }
     }
 
-    // Import character magic (merge with existing)
-    {
-        let path = import_dir.join("charactermagic.csv");
-        if path.exists() {
-            print!("Importing character magic...");
-            let existing = game.read_character_magic()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_character_magic(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_character_magic(&data)?;
-        } else {
-            println!("Skipping character magic (file not found)");
-        }
+    println!("ALX_RS - Skies of Arcadia Randomizer");
+    println!("========================================");
+    println!("Source ISO: {}", iso_path.display());
+    println!("Output ISO: {}", output.display());
+    println!();
+
+    let mut game = open_game_readonly(iso_path, offsets)?;
+    let (spoiler, summary) = game.save_as_randomized(&config, output)?;
+
+    println!("{}", summary);
+    if let Some(path) = spoiler_path {
+        fs::write(path, spoiler.to_string())?;
+        println!("Spoiler log: {}", path.display());
+    }
+    if verify_string {
+        println!("Verification: {}", spoiler.verification_string());
     }
 
-    // Import character super moves (merge with existing)
-    {
-        let path = import_dir.join("charactersupermove.csv");
-        if path.exists() {
-            print!("Importing character super moves...");
-            let existing = game.read_character_super_moves()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_character_super_moves(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_character_super_moves(&data)?;
-        } else {
-            println!("Skipping character super moves (file not found)");
-        }
+    println!("Randomize complete!");
+    Ok(())
+}
+
+/// Check an import folder without writing to the ISO.
+///
+/// Every CSV is parsed and every entry checked as the importer would, and
+/// ENP/EVP JSON files are built in memory. All problems are printed; the
+/// run fails if there were any.
+fn run_validate(
+    iso_path: &Path,
+    import_dir: &Path,
+    offsets: Option<&Offsets>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !import_dir.exists() {
+        return Err(format!("Import directory not found: {}", import_dir.display()).into());
     }
 
-    // Import shops (merge with existing)
-    {
-        let path = import_dir.join("shop.csv");
-        if path.exists() {
-            print!("Importing shops...");
-            let existing = game.read_shops()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_shops(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_shops(&data)?;
-        } else {
-            println!("Skipping shops (file not found)");
-        }
+    let mut game = open_game_readonly(iso_path, offsets)?;
+    println!("Validating {}...", import_dir.display());
+
+    let layout = ExportLayout::detect(import_dir);
+    let mut errors = validate_csvs(&mut game, &layout.csv_dir(import_dir))?;
+    errors.extend(validate_enemy_json(
+        &mut game,
+        &layout.json_dir(import_dir),
+    )?);
+
+    if errors.is_empty() {
+        println!("No problems found.");
+        return Ok(());
+    }
+    for error in &errors {
+        println!("  {}", error);
     }
+    Err(format!("{} problem(s) found", errors.len()).into())
+}
 
-    // Import treasure chests
-    if let Some(data) = import_csv!(
-        import_dir,
-        "treasurechest.csv",
-        import_treasure_chests,
-        "treasure chests"
-    ) {
-        game.write_treasure_chests(&data)?;
-    }
-
-    // Import crew members (merge with existing)
-    {
-        let path = import_dir.join("crewmember.csv");
-        if path.exists() {
-            print!("Importing crew members...");
-            let existing = game.read_crew_members()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_crew_members(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_crew_members(&data)?;
-        } else {
-            println!("Skipping crew members (file not found)");
+/// Import every CSV in `csv_dir` over the game's data and run the write
+/// checks on the result.
+fn validate_csvs(
+    game: &mut GameRoot,
+    csv_dir: &Path,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut data = game.read_game_data()?;
+    let mut errors = Vec::new();
+
+    for kind in EntryKind::ALL {
+        let path = csv_dir.join(kind.csv_filename());
+        if kind.is_enemy_file_data() || !path.exists() {
+            continue;
+        }
+        let reader = BufReader::new(File::open(&path)?);
+        if let Err(e) = CsvImporter::import_kind(kind, reader, &mut data) {
+            errors.push(format!("{}: {}", kind.csv_filename(), e));
         }
     }
 
-    // Import playable ships (merge with existing)
-    {
-        let path = import_dir.join("playableship.csv");
-        if path.exists() {
-            print!("Importing playable ships...");
-            let existing = game.read_playable_ships()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_playable_ships(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_playable_ships(&data)?;
-        } else {
-            println!("Skipping playable ships (file not found)");
-        }
+    errors.extend(data.validate_for_write().iter().map(ToString::to_string));
+    Ok(errors)
+}
+
+/// Build every ENP/EVP JSON dump under `json_dir` in memory.
+fn validate_enemy_json(
+    game: &mut GameRoot,
+    json_dir: &Path,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    use alx::io::{
+        build_enp, build_evp, find_missing_enemy_references, EncounterListDefinition,
+        EnpDefinition, EvpDefinition,
+    };
+
+    let enp_dir = json_dir.join("enp");
+    let encounters_dir = json_dir.join("encounters");
+    let evp_file = json_dir.join("evp").join("epevent.evp.json");
+    let mut errors = Vec::new();
+    if !enp_dir.exists() && !encounters_dir.exists() && !evp_file.exists() {
+        return Ok(errors);
     }
 
-    // Import ship cannons (merge with existing)
-    {
-        let path = import_dir.join("shipcannon.csv");
-        if path.exists() {
-            print!("Importing ship cannons...");
-            let existing = game.read_ship_cannons()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_ship_cannons(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_ship_cannons(&data)?;
-        } else {
-            println!("Skipping ship cannons (file not found)");
+    let item_db = game.build_item_database()?;
+    let global_db = game.build_global_enemy_database()?;
+
+    // Full definitions from enp/, then encounter lists from encounters/
+    // applied to the file's own definition, as the import does
+    let mut defs = Vec::new();
+    for path in json_files(&enp_dir)? {
+        match serde_json::from_str::<EnpDefinition>(&fs::read_to_string(&path)?) {
+            Ok(def) => defs.push((path, def)),
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
         }
     }
-
-    // Import ship accessories (merge with existing)
-    {
-        let path = import_dir.join("shipaccessory.csv");
-        if path.exists() {
-            print!("Importing ship accessories...");
-            let existing = game.read_ship_accessories()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_ship_accessories(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_ship_accessories(&data)?;
-        } else {
-            println!("Skipping ship accessories (file not found)");
+    for path in json_files(&encounters_dir)? {
+        let def = serde_json::from_str::<EncounterListDefinition>(&fs::read_to_string(&path)?)
+            .map_err(|e| e.to_string())
+            .and_then(|list| {
+                let mut def = read_enp_definition(game, &list.filename, &item_db)
+                    .map_err(|e| format!("reading original {}: {}", list.filename, e))?;
+                def.apply_encounters(list).map_err(|e| e.to_string())?;
+                Ok(def)
+            });
+        match def {
+            Ok(def) => defs.push((path, def)),
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
         }
     }
 
-    // Import ship items (merge with existing)
-    {
-        let path = import_dir.join("shipitem.csv");
-        if path.exists() {
-            print!("Importing ship items...");
-            let existing = game.read_ship_items()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_ship_items(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_ship_items(&data)?;
-        } else {
-            println!("Skipping ship items (file not found)");
+    for (path, def) in defs {
+        let name = path.display();
+        let file_db = match game.build_enemy_database_for_file(&def.filename) {
+            Ok(db) => db,
+            Err(e) => {
+                errors.push(format!("{}: {}", name, e));
+                continue;
+            }
+        };
+        if let Some(missing) = find_missing_enemy_references(&def, &file_db, Some(&global_db)) {
+            errors.push(format!("{}: {}", name, missing));
+        } else if let Err(e) = build_enp(&def, &file_db, Some(&global_db), &item_db) {
+            errors.push(format!("{}: {}", name, e));
         }
     }
 
-    // Import enemy ships (merge with existing)
-    {
-        let path = import_dir.join("enemyship.csv");
-        if path.exists() {
-            print!("Importing enemy ships...");
-            let existing = game.read_enemy_ships()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_enemy_ships(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_enemy_ships(&data)?;
-        } else {
-            println!("Skipping enemy ships (file not found)");
+    if evp_file.exists() {
+        let name = evp_file.display();
+        match serde_json::from_str::<EvpDefinition>(&fs::read_to_string(&evp_file)?) {
+            Ok(def) => {
+                let file_db = game.build_enemy_database_for_evp()?;
+                if let Err(e) = build_evp(&def, &file_db, Some(&global_db), &item_db) {
+                    errors.push(format!("{}: {}", name, e));
+                }
+            }
+            Err(e) => errors.push(format!("{}: {}", name, e)),
         }
     }
 
-    // Import enemy magic (merge with existing)
-    {
-        let path = import_dir.join("enemymagic.csv");
-        if path.exists() {
-            print!("Importing enemy magic...");
-            let existing = game.read_enemy_magic()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_enemy_magic(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_enemy_magic(&data)?;
+    Ok(errors)
+}
+
+/// Open the ISO at `target_iso`, import all CSVs into it and save.
+fn import_into_iso(
+    target_iso: &Path,
+    import_dir: &Path,
+    offsets: Option<&Offsets>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut game = open_import_target(target_iso, offsets)?;
+    import_into_game(&mut game, import_dir)
+}
+
+/// Open the ISO an import writes to.
+fn open_import_target(
+    target_iso: &Path,
+    offsets: Option<&Offsets>,
+) -> Result<GameRoot, Box<dyn std::error::Error>> {
+    println!("Loading game data...");
+    let mut game = open_game(target_iso, offsets)?;
+
+    println!(
+        "Detected: {} ({})",
+        game.version().region,
+        if game.version().is_gc() {
+            "GameCube"
         } else {
-            println!("Skipping enemy magic (file not found)");
+            "Unknown"
         }
-    }
+    );
+    println!();
+    Ok(game)
+}
 
-    // Import enemy super moves (merge with existing)
-    {
-        let path = import_dir.join("enemysupermove.csv");
-        if path.exists() {
-            print!("Importing enemy super moves...");
-            let existing = game.read_enemy_super_moves()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_enemy_super_moves(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_enemy_super_moves(&data)?;
-        } else {
-            println!("Skipping enemy super moves (file not found)");
+/// Import `import_dir` into `game` and save the changes to its ISO.
+fn import_into_game(
+    game: &mut GameRoot,
+    import_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    import_all(game, import_dir)?;
+
+    // Save changes to ISO
+    println!();
+    println!("Saving changes to ISO...");
+    game.save_dol()?;
+    game.save_level()?;
+
+    Ok(())
+}
+
+/// Turn a `FeatureUnavailable` error into `None` (printing why the entry
+/// type is skipped) so one missing file doesn't abort the whole run.
+fn skip_if_unavailable<T>(
+    result: alx::Result<T>,
+    kind: EntryKind,
+    bar: &ProgressBar,
+) -> Result<Option<T>, Box<dyn std::error::Error>> {
+    match result {
+        Ok(data) => Ok(Some(data)),
+        Err(alx::Error::FeatureUnavailable { reason, .. }) => {
+            bar.suspend(|| println!("Skipping {} ({})", kind.label(), reason));
+            Ok(None)
         }
+        Err(e) => Err(e.into()),
     }
+}
 
-    // Note: Enemy encounters are now imported via ENP JSON files, not CSV
-    // The CSV export is kept for reference/documentation purposes
-
-    // Import swashbucklers
-    if let Some(data) = import_csv!(
-        import_dir,
-        "swashbuckler.csv",
-        import_swashbucklers,
-        "swashbucklers"
-    ) {
-        game.write_swashbucklers(&data)?;
-    }
-
-    // Import spirit curves
-    if let Some(data) = import_csv!(
-        import_dir,
-        "spiritcurve.csv",
-        import_spirit_curves,
-        "spirit curves"
-    ) {
-        game.write_spirit_curves(&data)?;
-    }
-
-    // Import exp boosts
-    if let Some(data) = import_csv!(import_dir, "expboost.csv", import_exp_boosts, "exp boosts") {
-        game.write_exp_boosts(&data)?;
-    }
-
-    // Import EXP curves (from level file)
-    {
-        let path = import_dir.join("expcurve.csv");
-        if path.exists() {
-            print!("Importing exp curves...");
-            // Need to load level file first
-            game.load_level_file()?;
-            let existing = game.read_exp_curves()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_exp_curves(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_exp_curves(&data)?;
-        } else {
-            println!("Skipping exp curves (file not found)");
+/// Progress bar for a run over `total` entry kinds, drawn on stderr.
+fn progress_bar(total: usize) -> ProgressBar {
+    let bar = ProgressBar::new(total as u64);
+    bar.set_style(
+        ProgressStyle::with_template("{bar:30} {pos}/{len} {msg}")
+            .expect("valid progress template"),
+    );
+    bar
+}
+
+/// Move the progress bar to `progress`.
+fn show_progress(bar: &ProgressBar, progress: &ExportProgress) {
+    bar.set_position(progress.index as u64);
+    let eta = match progress.eta_secs {
+        Some(secs) => format!(", ~{:.0}s left", secs),
+        None => String::new(),
+    };
+    bar.set_message(format!(
+        "{} ({} entries{})",
+        progress.stage, progress.entries_done, eta
+    ));
+}
+
+fn import_all(game: &mut GameRoot, import_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    // Ensure DOL is loaded before any writes
+    game.load_dol()?;
+
+    let layout = ExportLayout::detect(import_dir);
+    let csv_dir = layout.csv_dir(import_dir);
+    let json_dir = layout.json_dir(import_dir);
+
+    // Enemy data is imported via ENP/EVP JSON files, not CSV.
+    // The CSV export is kept for reference/documentation purposes.
+    let kinds: Vec<EntryKind> = EntryKind::ALL
+        .into_iter()
+        .filter(|kind| !kind.is_enemy_file_data())
+        .collect();
+    let mut tracker = ProgressTracker::new(kinds.len());
+    let bar = progress_bar(kinds.len());
+    let mut data = GameData::default();
+
+    for (index, kind) in kinds.into_iter().enumerate() {
+        show_progress(&bar, &tracker.progress(kind.label(), index));
+        let path = csv_dir.join(kind.csv_filename());
+        if !path.exists() {
+            bar.suspend(|| println!("Skipping {} (file not found)", kind.label()));
+            continue;
         }
-    }
 
-    // Import Magic EXP curves (from level file)
-    {
-        let path = import_dir.join("magicexpcurve.csv");
-        if path.exists() {
-            print!("Importing magic exp curves...");
-            // Need to load level file first (may already be loaded)
-            game.load_level_file()?;
-            let existing = game.read_magic_exp_curves()?;
-            let file = File::open(&path)?;
-            let reader = BufReader::new(file);
-            let data = CsvImporter::import_magic_exp_curves(reader, &existing)?;
-            println!(" {} entries", data.len());
-            game.write_magic_exp_curves(&data)?;
-        } else {
-            println!("Skipping magic exp curves (file not found)");
+        // Merge importers keep the game's values for what the CSV leaves
+        // out; the level file tables are skipped if it is missing
+        if skip_if_unavailable(game.read_kind(kind, &mut data), kind, &bar)?.is_none() {
+            continue;
         }
+        let reader = BufReader::new(File::open(&path)?);
+        let count = CsvImporter::import_kind(kind, reader, &mut data)
+            .map_err(|e| format!("Failed to import {}: {}", kind.label(), e))?;
+        game.write_kind(kind, &data)?;
+
+        tracker.add_entries(count);
+        bar.suspend(|| println!("Imported {}: {} entries", kind.label(), count));
     }
+    show_progress(&bar, &tracker.done());
+    bar.finish_and_clear();
 
     // Import ENP files from JSON
-    import_enp_files(game, import_dir)?;
+    import_enp_files(game, &json_dir)?;
 
     // Import EVP file from JSON
-    import_evp_file(game, import_dir)?;
+    import_evp_file(game, &json_dir)?;
 
     Ok(())
 }
@@ -810,12 +1236,13 @@ fn import_enp_files(
     import_dir: &Path,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use alx::io::{
-        bake_enp_segments, build_enp, decompress_aklz, EnpDefinition, A099A_BAKED_FILENAME,
-        A099A_SEGMENTS,
+        build_enp, find_missing_enemy_references, CompressionMode, EncounterListDefinition,
+        EnpDefinition, A099A_BAKED_FILENAME, A099A_SEGMENTS,
     };
 
     let enp_dir = import_dir.join("enp");
-    if !enp_dir.exists() {
+    let encounters_dir = import_dir.join("encounters");
+    if !enp_dir.exists() && !encounters_dir.exists() {
         println!("Skipping ENP files (enp/ directory not found)");
         return Ok(());
     }
@@ -833,59 +1260,86 @@ fn import_enp_files(
     // Track if any a099a files were imported (need rebaking)
     let mut a099a_imported = false;
 
-    // Find all JSON files in enp directory
     let mut count = 0;
     let mut errors = 0;
 
-    for entry in std::fs::read_dir(&enp_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.extension().map(|e| e == "json").unwrap_or(false) {
-            // Read and parse JSON
-            let json_content = std::fs::read_to_string(&path)?;
-            let def: EnpDefinition = match serde_json::from_str(&json_content) {
-                Ok(d) => d,
-                Err(e) => {
-                    eprintln!("\n  Error parsing {}: {}", path.display(), e);
-                    errors += 1;
-                    continue;
-                }
-            };
+    // Full definitions from enp/
+    let mut defs = Vec::new();
+    for path in json_files(&enp_dir)? {
+        match serde_json::from_str::<EnpDefinition>(&fs::read_to_string(&path)?) {
+            Ok(def) => defs.push(def),
+            Err(e) => {
+                eprintln!("\n  Error parsing {}: {}", path.display(), e);
+                errors += 1;
+            }
+        }
+    }
 
-            // Check if this is an a099a segment file
-            if A099A_SEGMENTS.contains(&def.filename.as_str()) {
-                a099a_imported = true;
+    // Encounter-only definitions from encounters/ keep the file's own enemies
+    for path in json_files(&encounters_dir)? {
+        let list: EncounterListDefinition = match serde_json::from_str(&fs::read_to_string(&path)?)
+        {
+            Ok(list) => list,
+            Err(e) => {
+                eprintln!("\n  Error parsing {}: {}", path.display(), e);
+                errors += 1;
+                continue;
+            }
+        };
+        let filename = list.filename.clone();
+        let def = read_enp_definition(game, &filename, &item_db).and_then(|mut def| {
+            def.apply_encounters(list)?;
+            Ok(def)
+        });
+        match def {
+            Ok(def) => defs.push(def),
+            Err(e) => {
+                eprintln!("\n  Error reading original {}: {}", filename, e);
+                errors += 1;
             }
+        }
+    }
 
-            // Build enemy database from THIS specific ENP file's original data
-            let file_db = match game.build_enemy_database_for_file(&def.filename) {
-                Ok(db) => db,
-                Err(e) => {
-                    eprintln!("\n  Error reading original {}: {}", def.filename, e);
-                    errors += 1;
-                    continue;
-                }
-            };
+    for def in defs {
+        // Check if this is an a099a segment file
+        if A099A_SEGMENTS.contains(&def.filename.as_str()) {
+            a099a_imported = true;
+        }
 
-            // Build the ENP file with patched data
-            // Uses file-specific DB first, then falls back to global DB for "stolen" enemies
-            let enp_data = match build_enp(&def, &file_db, Some(&global_db), &item_db) {
-                Ok(d) => d,
-                Err(e) => {
-                    eprintln!("\n  Error building {}: {}", def.filename, e);
-                    errors += 1;
-                    continue;
-                }
-            };
+        // Build enemy database from THIS specific ENP file's original data
+        let file_db = match game.build_enemy_database_for_file(&def.filename) {
+            Ok(db) => db,
+            Err(e) => {
+                eprintln!("\n  Error reading original {}: {}", def.filename, e);
+                errors += 1;
+                continue;
+            }
+        };
 
-            // Write back to ISO
-            match game.write_enp_file(&def.filename, &enp_data) {
-                Ok(()) => count += 1,
-                Err(e) => {
-                    eprintln!("\n  Error writing {}: {}", def.filename, e);
-                    errors += 1;
-                }
+        // Catch dangling encounter references up front so the report names them
+        if let Some(missing) = find_missing_enemy_references(&def, &file_db, Some(&global_db)) {
+            eprintln!("\n  Warning: {}", missing);
+            errors += 1;
+            continue;
+        }
+
+        // Build the ENP file with patched data
+        // Uses file-specific DB first, then falls back to global DB for "stolen" enemies
+        let enp_data = match build_enp(&def, &file_db, Some(&global_db), &item_db) {
+            Ok(d) => d,
+            Err(e) => {
+                eprintln!("\n  Error building {}: {}", def.filename, e);
+                errors += 1;
+                continue;
+            }
+        };
+
+        // Write back to ISO
+        match game.write_enp_file(&def.filename, &enp_data, CompressionMode::Auto) {
+            Ok(()) => count += 1,
+            Err(e) => {
+                eprintln!("\n  Error writing {}: {}", def.filename, e);
+                errors += 1;
             }
         }
     }
@@ -901,328 +1355,219 @@ fn import_enp_files(
         print!("Rebaking {}...", A099A_BAKED_FILENAME);
         std::io::Write::flush(&mut std::io::stdout())?;
 
-        // Read all 13 segment files from the ISO (they've just been updated)
-        let mut segments: Vec<(String, Vec<u8>)> = Vec::new();
-        let mut rebake_ok = true;
-
-        for seg_name in A099A_SEGMENTS {
-            // Find and read the segment file from ISO
-            match game.read_enp_file_raw(seg_name) {
-                Ok(compressed) => {
-                    // Decompress the segment
-                    match decompress_aklz(&compressed) {
-                        Ok(decompressed) => {
-                            segments.push((seg_name.to_string(), decompressed));
-                        }
-                        Err(e) => {
-                            eprintln!("\n  Error decompressing {}: {}", seg_name, e);
-                            rebake_ok = false;
-                            break;
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("\n  Error reading {}: {}", seg_name, e);
-                    rebake_ok = false;
-                    break;
-                }
-            }
-        }
-
-        if rebake_ok {
-            // Create segment references for baking
-            let segment_refs: Vec<(&str, &[u8])> = segments
-                .iter()
-                .map(|(name, data)| (name.as_str(), data.as_slice()))
-                .collect();
-
-            // Bake into multi-segment format
-            match bake_enp_segments(&segment_refs) {
-                Ok(baked) => {
-                    // Write to ISO (write_enp_file handles compression)
-                    match game.write_enp_file(A099A_BAKED_FILENAME, &baked) {
-                        Ok(()) => println!(" done ({} bytes uncompressed)", baked.len()),
-                        Err(e) => eprintln!("\n  Error writing {}: {}", A099A_BAKED_FILENAME, e),
-                    }
-                }
-                Err(e) => {
-                    eprintln!("\n  Error baking {}: {}", A099A_BAKED_FILENAME, e);
-                }
-            }
-        }
+        // Segments were just updated; on failure the baked file is untouched
+        let size = game.rebake_a099a()?;
+        println!(" done ({} bytes uncompressed)", size);
     }
 
     Ok(())
 }
 
-fn export_all(game: &mut GameRoot, output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    export_csv!(
-        game,
-        output_dir,
-        "accessories",
-        read_accessories,
-        export_accessories,
-        "accessory.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "armors",
-        read_armors,
-        export_armors,
-        "armor.csv"
-    );
-
-    // Weapons need weapon effects for effect name lookup
-    print!("Exporting weapons...");
-    let weapons = game.read_weapons()?;
-    let weapon_effects = game.read_weapon_effects()?;
-    CsvExporter::export_weapons(
-        &weapons,
-        File::create(output_dir.join("weapon.csv"))?,
-        &weapon_effects,
-    )?;
-    println!(" {} entries", weapons.len());
-    export_csv!(
-        game,
-        output_dir,
-        "usable items",
-        read_usable_items,
-        export_usable_items,
-        "usableitem.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "special items",
-        read_special_items,
-        export_special_items,
-        "specialitem.csv"
-    );
-
-    // Build item database early for lookups (characters, shops, treasure chests, and enemies need it)
-    let item_db = game.build_item_database()?;
+/// List the JSON files in `dir`, sorted. A missing directory has none.
+fn json_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    paths.retain(|path| path.extension().is_some_and(|e| e == "json"));
+    paths.sort();
+    Ok(paths)
+}
 
-    // Characters need item database for equipment name lookup
-    print!("Exporting characters...");
-    let characters = game.read_characters()?;
-    CsvExporter::export_characters(
-        &characters,
-        &item_db,
-        File::create(output_dir.join("character.csv"))?,
-    )?;
-    println!(" {} entries", characters.len());
+/// Read an ENP file from the ISO as an editable definition.
+fn read_enp_definition(
+    game: &mut GameRoot,
+    filename: &str,
+    item_db: &alx::items::ItemDatabase,
+) -> alx::Result<alx::io::EnpDefinition> {
+    use alx::io::{decompress_aklz, dump_enp_editable};
 
-    export_csv!(
-        game,
-        output_dir,
-        "character magic",
-        read_character_magic,
-        export_character_magic,
-        "charactermagic.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "character super moves",
-        read_character_super_moves,
-        export_character_super_moves,
-        "charactersupermove.csv"
-    );
+    let data = decompress_aklz(&game.read_enp_file_raw(filename)?)?;
+    dump_enp_editable(&data, filename, game.version(), item_db)
+}
 
-    // Shops need item database for item name lookup
-    print!("Exporting shops...");
-    let shops = game.read_shops()?;
-    CsvExporter::export_shops(&shops, File::create(output_dir.join("shop.csv"))?, &item_db)?;
-    println!(" {} entries", shops.len());
-
-    // Treasure chests need item database for item name lookup
-    print!("Exporting treasure chests...");
-    let chests = game.read_treasure_chests()?;
-    CsvExporter::export_treasure_chests(
-        &chests,
-        File::create(output_dir.join("treasurechest.csv"))?,
-        &item_db,
-    )?;
-    println!(" {} entries", chests.len());
+fn export_all(
+    game: &mut GameRoot,
+    output_dir: &Path,
+    options: &ExportOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let csv_dir = options.layout.csv_dir(output_dir);
+    let json_dir = options.layout.json_dir(output_dir);
+    fs::create_dir_all(&csv_dir)?;
 
-    export_csv!(
-        game,
-        output_dir,
-        "crew members",
-        read_crew_members,
-        export_crew_members,
-        "crewmember.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "playable ships",
-        read_playable_ships,
-        export_playable_ships,
-        "playableship.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "ship cannons",
-        read_ship_cannons,
-        export_ship_cannons,
-        "shipcannon.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "ship accessories",
-        read_ship_accessories,
-        export_ship_accessories,
-        "shipaccessory.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "ship items",
-        read_ship_items,
-        export_ship_items,
-        "shipitem.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "enemy ships",
-        read_enemy_ships,
-        export_enemy_ships,
-        "enemyship.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "enemy magic",
-        read_enemy_magic,
-        export_enemy_magic,
-        "enemymagic.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "enemy super moves",
-        read_enemy_super_moves,
-        export_enemy_super_moves,
-        "enemysupermove.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "swashbucklers",
-        read_swashbucklers,
-        export_swashbucklers,
-        "swashbuckler.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "spirit curves",
-        read_spirit_curves,
-        export_spirit_curves,
-        "spiritcurve.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "exp boosts",
-        read_exp_boosts,
-        export_exp_boosts,
-        "expboost.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "exp curves",
-        read_exp_curves,
-        export_exp_curves,
-        "expcurve.csv"
-    );
-    export_csv!(
-        game,
-        output_dir,
-        "magic exp curves",
-        read_magic_exp_curves,
-        export_magic_exp_curves,
-        "magicexpcurve.csv"
-    );
+    // Build item database up front for lookups (characters, shops, treasure chests, and enemies need it)
+    let item_db = game.build_item_database()?;
 
-    // Enemies (from ENP files) - special handling for two outputs
-    print!("Exporting enemies...");
+    // Enemies and their tasks are read together and shared by several kinds
     let (enemies, tasks) = game.read_enemies()?;
-    // Use US enemy names from vocabulary
-    let enemy_names = alx::lookups::enemy_names_map();
-    CsvExporter::export_enemies(
-        &enemies,
-        File::create(output_dir.join("enemy.csv"))?,
-        &item_db,
-        &enemy_names,
-    )?;
-
-    // Build lookups for enemy task names (magic and super moves)
-    let enemy_magic_data = game.read_enemy_magic()?;
-    let enemy_super_moves_data = game.read_enemy_super_moves()?;
-
-    let mut enemy_magic_names: std::collections::HashMap<u32, String> =
-        std::collections::HashMap::new();
-    for m in &enemy_magic_data {
-        enemy_magic_names.insert(m.id, m.name.clone());
-    }
-
-    let mut enemy_super_move_names: std::collections::HashMap<u32, String> =
-        std::collections::HashMap::new();
-    for s in &enemy_super_moves_data {
-        enemy_super_move_names.insert(s.id, s.name.clone());
-    }
-
-    CsvExporter::export_enemy_tasks(
-        &tasks,
-        &enemies,
-        &enemy_magic_names,
-        &enemy_super_move_names,
-        File::create(output_dir.join("enemytask.csv"))?,
-    )?;
-    println!(" {} enemies, {} tasks", enemies.len(), tasks.len());
 
-    // Enemy encounters (from ENP files)
-    print!("Exporting enemy encounters...");
-    let encounters = game.read_enemy_encounters()?;
-    // Build enemy name lookup map for encounters (id -> (jp_name, us_name))
+    // Build enemy name lookup map for encounters/events (id -> (jp_name, us_name))
     let mut encounter_enemy_names: std::collections::HashMap<u32, (String, String)> =
         std::collections::HashMap::new();
     for enemy in &enemies {
-        let us_name = enemy_names
-            .get(&enemy.id)
-            .cloned()
-            .unwrap_or_else(|| "???".to_string());
+        let us_name = alx::lookups::enemy_us_name(enemy.id).to_string();
         encounter_enemy_names.insert(enemy.id, (enemy.name_jp.clone(), us_name));
     }
-    CsvExporter::export_enemy_encounters(
-        &encounters,
-        File::create(output_dir.join("enemyencounter.csv"))?,
-        &encounter_enemy_names,
-    )?;
-    println!(" {} encounters", encounters.len());
-
-    // Enemy events (from EVP file - scripted battles)
-    print!("Exporting enemy events...");
-    let events = game.read_enemy_events()?;
-    CsvExporter::export_enemy_events(
-        &events,
-        File::create(output_dir.join("enemyevent.csv"))?,
-        &encounter_enemy_names,
-    )?;
-    println!(" {} events", events.len());
+
+    let mut tracker = ProgressTracker::new(EntryKind::ALL.len());
+    let bar = progress_bar(EntryKind::ALL.len());
+
+    for (index, kind) in EntryKind::ALL.into_iter().enumerate() {
+        show_progress(&bar, &tracker.progress(kind.label(), index));
+        let path = csv_dir.join(kind.csv_filename());
+
+        let count = match kind {
+            EntryKind::Accessory => export_csv!(game, path, read_accessories, export_accessories),
+            EntryKind::Armor => export_csv!(game, path, read_armors, export_armors),
+            EntryKind::Weapon => {
+                // Weapons need weapon effects for effect name lookup
+                let weapons = game.read_weapons()?;
+                let weapon_effects = game.read_weapon_effects()?;
+                CsvExporter::export_weapons(&weapons, File::create(path)?, &weapon_effects)?;
+                weapons.len()
+            }
+            EntryKind::UsableItem => {
+                export_csv!(game, path, read_usable_items, export_usable_items)
+            }
+            EntryKind::SpecialItem => {
+                export_csv!(game, path, read_special_items, export_special_items)
+            }
+            EntryKind::Character => {
+                // Characters need item database for equipment name lookup
+                let characters = game.read_characters()?;
+                CsvExporter::export_characters(&characters, &item_db, File::create(path)?)?;
+                characters.len()
+            }
+            EntryKind::CharacterMagic => {
+                export_csv!(game, path, read_character_magic, export_character_magic)
+            }
+            EntryKind::CharacterSuperMove => export_csv!(
+                game,
+                path,
+                read_character_super_moves,
+                export_character_super_moves
+            ),
+            EntryKind::Shop => {
+                // Shops need item database for item name lookup
+                let shops = game.read_shops()?;
+                CsvExporter::export_shops(&shops, File::create(path)?, &item_db)?;
+                shops.len()
+            }
+            EntryKind::TreasureChest => {
+                // Treasure chests need item database for item name lookup
+                let chests = game.read_treasure_chests()?;
+                CsvExporter::export_treasure_chests(&chests, File::create(path)?, &item_db)?;
+                chests.len()
+            }
+            EntryKind::CrewMember => {
+                export_csv!(game, path, read_crew_members, export_crew_members)
+            }
+            EntryKind::PlayableShip => {
+                export_csv!(game, path, read_playable_ships, export_playable_ships)
+            }
+            EntryKind::ShipCannon => {
+                export_csv!(game, path, read_ship_cannons, export_ship_cannons)
+            }
+            EntryKind::ShipAccessory => {
+                export_csv!(game, path, read_ship_accessories, export_ship_accessories)
+            }
+            EntryKind::ShipItem => export_csv!(game, path, read_ship_items, export_ship_items),
+            EntryKind::EnemyShip => export_csv!(game, path, read_enemy_ships, export_enemy_ships),
+            EntryKind::EnemyMagic => export_csv!(game, path, read_enemy_magic, export_enemy_magic),
+            EntryKind::EnemySuperMove => {
+                export_csv!(game, path, read_enemy_super_moves, export_enemy_super_moves)
+            }
+            EntryKind::Swashbuckler => {
+                export_csv!(game, path, read_swashbucklers, export_swashbucklers)
+            }
+            EntryKind::SpiritCurve => {
+                export_csv!(game, path, read_spirit_curves, export_spirit_curves)
+            }
+            EntryKind::ExpBoost => export_csv!(game, path, read_exp_boosts, export_exp_boosts),
+            EntryKind::ExpCurve => {
+                // EXP curves live in the level file, which may be missing
+                let Some(curves) = skip_if_unavailable(game.read_exp_curves(), kind, &bar)? else {
+                    continue;
+                };
+                CsvExporter::export_exp_curves(&curves, File::create(path)?)?;
+                curves.len()
+            }
+            EntryKind::MagicExpCurve => {
+                let Some(curves) = skip_if_unavailable(game.read_magic_exp_curves(), kind, &bar)?
+                else {
+                    continue;
+                };
+                CsvExporter::export_magic_exp_curves(&curves, File::create(path)?)?;
+                curves.len()
+            }
+            EntryKind::Enemy => {
+                CsvExporter::export_enemies(&enemies, File::create(path)?, &item_db)?;
+                enemies.len()
+            }
+            EntryKind::EnemyTask => {
+                let (enemy_magic_names, enemy_super_move_names) = game.enemy_task_name_maps()?;
+                CsvExporter::export_enemy_tasks(
+                    &tasks,
+                    &enemies,
+                    &enemy_magic_names,
+                    &enemy_super_move_names,
+                    File::create(path)?,
+                )?;
+                tasks.len()
+            }
+            EntryKind::EnemyEncounter => {
+                // Enemy encounters (from ENP files)
+                let encounters = game.read_enemy_encounters()?;
+                CsvExporter::export_enemy_encounters(
+                    &encounters,
+                    File::create(path)?,
+                    &encounter_enemy_names,
+                )?;
+                encounters.len()
+            }
+            EntryKind::EnemyEvent => {
+                // Enemy events (from EVP file - scripted battles)
+                let events = game.read_enemy_events()?;
+                CsvExporter::export_enemy_events(
+                    &events,
+                    File::create(path)?,
+                    &encounter_enemy_names,
+                )?;
+                events.len()
+            }
+        };
+
+        tracker.add_entries(count);
+        bar.suspend(|| println!("Exported {}: {} entries", kind.label(), count));
+    }
+    show_progress(&bar, &tracker.done());
+    bar.finish_and_clear();
+
+    // Per-file enemy CSVs skip the dedup, for diagnosing merged stats
+    if options.enemies_by_file {
+        print!("Exporting enemies by file...");
+        let (per_file, _) = game.read_enemies_per_file()?;
+        let paths =
+            CsvExporter::export_enemies_by_file(&per_file, &csv_dir.join("enemies"), &item_db)?;
+        println!(" {} files", paths.len());
+    }
 
     // Export ENP file dumps
-    export_enp_dumps(game, output_dir, &item_db)?;
+    let stats = export_enp_dumps(
+        game,
+        &json_dir,
+        &item_db,
+        options.compact_json,
+        options.encounters_only,
+    )?;
+    if options.verbose {
+        print_enp_dump_stats(&stats);
+    }
 
     // Export EVP file dump
-    export_evp_dump(game, output_dir, &item_db)?;
+    export_evp_dump(game, &json_dir, &item_db, options.compact_json)?;
 
     Ok(())
 }
@@ -1231,6 +1576,7 @@ fn export_evp_dump(
     game: &mut GameRoot,
     output_dir: &Path,
     item_db: &alx::items::ItemDatabase,
+    compact_json: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use alx::io::{decompress_aklz, dump_evp_editable};
 
@@ -1272,7 +1618,7 @@ fn export_evp_dump(
         };
 
         // Convert to JSON
-        let json = serde_json::to_string_pretty(&dump)?;
+        let json = to_json(&dump, compact_json)?;
 
         // Write to evp subfolder
         let output_file = evp_dir.join(format!("{}.json", filename));
@@ -1293,27 +1639,25 @@ fn export_enp_dumps(
     game: &mut GameRoot,
     output_dir: &Path,
     item_db: &alx::items::ItemDatabase,
-) -> Result<(), Box<dyn std::error::Error>> {
-    use alx::io::{decompress_aklz, dump_enp_editable};
+    compact_json: bool,
+    encounters_only: bool,
+) -> Result<EnpDumpStats, Box<dyn std::error::Error>> {
+    use alx::io::{decompress_aklz, dump_enp_segments_editable};
 
-    let enp_dir = output_dir.join("enp");
+    let enp_dir = output_dir.join(if encounters_only { "encounters" } else { "enp" });
     fs::create_dir_all(&enp_dir)?;
 
-    print!("Exporting ENP file dumps...");
+    if encounters_only {
+        print!("Exporting ENP encounters...");
+    } else {
+        print!("Exporting ENP file dumps...");
+    }
 
     // Find all ENP files
-    let all_files = game.iso_mut().list_files_matching("")?;
-    let enp_files: Vec<_> = all_files
-        .iter()
-        .filter(|e| {
-            e.path
-                .file_name()
-                .map(|s| s.to_string_lossy().ends_with(".enp"))
-                .unwrap_or(false)
-        })
-        .collect();
+    let enp_files: Vec<_> = game.enp_file_entries()?.into_values().collect();
 
-    let mut count = 0;
+    let mut stats = EnpDumpStats::new();
+    let mut written = std::collections::HashSet::new();
     for entry in &enp_files {
         let filename = entry
             .path
@@ -1332,26 +1676,59 @@ fn export_enp_dumps(
             Err(_) => continue,
         };
 
-        // Dump the structure using simplified editable format
-        let dump = match dump_enp_editable(&data, &filename, game.version(), item_db) {
+        // Dump the structure using simplified editable format, one
+        // definition per segment for multi-segment files
+        let mut dumps = match dump_enp_segments_editable(&data, &filename, game.version(), item_db)
+        {
             Ok(d) => d,
             Err(_) => continue,
         };
 
-        // Skip files with no enemies (likely multi-segment or special format)
-        if dump.enemies.is_empty() {
-            continue;
-        }
+        let mut wrote_any = false;
+        for dump in &mut dumps {
+            dump.meta.compressed_size = Some(raw_data.len());
+            // Skip segments with no enemies, and segments already dumped
+            // from another file
+            if dump.enemies.is_empty() || !written.insert(dump.filename.clone()) {
+                continue;
+            }
 
-        // Convert to JSON
-        let json = serde_json::to_string_pretty(&dump)?;
+            // Convert to JSON
+            let json = if encounters_only {
+                to_json(&dump.encounters_only(), compact_json)?
+            } else {
+                to_json(&*dump, compact_json)?
+            };
 
-        // Write to enp subfolder
-        let output_file = enp_dir.join(format!("{}.json", filename));
-        fs::write(&output_file, &json)?;
-        count += 1;
+            // Write to enp (or encounters) subfolder
+            let output_file = enp_dir.join(format!("{}.json", dump.filename));
+            fs::write(&output_file, &json)?;
+            wrote_any = true;
+        }
+        if wrote_any {
+            stats.record(&filename, raw_data.len(), data.len());
+        }
     }
 
-    println!(" {} files", count);
-    Ok(())
+    println!(" {} files", stats.len());
+    Ok(stats)
+}
+
+/// Print per-file compression ratios and totals for dumped ENP files.
+fn print_enp_dump_stats(stats: &EnpDumpStats) {
+    for file in &stats.files {
+        println!(
+            "  {}: {} -> {} bytes ({:.1}%)",
+            file.filename,
+            file.compressed_size,
+            file.decompressed_size,
+            file.ratio() * 100.0
+        );
+    }
+    println!(
+        "  Total: {} -> {} bytes ({:.1}%)",
+        stats.total_compressed(),
+        stats.total_decompressed(),
+        stats.ratio() * 100.0
+    );
 }
@@ -299,3 +299,717 @@ fn test_binary_version_flag() {
 
     println!("✓ Version flag works!");
 }
+
+#[test]
+fn test_binary_compress_decompress_roundtrip() {
+    let binary = get_binary_path();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+
+    // Data with repeated runs, similar to ENP files
+    let mut original = Vec::new();
+    for i in 0..64u8 {
+        original.extend_from_slice(&[i, 0, 0, 0xff, 0xff, 0xff, 0xff, i]);
+    }
+    let input = temp_dir.path().join("segment.bin");
+    let compressed = temp_dir.path().join("segment.aklz");
+    let decompressed = temp_dir.path().join("segment.out");
+    fs::write(&input, &original).unwrap();
+
+    let output = Command::new(&binary)
+        .arg("--compress")
+        .arg(&input)
+        .arg("-o")
+        .arg(&compressed)
+        .output()
+        .expect("Failed to run alx_rs --compress");
+    assert!(
+        output.status.success(),
+        "--compress failed with: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(&fs::read(&compressed).unwrap()[..4], b"AKLZ");
+
+    let output = Command::new(&binary)
+        .arg("--decompress")
+        .arg(&compressed)
+        .arg("-o")
+        .arg(&decompressed)
+        .output()
+        .expect("Failed to run alx_rs --decompress");
+    assert!(
+        output.status.success(),
+        "--decompress failed with: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_eq!(fs::read(&decompressed).unwrap(), original);
+
+    println!("✓ AKLZ compress/decompress roundtrip works!");
+}
+
+#[test]
+fn test_binary_decompress_rejects_plain_file() {
+    let binary = get_binary_path();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input = temp_dir.path().join("plain.bin");
+    fs::write(&input, b"not compressed").unwrap();
+
+    let output = Command::new(&binary)
+        .arg("--decompress")
+        .arg(&input)
+        .output()
+        .expect("Failed to run alx_rs --decompress");
+
+    assert!(!output.status.success(), "Should fail for non-AKLZ input");
+    assert!(!temp_dir.path().join("plain.bin.dec").exists());
+}
+
+#[test]
+fn test_binary_emit_schema() {
+    let binary = get_binary_path();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let schema_dir = temp_dir.path().join("schema");
+
+    let output = Command::new(&binary)
+        .arg("--emit-schema")
+        .arg(&schema_dir)
+        .output()
+        .expect("Failed to run alx_rs --emit-schema");
+
+    assert!(
+        output.status.success(),
+        "--emit-schema failed with: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let weapon: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(schema_dir.join("weapon.schema.json")).unwrap())
+            .unwrap();
+    let properties = weapon["properties"].as_object().unwrap();
+    for field in [
+        "id",
+        "name",
+        "character_id",
+        "attack",
+        "effect_id",
+        "trait_data",
+    ] {
+        assert!(properties.contains_key(field), "missing field {}", field);
+    }
+    assert!(schema_dir.join("enemy_encounter.schema.json").exists());
+    assert!(schema_dir.join("game_data.schema.json").exists());
+}
+
+#[test]
+fn test_binary_verbose_prints_enp_stats() {
+    skip_if_no_iso!();
+
+    let binary = get_binary_path();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let output_dir = temp_dir.path().join("data");
+
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("--verbose")
+        .output()
+        .expect("Failed to run alx_rs");
+
+    assert!(
+        output.status.success(),
+        "alx_rs failed with: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // At least one per-file line plus the totals line
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout
+            .lines()
+            .any(|l| l.contains(".enp:") && l.ends_with("%)")),
+        "Missing per-file ENP stats"
+    );
+    assert!(stdout.contains("  Total: "), "Missing ENP stats totals");
+
+    println!("✓ Verbose export prints ENP stats!");
+}
+
+#[test]
+fn test_binary_dump_enp_compact_json_matches_pretty() {
+    skip_if_no_iso!();
+
+    let binary = get_binary_path();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let pretty_path = temp_dir.path().join("pretty.json");
+    let compact_path = temp_dir.path().join("compact.json");
+
+    for (path, compact) in [(&pretty_path, false), (&compact_path, true)] {
+        let mut cmd = Command::new(&binary);
+        cmd.arg(TEST_ISO_PATH)
+            .arg("--dump-enp")
+            .arg("a099a_01ep.enp")
+            .arg("-o")
+            .arg(path);
+        if compact {
+            cmd.arg("--compact-json");
+        }
+        let output = cmd.output().expect("Failed to run alx_rs");
+        assert!(
+            output.status.success(),
+            "alx_rs failed with: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let pretty = fs::read_to_string(&pretty_path).unwrap();
+    let compact = fs::read_to_string(&compact_path).unwrap();
+    assert!(compact.len() < pretty.len());
+    assert!(!compact.contains('\n'));
+
+    // Both forms must describe the same ENP definition
+    let pretty_def: alx::io::EnpDefinition = serde_json::from_str(&pretty).unwrap();
+    let compact_def: alx::io::EnpDefinition = serde_json::from_str(&compact).unwrap();
+    assert_eq!(
+        serde_json::to_value(&pretty_def).unwrap(),
+        serde_json::to_value(&compact_def).unwrap()
+    );
+
+    println!("✓ Compact and pretty ENP dumps match!");
+}
+
+#[test]
+fn test_binary_make_patch_against_itself_is_empty() {
+    skip_if_no_iso!();
+
+    let binary = get_binary_path();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let patch_path = temp_dir.path().join("mod.ips");
+
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("--make-patch")
+        .arg(TEST_ISO_PATH)
+        .arg("-o")
+        .arg(&patch_path)
+        .output()
+        .expect("Failed to run alx_rs");
+
+    assert!(
+        output.status.success(),
+        "alx_rs failed with: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // No differences: just the header and footer
+    assert_eq!(fs::read(&patch_path).unwrap(), b"PATCHEOF");
+
+    println!("✓ make-patch writes an empty IPS for identical DOLs!");
+}
+
+#[test]
+fn test_binary_examine_prints_summary() {
+    skip_if_no_iso!();
+
+    let binary = get_binary_path();
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("--examine")
+        .output()
+        .expect("Failed to run alx_rs --examine");
+
+    assert!(
+        output.status.success(),
+        "--examine failed with: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Version: "), "No version in:\n{}", stdout);
+    assert!(stdout.contains("GEAE8P"), "No product ID in:\n{}", stdout);
+    assert!(
+        stdout.contains("weapons: 80"),
+        "No weapon count in:\n{}",
+        stdout
+    );
+    assert!(stdout.contains("CRC32 "));
+
+    println!("✓ examine prints a summary!");
+}
+
+#[test]
+fn test_binary_list_enp_prints_enemy_counts() {
+    skip_if_no_iso!();
+
+    let binary = get_binary_path();
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("--list-enp")
+        .output()
+        .expect("Failed to run alx_rs --list-enp");
+
+    assert!(
+        output.status.success(),
+        "--list-enp failed with: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout
+        .lines()
+        .find(|line| line.starts_with("a101b_ep.enp"))
+        .unwrap_or_else(|| panic!("a101b_ep.enp not listed in:\n{}", stdout));
+    let columns: Vec<&str> = line.split_whitespace().collect();
+    assert_eq!(columns.len(), 4, "Unexpected row: {}", line);
+    let enemies: usize = columns[3].parse().unwrap();
+    assert!(enemies > 0, "a101b_ep.enp has no enemies: {}", line);
+
+    println!("✓ list-enp prints ENP enemy counts!");
+}
+
+#[test]
+fn test_binary_validate_reports_errors_without_writing() {
+    skip_if_no_iso!();
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut game = alx::GameRoot::open_readonly(Path::new(TEST_ISO_PATH)).unwrap();
+
+    // Name is longer than the 17 bytes an accessory name can hold
+    let mut accessories = game.read_accessories().unwrap();
+    accessories[0].name = "An accessory name that is far too long".to_string();
+    let mut csv = Vec::new();
+    alx::csv::CsvExporter::export_accessories(&accessories, &mut csv).unwrap();
+    fs::write(dir.path().join("accessory.csv"), csv).unwrap();
+
+    // Last row has fewer fields than the header
+    let weapons = game.read_weapons().unwrap();
+    let weapon_effects = game.read_weapon_effects().unwrap();
+    let mut csv = Vec::new();
+    alx::csv::CsvExporter::export_weapons(&weapons, &mut csv, &weapon_effects).unwrap();
+    csv.extend_from_slice(b"0,Cutlass\n");
+    fs::write(dir.path().join("weapon.csv"), csv).unwrap();
+
+    let modified_before = fs::metadata(TEST_ISO_PATH).unwrap().modified().unwrap();
+
+    let binary = get_binary_path();
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("--validate")
+        .arg(dir.path())
+        .output()
+        .expect("Failed to run alx_rs --validate");
+
+    assert!(!output.status.success(), "--validate should fail");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("accessory.csv"),
+        "No accessory error in:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("weapon.csv"),
+        "No weapon error in:\n{}",
+        stdout
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("2 problem(s) found"));
+
+    let modified_after = fs::metadata(TEST_ISO_PATH).unwrap().modified().unwrap();
+    assert_eq!(
+        modified_before, modified_after,
+        "--validate wrote to the ISO"
+    );
+
+    println!("✓ validate reports problems without writing!");
+}
+
+#[test]
+fn test_binary_offsets_file() {
+    skip_if_no_iso!();
+
+    let dir = tempfile::tempdir().unwrap();
+    let binary = get_binary_path();
+    let examine = |offsets: &alx::game::Offsets| {
+        let path = dir.path().join("offsets.toml");
+        fs::write(&path, offsets.to_toml().unwrap()).unwrap();
+        Command::new(&binary)
+            .arg(TEST_ISO_PATH)
+            .arg("--examine")
+            .arg("--offsets")
+            .arg(&path)
+            .output()
+            .expect("Failed to run alx_rs --examine --offsets")
+    };
+
+    // The US table read back from a file works against the US ISO
+    let output = examine(&alx::game::Offsets::gc_us());
+    assert!(
+        output.status.success(),
+        "--offsets failed with: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("weapons: 80"),
+        "No weapon count in:\n{}",
+        stdout
+    );
+
+    // A broken table is rejected before the ISO is read
+    let mut broken = alx::game::Offsets::gc_us();
+    broken.armor_data = broken.weapon_data.clone();
+    let output = examine(&broken);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("overlap"));
+
+    println!("✓ --offsets reads the offset table from a file!");
+}
+
+#[test]
+fn test_binary_randomize_writes_loadable_iso() {
+    skip_if_no_iso!();
+
+    let dir = tempfile::tempdir().unwrap();
+    let output_iso = dir.path().join("randomized.iso");
+    let spoiler = dir.path().join("spoiler.txt");
+
+    let binary = get_binary_path();
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("--randomize")
+        .arg("--seed")
+        .arg("99")
+        .arg("--output")
+        .arg(&output_iso)
+        .arg("--spoiler")
+        .arg(&spoiler)
+        .arg("--verify-string")
+        .arg("--yes")
+        .output()
+        .expect("Failed to run alx_rs --randomize");
+
+    assert!(
+        output.status.success(),
+        "--randomize failed with: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Seed 99"), "No seed in:\n{}", stdout);
+    assert!(stdout.contains("Verification: "), "No code in:\n{}", stdout);
+
+    let spoiler_text = fs::read_to_string(&spoiler).unwrap();
+    assert!(spoiler_text.starts_with("Seed: 99"));
+    assert!(spoiler_text.lines().count() > 1);
+
+    let mut game = alx::GameRoot::open(&output_iso).expect("Randomized ISO should load");
+    assert_eq!(game.read_shops().unwrap().len(), 43);
+
+    println!("✓ randomize writes a loadable ISO!");
+}
+
+#[test]
+fn test_binary_nested_layout() {
+    skip_if_no_iso!();
+
+    let binary = get_binary_path();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let output_dir = temp_dir.path().join("data");
+
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("--layout")
+        .arg("nested")
+        .output()
+        .expect("Failed to run alx_rs --layout nested");
+
+    assert!(output.status.success(), "alx_rs --layout nested failed");
+    assert!(output_dir.join("csv").join("weapon.csv").exists());
+    assert!(output_dir.join("csv").join("enemy.csv").exists());
+    assert!(!output_dir.join("weapon.csv").exists());
+    let enp_dir = output_dir.join("json").join("enp");
+    assert!(fs::read_dir(&enp_dir).unwrap().next().is_some());
+    assert!(output_dir
+        .join("json")
+        .join("evp")
+        .join("epevent.evp.json")
+        .exists());
+    assert!(!output_dir.join("enp").exists());
+
+    // Validate finds the files in the nested folders
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("--validate")
+        .arg(&output_dir)
+        .output()
+        .expect("Failed to run alx_rs --validate");
+    assert!(
+        output.status.success(),
+        "--validate failed on nested export:\n{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    println!("✓ nested layout writes csv/ and json/ folders!");
+}
+
+#[test]
+fn test_binary_validate_checks_encounter_lists() {
+    skip_if_no_iso!();
+
+    let binary = get_binary_path();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let output_dir = temp_dir.path().join("data");
+
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("--encounters-only")
+        .output()
+        .expect("Failed to run alx_rs --encounters-only");
+    assert!(output.status.success(), "alx_rs --encounters-only failed");
+    let encounters_dir = output_dir.join("encounters");
+    let first = fs::read_dir(&encounters_dir)
+        .unwrap()
+        .next()
+        .expect("No encounter lists written")
+        .unwrap()
+        .path();
+
+    let validate = || {
+        Command::new(&binary)
+            .arg(TEST_ISO_PATH)
+            .arg("--validate")
+            .arg(&output_dir)
+            .output()
+            .expect("Failed to run alx_rs --validate")
+    };
+    let output = validate();
+    assert!(
+        output.status.success(),
+        "--validate failed on encounter lists:\n{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    fs::write(&first, "{ not json").unwrap();
+    let output = validate();
+    assert!(!output.status.success(), "--validate should fail");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let name = first.file_name().unwrap().to_string_lossy();
+    assert!(
+        stdout.contains(name.as_ref()),
+        "No {} error in:\n{}",
+        name,
+        stdout
+    );
+
+    println!("✓ validate checks encounters/ lists!");
+}
+
+/// Test that an import --output directory gets a copy with a derived name.
+#[test]
+fn test_binary_import_output_directory() {
+    skip_if_no_iso!();
+
+    let binary = get_binary_path();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path().join("data");
+    // Not created yet; the trailing separator marks it as a directory
+    let out_dir = temp_dir.path().join("out");
+    let out_arg = format!("{}{}", out_dir.display(), std::path::MAIN_SEPARATOR);
+
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("-o")
+        .arg(&data_dir)
+        .output()
+        .expect("Failed to run alx_rs export");
+    assert!(output.status.success(), "alx_rs export failed");
+
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("--import")
+        .arg(&data_dir)
+        .arg("-o")
+        .arg(&out_arg)
+        .arg("-y")
+        .output()
+        .expect("Failed to run alx_rs --import");
+    assert!(
+        output.status.success(),
+        "alx_rs --import failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let written = out_dir.join("Skies of Arcadia Legends (USA)-randomized.iso");
+    assert!(written.exists(), "{} was not written", written.display());
+    assert_eq!(
+        fs::metadata(&written).unwrap().len(),
+        fs::metadata(TEST_ISO_PATH).unwrap().len()
+    );
+
+    println!("✓ import --output directory writes a derived file name!");
+}
+
+#[test]
+fn test_binary_dump_evp() {
+    skip_if_no_iso!();
+
+    let binary = get_binary_path();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("evp.json");
+
+    // With no file given, the ISO path after the flag isn't taken as one
+    let output = Command::new(&binary)
+        .arg("--dump-evp")
+        .arg(TEST_ISO_PATH)
+        .arg("-o")
+        .arg(&path)
+        .output()
+        .expect("Failed to run alx_rs --dump-evp");
+    assert!(
+        output.status.success(),
+        "alx_rs failed with: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let def: alx::io::EvpDefinition =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert!(!def.events.is_empty(), "EVP dump has no events");
+
+    // An unknown file name is an error
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("--dump-evp=missing.evp")
+        .output()
+        .expect("Failed to run alx_rs --dump-evp");
+    assert!(!output.status.success());
+
+    println!("✓ EVP dump has {} events!", def.events.len());
+}
+
+#[test]
+fn test_decompress_file_matches_dump_enp_size() {
+    skip_if_no_iso!();
+
+    let output = Command::new(get_binary_path())
+        .arg(TEST_ISO_PATH)
+        .arg("--dump-enp")
+        .arg("a101b_ep.enp")
+        .output()
+        .expect("Failed to run alx_rs");
+    assert!(
+        output.status.success(),
+        "alx_rs failed with: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let reported: usize = stdout
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Decompressed size: "))
+        .and_then(|rest| rest.trim_end_matches(" bytes").parse().ok())
+        .expect("no decompressed size in --dump-enp output");
+
+    let mut game = alx::game::GameRoot::open_readonly(Path::new(TEST_ISO_PATH)).unwrap();
+    let entry = game
+        .list_iso_files_matching("a101b_ep.enp")
+        .unwrap()
+        .into_iter()
+        .next()
+        .expect("a101b_ep.enp not found");
+    let data = game.decompress_file(&entry.path.to_string_lossy()).unwrap();
+    assert_eq!(data.len(), reported);
+
+    assert!(game.decompress_file("missing_ep.enp").is_err());
+}
+
+/// Enemies and encounters of each dump, without the dump-time metadata.
+fn segment_contents(dumps: &[alx::io::EnpDefinition]) -> Vec<serde_json::Value> {
+    dumps
+        .iter()
+        .map(|dump| {
+            serde_json::json!({
+                "filename": dump.filename,
+                "enemies": dump.enemies,
+                "encounters": dump.encounters,
+            })
+        })
+        .collect()
+}
+
+/// Test that importing an unchanged a099a segment rebakes a099a_ep.enp
+/// with the same contents.
+#[test]
+fn test_binary_import_rebakes_a099a() {
+    skip_if_no_iso!();
+
+    use alx::io::{decompress_aklz, dump_enp_segments_editable, A099A_BAKED_FILENAME};
+
+    let binary = get_binary_path();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path().join("data");
+    fs::create_dir_all(data_dir.join("enp")).unwrap();
+    let output_iso = temp_dir.path().join("rebaked.iso");
+
+    let mut game = alx::game::GameRoot::open_readonly(Path::new(TEST_ISO_PATH)).unwrap();
+    let item_db = game.build_item_database().unwrap();
+    let baked = decompress_aklz(&game.read_enp_file_raw(A099A_BAKED_FILENAME).unwrap()).unwrap();
+    let original =
+        dump_enp_segments_editable(&baked, A099A_BAKED_FILENAME, game.version(), &item_db).unwrap();
+    let segment = original
+        .iter()
+        .find(|dump| !dump.enemies.is_empty())
+        .expect("no a099a segment with enemies")
+        .filename
+        .clone();
+
+    // Dump the segment file and import it back unchanged
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("--dump-enp")
+        .arg(&segment)
+        .arg("-o")
+        .arg(data_dir.join("enp").join(format!("{}.json", segment)))
+        .output()
+        .expect("Failed to run alx_rs --dump-enp");
+    assert!(
+        output.status.success(),
+        "alx_rs --dump-enp failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = Command::new(&binary)
+        .arg(TEST_ISO_PATH)
+        .arg("--import")
+        .arg(&data_dir)
+        .arg("-o")
+        .arg(&output_iso)
+        .arg("-y")
+        .output()
+        .expect("Failed to run alx_rs --import");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "alx_rs --import failed:\n{}\n{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        stdout.contains(&format!("Rebaking {}", A099A_BAKED_FILENAME)),
+        "No rebake in:\n{}",
+        stdout
+    );
+
+    let mut saved = alx::game::GameRoot::open_readonly(&output_iso).unwrap();
+    let raw = saved.read_enp_file_raw(A099A_BAKED_FILENAME).unwrap();
+    let rebaked = decompress_aklz(&raw).expect("rebaked file doesn't decompress");
+    let dumps =
+        dump_enp_segments_editable(&rebaked, A099A_BAKED_FILENAME, saved.version(), &item_db)
+            .unwrap();
+    assert_eq!(segment_contents(&dumps), segment_contents(&original));
+
+    println!(
+        "✓ import rebakes {} from {}!",
+        A099A_BAKED_FILENAME, segment
+    );
+}
@@ -1,16 +1,30 @@
 //! Skies of Arcadia Legends Randomizer - Tauri Backend
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::sync::Mutex;
-use tauri::State;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Mutex, RwLock};
+use tauri::{AppHandle, Emitter, State};
 
-use alx::game::GameRoot;
+use alx::csv::{CsvExporter, CsvImporter, ExportProgress, ImportProgress, ProgressTracker};
+use alx::entries::EntryKind;
+use alx::game::{Edit, EditHistory, FieldDiff, GameData, GameRoot, GameSnapshot};
+use alx::items::ItemDatabase;
+use alx::randomizer::{self, RandomizerConfig, ReachabilityReport, SpoilerLog};
+use alx::{lookups, Error};
 
 /// Application state holding the loaded game
 pub struct AppState {
     pub game: Mutex<Option<GameRoot>>,
     pub iso_path: Mutex<Option<PathBuf>>,
+    /// Parsed data of the loaded game, read once and shared by read commands
+    pub snapshot: RwLock<Option<GameSnapshot>>,
+    /// Game data as first read from the ISO, to compare the snapshot with
+    pub opened: RwLock<Option<GameSnapshot>>,
+    /// Undo/redo steps for edits to the snapshot
+    pub history: Mutex<EditHistory>,
 }
 
 impl Default for AppState {
@@ -18,6 +32,9 @@ impl Default for AppState {
         Self {
             game: Mutex::new(None),
             iso_path: Mutex::new(None),
+            snapshot: RwLock::new(None),
+            opened: RwLock::new(None),
+            history: Mutex::new(EditHistory::default()),
         }
     }
 }
@@ -28,6 +45,56 @@ pub struct GameInfo {
     pub version: String,
     pub region: String,
     pub path: String,
+    /// Whether the DOL matches a known-good dump, e.g. "Known good (US)"
+    pub dump_quality: String,
+    pub known_dump: bool,
+}
+
+/// A named data region in Start.dol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DolRegion {
+    pub name: String,
+    pub start: usize,
+    pub end: usize,
+    pub size: usize,
+}
+
+/// Display metadata for one element
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementMetadata {
+    pub id: i8,
+    pub name: String,
+    /// RGB icon color
+    pub color: (u8, u8, u8),
+    pub symbol: String,
+}
+
+/// Result of an export to a folder
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportSummary {
+    pub output_dir: String,
+    /// Entries written per kind name, e.g. "weapon"
+    pub counts: HashMap<String, usize>,
+}
+
+/// Result of an import from a folder
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportSummary {
+    /// Entries read per kind name, e.g. "weapon"
+    pub imported: HashMap<String, usize>,
+    /// Kinds with no CSV in the folder, or that are only imported from JSON
+    pub skipped: Vec<String>,
+    /// One message per CSV that failed to import; nothing was changed for it
+    pub errors: Vec<String>,
+    /// Number of fields the import changed
+    pub changed_fields: usize,
+}
+
+/// What a randomizer run would change, without saving it
+#[derive(Debug, Clone, Serialize)]
+pub struct RandomizePreview {
+    pub spoiler: SpoilerLog,
+    pub reachability: ReachabilityReport,
 }
 
 /// Result type for commands
@@ -56,7 +123,7 @@ impl<T> CommandResult<T> {
     }
 }
 
-/// Load an ISO file and detect its version
+/// Load an ISO file (or an extracted game directory) and detect its version
 #[tauri::command]
 fn load_iso(path: String, state: State<AppState>) -> CommandResult<GameInfo> {
     let path_buf = PathBuf::from(&path);
@@ -65,21 +132,43 @@ fn load_iso(path: String, state: State<AppState>) -> CommandResult<GameInfo> {
         return CommandResult::err("File does not exist");
     }
 
-    match GameRoot::open(&path_buf) {
-        Ok(game) => {
+    // Extracted game files can be loaded as well as an ISO
+    let opened = if path_buf.is_dir() {
+        GameRoot::open_fst_dir(&path_buf)
+    } else {
+        GameRoot::open(&path_buf)
+    };
+
+    match opened {
+        Ok(mut game) => {
+            let dump_quality = game.dump_quality();
             let version = game.version();
             let info = GameInfo {
                 version: version.to_string(),
                 region: format!("{} ({})", version.region, version.platform),
                 path: path.clone(),
+                dump_quality: dump_quality.to_string(),
+                known_dump: dump_quality.is_known(),
             };
 
             // Store in state
             *state.game.lock().unwrap() = Some(game);
             *state.iso_path.lock().unwrap() = Some(path_buf);
+            *state.snapshot.write().unwrap() = None;
+            *state.opened.write().unwrap() = None;
+            state.history.lock().unwrap().clear();
 
             CommandResult::ok(info)
         }
+        Err(Error::TruncatedIso {
+            expected_min,
+            actual,
+        }) => CommandResult::err(format!(
+            "The ISO file is incomplete ({} of at least {} bytes). \
+             It may be a partial download; please re-copy or re-download it.",
+            actual, expected_min
+        )),
+        Err(Error::InvalidIso(msg)) => CommandResult::err(format!("Not a valid ISO: {}", msg)),
         Err(e) => CommandResult::err(format!("Failed to load ISO: {}", e)),
     }
 }
@@ -93,27 +182,457 @@ fn is_iso_loaded(state: State<AppState>) -> bool {
 /// Get info about the currently loaded ISO
 #[tauri::command]
 fn get_game_info(state: State<AppState>) -> CommandResult<GameInfo> {
-    let game_lock = state.game.lock().unwrap();
+    let mut game_lock = state.game.lock().unwrap();
     let path_lock = state.iso_path.lock().unwrap();
 
-    match (&*game_lock, &*path_lock) {
+    match (game_lock.as_mut(), &*path_lock) {
         (Some(game), Some(path)) => {
+            let dump_quality = game.dump_quality();
             let version = game.version();
             CommandResult::ok(GameInfo {
                 version: version.to_string(),
                 region: format!("{} ({})", version.region, version.platform),
                 path: path.display().to_string(),
+                dump_quality: dump_quality.to_string(),
+                known_dump: dump_quality.is_known(),
             })
         }
         _ => CommandResult::err("No ISO loaded"),
     }
 }
 
+/// Get the number of entries of each data type in the loaded ISO
+#[tauri::command]
+fn get_entry_counts(state: State<AppState>) -> CommandResult<HashMap<String, usize>> {
+    let mut game_lock = state.game.lock().unwrap();
+
+    match game_lock.as_mut() {
+        Some(game) => match game.entry_counts() {
+            Ok(counts) => CommandResult::ok(counts),
+            Err(e) => CommandResult::err(format!("Failed to count entries: {}", e)),
+        },
+        None => CommandResult::err("No ISO loaded"),
+    }
+}
+
+/// List the ENP (enemy file) names in the loaded ISO
+#[tauri::command]
+fn list_enp_files(state: State<AppState>) -> CommandResult<Vec<String>> {
+    let mut game_lock = state.game.lock().unwrap();
+
+    match game_lock.as_mut() {
+        Some(game) => match game.list_enp_files() {
+            Ok(files) => CommandResult::ok(files),
+            Err(e) => CommandResult::err(format!("Failed to list ENP files: {}", e)),
+        },
+        None => CommandResult::err("No ISO loaded"),
+    }
+}
+
+/// Get the Start.dol data regions of the loaded ISO, sorted by offset
+#[tauri::command]
+fn get_dol_layout(state: State<AppState>) -> CommandResult<Vec<DolRegion>> {
+    let game_lock = state.game.lock().unwrap();
+
+    match game_lock.as_ref() {
+        Some(game) => CommandResult::ok(
+            game.offsets()
+                .regions()
+                .into_iter()
+                .map(|(name, range)| DolRegion {
+                    name: name.to_string(),
+                    start: range.start,
+                    end: range.end,
+                    size: range.len(),
+                })
+                .collect(),
+        ),
+        None => CommandResult::err("No ISO loaded"),
+    }
+}
+
+/// Get the name, icon color and symbol of each of the six elements
+#[tauri::command]
+fn get_element_metadata() -> CommandResult<Vec<ElementMetadata>> {
+    CommandResult::ok(
+        lookups::ELEMENT_IDS
+            .map(|id| ElementMetadata {
+                id,
+                name: lookups::element_name(id).to_string(),
+                color: lookups::element_color(id),
+                symbol: lookups::element_symbol(id).to_string(),
+            })
+            .collect(),
+    )
+}
+
+/// Get the snapshot of the loaded game, reading it from the ISO on first use.
+///
+/// Later calls only take the read lock, so concurrent reads don't wait on
+/// each other or on the ISO handle.
+fn game_snapshot(state: &AppState) -> Result<GameSnapshot, String> {
+    if let Some(snapshot) = state.snapshot.read().unwrap().as_ref() {
+        return Ok(snapshot.clone());
+    }
+
+    let mut game_lock = state.game.lock().unwrap();
+    // Another caller may have read the game while we waited for the lock,
+    // and edits may already have been applied to its snapshot
+    if let Some(snapshot) = state.snapshot.read().unwrap().as_ref() {
+        return Ok(snapshot.clone());
+    }
+    let game = game_lock.as_mut().ok_or("No ISO loaded")?;
+    let snapshot = game
+        .snapshot()
+        .map_err(|e| format!("Failed to read game data: {}", e))?;
+    *state.snapshot.write().unwrap() = Some(snapshot.clone());
+    let mut opened = state.opened.write().unwrap();
+    if opened.is_none() {
+        *opened = Some(snapshot.clone());
+    }
+    Ok(snapshot)
+}
+
+/// Get all parsed data of the loaded ISO
+#[tauri::command]
+fn get_game_data(state: State<AppState>) -> CommandResult<GameData> {
+    match game_snapshot(&state) {
+        Ok(snapshot) => CommandResult::ok(snapshot.data().clone()),
+        Err(e) => CommandResult::err(e),
+    }
+}
+
+/// Change the loaded game data through the edit history and publish the
+/// result as the new snapshot.
+///
+/// The snapshot stays write-locked from reading the data to storing the
+/// result, so concurrent updates can't apply to stale data and drop each
+/// other's changes.
+fn update_game_data<T>(
+    state: &AppState,
+    update: impl FnOnce(&mut EditHistory, &mut GameData) -> alx::Result<T>,
+) -> Result<T, String> {
+    // Read the game data first, as that takes the snapshot lock itself
+    game_snapshot(state)?;
+    let mut history = state.history.lock().unwrap();
+    let mut snapshot_lock = state.snapshot.write().unwrap();
+    let snapshot = snapshot_lock.as_ref().ok_or("No ISO loaded")?;
+    let version = snapshot.version().clone();
+    let mut data = snapshot.data().clone();
+    let result = update(&mut history, &mut data).map_err(|e| e.to_string())?;
+    *snapshot_lock = Some(GameSnapshot::new(version, data));
+    Ok(result)
+}
+
+/// Apply a batch of entry edits to the loaded game data as one undo step.
+///
+/// The edits are applied all or nothing: if any fails validation the data
+/// is left unchanged and the error is returned.
+#[tauri::command]
+fn apply_edits(edits: Vec<Edit>, state: State<AppState>) -> CommandResult<()> {
+    match update_game_data(&state, |history, data| history.apply(data, &edits)) {
+        Ok(()) => CommandResult::ok(()),
+        Err(e) => CommandResult::err(format!("Edits not applied: {}", e)),
+    }
+}
+
+/// Undo the last batch of edits. Returns whether there was one to undo.
+#[tauri::command]
+fn undo(state: State<AppState>) -> CommandResult<bool> {
+    match update_game_data(&state, |history, data| history.undo(data)) {
+        Ok(undone) => CommandResult::ok(undone),
+        Err(e) => CommandResult::err(format!("Failed to undo: {}", e)),
+    }
+}
+
+/// Redo the last undone batch of edits. Returns whether there was one to redo.
+#[tauri::command]
+fn redo(state: State<AppState>) -> CommandResult<bool> {
+    match update_game_data(&state, |history, data| history.redo(data)) {
+        Ok(redone) => CommandResult::ok(redone),
+        Err(e) => CommandResult::err(format!("Failed to redo: {}", e)),
+    }
+}
+
+/// Diff the edited game data against what is currently on disk.
+///
+/// The ISO is opened a second time and read fresh, so the preview also
+/// reflects changes made to the file since it was loaded.
+fn changes_preview(state: &AppState) -> Result<Vec<FieldDiff>, String> {
+    let snapshot = game_snapshot(state)?;
+    let path = state
+        .iso_path
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or("No ISO loaded")?;
+    let opened = if path.is_dir() {
+        GameRoot::open_fst_dir(&path)
+    } else {
+        GameRoot::open_readonly(&path)
+    };
+    let saved = opened
+        .and_then(|mut game| game.read_game_data())
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    saved.diff(snapshot.data()).map_err(|e| e.to_string())
+}
+
+/// List the fields that saving would change, for the "Save?" dialog
+#[tauri::command]
+fn preview_changes(state: State<AppState>) -> CommandResult<Vec<FieldDiff>> {
+    match changes_preview(&state) {
+        Ok(diffs) => CommandResult::ok(diffs),
+        Err(e) => CommandResult::err(format!("Failed to preview changes: {}", e)),
+    }
+}
+
+/// Preview a randomizer run on the loaded game data without changing it
+#[tauri::command]
+fn preview_randomize(
+    config: RandomizerConfig,
+    state: State<AppState>,
+) -> CommandResult<RandomizePreview> {
+    let snapshot = match game_snapshot(&state) {
+        Ok(snapshot) => snapshot,
+        Err(e) => return CommandResult::err(e),
+    };
+    match randomizer::plan(snapshot.data(), &config) {
+        Ok((spoiler, reachability)) => CommandResult::ok(RandomizePreview {
+            spoiler,
+            reachability,
+        }),
+        Err(e) => CommandResult::err(format!("Failed to preview randomizer: {}", e)),
+    }
+}
+
+/// Parse the entry kind names a command was limited to, or every kind.
+fn selected_kinds(only: Option<&[String]>) -> Result<Vec<EntryKind>, String> {
+    match only {
+        Some(names) => names
+            .iter()
+            .map(|name| EntryKind::from_str(name).map_err(|e| e.to_string()))
+            .collect(),
+        None => Ok(EntryKind::ALL.to_vec()),
+    }
+}
+
+/// Write one CSV per selected kind of `data` into `output_dir`.
+fn export_game_data(
+    data: &GameData,
+    output_dir: &Path,
+    only: Option<&[String]>,
+    mut progress: impl FnMut(ExportProgress),
+) -> Result<ExportSummary, String> {
+    let kinds = selected_kinds(only)?;
+    fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create {}: {}", output_dir.display(), e))?;
+    let item_db = ItemDatabase::from_game_data(
+        &data.weapons,
+        &data.armors,
+        &data.accessories,
+        &data.usable_items,
+        &data.special_items,
+        &data.ship_cannons,
+        &data.ship_accessories,
+        &data.ship_items,
+    );
+
+    let mut summary = ExportSummary {
+        output_dir: output_dir.display().to_string(),
+        counts: HashMap::new(),
+    };
+    let mut tracker = ProgressTracker::new(kinds.len());
+    for (index, kind) in kinds.iter().enumerate() {
+        progress(tracker.progress(kind.name(), index));
+        let path = output_dir.join(kind.csv_filename());
+        let file = File::create(&path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let count = CsvExporter::export_kind(*kind, data, &item_db, file)
+            .map_err(|e| format!("Failed to export {}: {}", kind.label(), e))?;
+        tracker.add_entries(count);
+        summary.counts.insert(kind.name().to_string(), count);
+    }
+    progress(tracker.done());
+    Ok(summary)
+}
+
+/// Export the loaded (and edited) game data as CSV files into a folder.
+///
+/// `only` limits the export to the given kinds, e.g. `["weapon", "shop"]`.
+/// Emits an `export-progress` event before each kind and one when done.
+/// Unlike a CLI export, no ENP/EVP JSON dumps are written.
+#[tauri::command]
+fn export_data(
+    output_dir: String,
+    only: Option<Vec<String>>,
+    app: AppHandle,
+    state: State<AppState>,
+) -> CommandResult<ExportSummary> {
+    let exported = game_snapshot(&state).and_then(|snapshot| {
+        export_game_data(
+            snapshot.data(),
+            Path::new(&output_dir),
+            only.as_deref(),
+            |progress| {
+                let _ = app.emit("export-progress", progress);
+            },
+        )
+    });
+    match exported {
+        Ok(summary) => CommandResult::ok(summary),
+        Err(e) => CommandResult::err(format!("Export failed: {}", e)),
+    }
+}
+
+/// Read the CSVs in `import_dir` over a copy of `data`.
+///
+/// A CSV that fails to import is reported in the summary and leaves its
+/// kind unchanged, so one bad file doesn't stop the rest.
+fn import_game_data(
+    data: &GameData,
+    import_dir: &Path,
+    only: Option<&[String]>,
+    mut progress: impl FnMut(ImportProgress),
+) -> Result<(GameData, ImportSummary), String> {
+    let kinds = selected_kinds(only)?;
+    if !import_dir.is_dir() {
+        return Err(format!("Folder not found: {}", import_dir.display()));
+    }
+
+    let mut imported = data.clone();
+    let mut summary = ImportSummary::default();
+    let mut tracker = ProgressTracker::new(kinds.len());
+    for (index, kind) in kinds.into_iter().enumerate() {
+        progress(tracker.progress(kind.name(), index));
+        let path = import_dir.join(kind.csv_filename());
+        // Enemy data is imported via ENP/EVP JSON files, not CSV
+        if kind.is_enemy_file_data() || !path.exists() {
+            summary.skipped.push(kind.name().to_string());
+            continue;
+        }
+        let mut attempt = imported.clone();
+        let result = File::open(&path)
+            .map_err(Error::from)
+            .and_then(|file| CsvImporter::import_kind(kind, file, &mut attempt));
+        match result {
+            Ok(count) => {
+                imported = attempt;
+                tracker.add_entries(count);
+                summary.imported.insert(kind.name().to_string(), count);
+            }
+            Err(e) => summary
+                .errors
+                .push(format!("{}: {}", kind.csv_filename(), e)),
+        }
+    }
+    progress(tracker.done());
+    Ok((imported, summary))
+}
+
+/// Turn field diffs into one edit per changed entry.
+///
+/// Entries are told apart by the same key as edits, so a task or encounter
+/// only changes within its own enemy or file.
+fn edits_from_diffs(diffs: &[FieldDiff]) -> Vec<Edit> {
+    let mut edits: Vec<Edit> = Vec::new();
+    for diff in diffs {
+        let edit = match edits.iter_mut().find(|edit| {
+            edit.kind == diff.kind
+                && edit.id == diff.id
+                && edit.enemy_id == diff.enemy_id
+                && edit.filter == diff.filter
+        }) {
+            Some(edit) => edit,
+            None => {
+                let mut edit = Edit::new(
+                    diff.kind,
+                    diff.id,
+                    serde_json::Value::Object(Default::default()),
+                );
+                if let Some(enemy_id) = diff.enemy_id {
+                    edit = edit.for_enemy(enemy_id);
+                }
+                if let Some(filter) = &diff.filter {
+                    edit = edit.in_file(filter);
+                }
+                edits.push(edit);
+                edits.last_mut().unwrap()
+            }
+        };
+        edit.fields[diff.field.as_str()] = diff.after.clone();
+    }
+    edits
+}
+
+/// Import a folder into the loaded game data as one undo step.
+fn import_into_state(
+    state: &AppState,
+    import_dir: &Path,
+    only: Option<&[String]>,
+    progress: impl FnMut(ImportProgress),
+) -> Result<ImportSummary, String> {
+    let snapshot = game_snapshot(state)?;
+    let (imported, mut summary) = import_game_data(snapshot.data(), import_dir, only, progress)?;
+    let diffs = snapshot.data().diff(&imported).map_err(|e| e.to_string())?;
+    summary.changed_fields = diffs.len();
+    let edits = edits_from_diffs(&diffs);
+    update_game_data(state, |history, data| history.apply(data, &edits))?;
+    Ok(summary)
+}
+
+/// Import the CSVs in a folder into the loaded game data.
+///
+/// Nothing is written to the ISO; the import is one undo step and leaves
+/// unsaved changes. `only` limits the import to the given kinds.
+/// Emits an `import-progress` event before each kind and one when done.
+#[tauri::command]
+fn import_data(
+    import_dir: String,
+    only: Option<Vec<String>>,
+    app: AppHandle,
+    state: State<AppState>,
+) -> CommandResult<ImportSummary> {
+    let imported = import_into_state(
+        &state,
+        Path::new(&import_dir),
+        only.as_deref(),
+        |progress| {
+            let _ = app.emit("import-progress", progress);
+        },
+    );
+    match imported {
+        Ok(summary) => CommandResult::ok(summary),
+        Err(e) => CommandResult::err(format!("Import failed: {}", e)),
+    }
+}
+
+/// Check if the loaded game data differs from what was read from the ISO.
+///
+/// Edits undone back to the opened data, or an import that changed nothing,
+/// don't count.
+fn has_unsaved_changes_in(state: &AppState) -> bool {
+    let current = state.snapshot.read().unwrap().clone();
+    let opened = state.opened.read().unwrap().clone();
+    match (opened, current) {
+        (Some(opened), Some(current)) => opened.data() != current.data(),
+        _ => false,
+    }
+}
+
+/// Check if the loaded game data has edits or imports that aren't saved
+#[tauri::command]
+fn has_unsaved_changes(state: State<AppState>) -> bool {
+    has_unsaved_changes_in(&state)
+}
+
 /// Close the currently loaded ISO
 #[tauri::command]
 fn close_iso(state: State<AppState>) -> CommandResult<()> {
     *state.game.lock().unwrap() = None;
     *state.iso_path.lock().unwrap() = None;
+    *state.snapshot.write().unwrap() = None;
+    *state.opened.write().unwrap() = None;
+    state.history.lock().unwrap().clear();
     CommandResult::ok(())
 }
 
@@ -127,8 +646,140 @@ pub fn run() {
             load_iso,
             is_iso_loaded,
             get_game_info,
+            get_entry_counts,
+            list_enp_files,
+            get_dol_layout,
+            get_element_metadata,
+            get_game_data,
+            apply_edits,
+            undo,
+            redo,
+            preview_changes,
+            preview_randomize,
+            export_data,
+            import_data,
+            has_unsaved_changes,
             close_iso,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Path to the test ISO (relative to this crate).
+    const ISO_PATH: &str = "../../../roms/Skies of Arcadia Legends (USA).iso";
+
+    #[test]
+    fn test_export_game_data() {
+        if !Path::new(ISO_PATH).exists() {
+            eprintln!("Skipping test: ISO not found at {}", ISO_PATH);
+            return;
+        }
+
+        let mut game = GameRoot::open_readonly(Path::new(ISO_PATH)).unwrap();
+        let data = game.read_game_data().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let only = vec!["weapon".to_string(), "treasure_chest".to_string()];
+
+        let mut events = Vec::new();
+        let summary = export_game_data(&data, dir.path(), Some(&only), |progress| {
+            events.push(progress)
+        })
+        .unwrap();
+
+        assert_eq!(summary.counts.len(), 2);
+        assert_eq!(summary.counts["weapon"], data.weapons.len());
+        assert_eq!(summary.counts["treasure_chest"], data.treasure_chests.len());
+        assert!(dir.path().join("weapon.csv").exists());
+        assert!(dir.path().join("treasurechest.csv").exists());
+        assert!(!dir.path().join("armor.csv").exists());
+
+        let indices: Vec<(usize, usize)> = events.iter().map(|e| (e.index, e.total)).collect();
+        assert_eq!(indices, vec![(0, 2), (1, 2), (2, 2)]);
+        assert_eq!(events[1].stage, "treasure_chest");
+        assert!(events.last().unwrap().is_done());
+        assert_eq!(
+            events.last().unwrap().entries_done,
+            data.weapons.len() + data.treasure_chests.len()
+        );
+
+        let bad = vec!["not_a_kind".to_string()];
+        assert!(export_game_data(&data, dir.path(), Some(&bad), |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_export_progress_is_monotonic() {
+        if !Path::new(ISO_PATH).exists() {
+            eprintln!("Skipping test: ISO not found at {}", ISO_PATH);
+            return;
+        }
+
+        let mut game = GameRoot::open_readonly(Path::new(ISO_PATH)).unwrap();
+        let data = game.read_game_data().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut events = Vec::new();
+        let summary =
+            export_game_data(&data, dir.path(), None, |progress| events.push(progress)).unwrap();
+
+        assert_eq!(events.len(), EntryKind::ALL.len() + 1);
+        for (i, event) in events.iter().enumerate() {
+            assert_eq!(event.index, i);
+            assert_eq!(event.total, EntryKind::ALL.len());
+        }
+        assert!(events
+            .windows(2)
+            .all(|w| w[0].entries_done <= w[1].entries_done));
+        let done = events.last().unwrap();
+        assert!(done.is_done());
+        assert_eq!(done.entries_done, summary.counts.values().sum::<usize>());
+    }
+
+    #[test]
+    fn test_import_into_state() {
+        if !Path::new(ISO_PATH).exists() {
+            eprintln!("Skipping test: ISO not found at {}", ISO_PATH);
+            return;
+        }
+
+        let mut game = GameRoot::open_readonly(Path::new(ISO_PATH)).unwrap();
+        let data = game.read_game_data().unwrap();
+        let state = AppState::default();
+        let opened = GameSnapshot::new(game.version().clone(), data.clone());
+        *state.snapshot.write().unwrap() = Some(opened.clone());
+        *state.opened.write().unwrap() = Some(opened);
+
+        // A folder with just a treasure chest CSV, one amount changed
+        let dir = tempfile::tempdir().unwrap();
+        let mut chests = data.treasure_chests.clone();
+        chests[0].item_amount = 7;
+        CsvExporter::export_treasure_chests(
+            &chests,
+            File::create(dir.path().join("treasurechest.csv")).unwrap(),
+            &game.build_item_database().unwrap(),
+        )
+        .unwrap();
+        fs::write(dir.path().join("armor.csv"), "not,a,valid\narmor,csv\n").unwrap();
+
+        assert!(!has_unsaved_changes_in(&state));
+        let summary = import_into_state(&state, dir.path(), None, |_| {}).unwrap();
+        assert_eq!(summary.imported.len(), 1);
+        assert_eq!(summary.imported["treasure_chest"], chests.len());
+        assert_eq!(summary.errors.len(), 1);
+        assert!(summary.errors[0].starts_with("armor.csv"));
+        assert!(summary.skipped.contains(&"weapon".to_string()));
+        assert_eq!(summary.changed_fields, 1);
+
+        assert!(has_unsaved_changes_in(&state));
+        let snapshot = game_snapshot(&state).unwrap();
+        assert_eq!(snapshot.data().treasure_chests[0].item_amount, 7);
+        assert_eq!(snapshot.data().armors, data.armors);
+
+        // Undoing the import leaves nothing to save
+        assert!(update_game_data(&state, |history, data| history.undo(data)).unwrap());
+        assert!(!has_unsaved_changes_in(&state));
+    }
+}
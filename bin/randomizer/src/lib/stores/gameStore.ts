@@ -6,6 +6,8 @@ export interface GameInfo {
   version: string;
   region: string;
   path: string;
+  dump_quality: string;
+  known_dump: boolean;
 }
 
 export interface RandomizerSettings {
@@ -1,17 +1,23 @@
 //! CSV export functionality matching original ALX format.
 
+use std::collections::BTreeMap;
+use std::fs::File;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use crate::entries::{
     Accessory, Armor, Character, CharacterMagic, CharacterSuperMove, CrewMember, Enemy,
-    EnemyEncounter, EnemyEvent, EnemyMagic, EnemyShip, EnemySuperMove, EnemyTask, ExpBoost,
-    ExpCurve, MagicExpCurve, PlayableShip, ShipAccessory, ShipCannon, ShipItem, Shop, SpecialItem,
-    SpiritCurve, Swashbuckler, TreasureChest, UsableItem, Weapon, WeaponEffect, DEFEAT_CONDITIONS,
-    ESCAPE_CONDITIONS, TRAIT_NAMES,
+    EnemyEncounter, EnemyEvent, EnemyMagic, EnemyShip, EnemySuperMove, EnemyTask, EntryKind,
+    ExpBoost, ExpCurve, MagicExpCurve, PlayableShip, ShipAccessory, ShipCannon, ShipItem, Shop,
+    SpecialItem, SpiritCurve, Swashbuckler, TreasureChest, UsableItem, Weapon, WeaponEffect,
+    DEFEAT_CONDITIONS, ESCAPE_CONDITIONS, TRAIT_NAMES,
 };
 use crate::error::Result;
+use crate::game::GameData;
 use crate::items::ItemDatabase;
-use crate::lookups::{EFFECT_NAMES, ELEMENT_NAMES, SCOPE_NAMES, SHIP_OCCASION_NAMES, STATE_NAMES};
+use crate::lookups::{
+    enemy_us_name, EFFECT_NAMES, ELEMENT_NAMES, SCOPE_NAMES, SHIP_OCCASION_NAMES, STATE_NAMES,
+};
 
 /// CSV exporter for game data.
 pub struct CsvExporter;
@@ -426,6 +432,7 @@ impl CsvExporter {
             "[Effect Name]",
             "Scope ID",
             "[Scope Name]",
+            "Consume%",
             "Element ID",
             "[Element Name]",
             "Sell%",
@@ -475,6 +482,7 @@ impl CsvExporter {
                 EFFECT_NAMES.get(item.effect_id as i16).to_string(),
                 item.scope_id.to_string(),
                 SCOPE_NAMES.get(item.scope_id).to_string(),
+                item.consume_percent.to_string(),
                 item.element_id.to_string(),
                 ELEMENT_NAMES.get(item.element_id).to_string(),
                 item.sell_percent.to_string(),
@@ -632,66 +640,18 @@ impl CsvExporter {
 
         for c in characters {
             // Movement flags columns
-            let may_dodge = if (c.movement_flags & 0x800) != 0 {
-                "X"
-            } else {
-                ""
-            };
-            let unk_damage = if (c.movement_flags & 0x400) != 0 {
-                "X"
-            } else {
-                ""
-            };
-            let unk_ranged = if (c.movement_flags & 0x200) != 0 {
-                "X"
-            } else {
-                ""
-            };
-            let unk_melee = if (c.movement_flags & 0x100) != 0 {
-                "X"
-            } else {
-                ""
-            };
-            let ranged_atk = if (c.movement_flags & 0x080) != 0 {
-                "X"
-            } else {
-                ""
-            };
-            let melee_atk = if (c.movement_flags & 0x040) != 0 {
-                "X"
-            } else {
-                ""
-            };
-            let ranged_only = if (c.movement_flags & 0x020) != 0 {
-                "X"
-            } else {
-                ""
-            };
-            let take_cover = if (c.movement_flags & 0x010) != 0 {
-                "X"
-            } else {
-                ""
-            };
-            let in_air = if (c.movement_flags & 0x008) != 0 {
-                "X"
-            } else {
-                ""
-            };
-            let on_ground = if (c.movement_flags & 0x004) != 0 {
-                "X"
-            } else {
-                ""
-            };
-            let reserved = if (c.movement_flags & 0x002) != 0 {
-                "X"
-            } else {
-                ""
-            };
-            let may_move = if (c.movement_flags & 0x001) != 0 {
-                "X"
-            } else {
-                ""
-            };
+            let may_dodge = if c.may_dodge() { "X" } else { "" };
+            let unk_damage = if c.unk_damage() { "X" } else { "" };
+            let unk_ranged = if c.unk_ranged() { "X" } else { "" };
+            let unk_melee = if c.unk_melee() { "X" } else { "" };
+            let ranged_atk = if c.ranged_atk() { "X" } else { "" };
+            let melee_atk = if c.melee_atk() { "X" } else { "" };
+            let ranged_only = if c.ranged_only() { "X" } else { "" };
+            let take_cover = if c.take_cover() { "X" } else { "" };
+            let in_air = if c.in_air() { "X" } else { "" };
+            let on_ground = if c.on_ground() { "X" } else { "" };
+            let reserved = if c.reserved() { "X" } else { "" };
+            let may_move = if c.may_move() { "X" } else { "" };
 
             wtr.write_record(&[
                 c.id.to_string(),
@@ -1057,6 +1017,14 @@ impl CsvExporter {
             "Accessory 1",
             "Accessory 2",
             "Accessory 3",
+            "Value",
+            "MAXHP Growth",
+            "MAXSP Growth",
+            "SP Growth",
+            "Defense Growth",
+            "MagDef Growth",
+            "Quick Growth",
+            "Dodge% Growth",
         ])?;
 
         for ship in ships {
@@ -1084,6 +1052,14 @@ impl CsvExporter {
                 ship.accessory_ids[0].to_string(),
                 ship.accessory_ids[1].to_string(),
                 ship.accessory_ids[2].to_string(),
+                ship.value.to_string(),
+                ship.max_hp_growth.to_string(),
+                ship.max_sp_growth.to_string(),
+                ship.sp_growth.to_string(),
+                ship.defense_growth.to_string(),
+                ship.mag_def_growth.to_string(),
+                ship.quick_growth.to_string(),
+                ship.dodge_growth.to_string(),
             ])?;
         }
 
@@ -1588,12 +1564,79 @@ impl CsvExporter {
     /// Export enemies to CSV format matching original ALX format.
     ///
     /// The `item_db` is used to look up item names for drops.
-    /// The `enemy_names` map converts enemy IDs to US names.
+    /// Every row has both names: `Entry JP Name` is the name stored in the
+    /// game data, and `[Entry US Name]` comes from
+    /// [`enemy_us_name`](crate::lookups::enemy_us_name).
     pub fn export_enemies<W: Write>(
         enemies: &[Enemy],
         writer: W,
         item_db: &ItemDatabase,
-        enemy_names: &std::collections::HashMap<u32, String>,
+    ) -> Result<()> {
+        // Process enemies: determine which entries should be marked as global ('*')
+        // and sort by ID, then by file order
+        let mut processed = process_enemy_filters(enemies);
+
+        // Sort by ID, then global first, then by file name
+        processed.sort_by(|(filter_a, a), (filter_b, b)| {
+            let id_cmp = a.id.cmp(&b.id);
+            if id_cmp != std::cmp::Ordering::Equal {
+                return id_cmp;
+            }
+            // '*' filter comes first
+            let a_is_global = *filter_a == "*";
+            let b_is_global = *filter_b == "*";
+            if a_is_global != b_is_global {
+                return b_is_global.cmp(&a_is_global);
+            }
+            // Then by file name
+            filter_a.cmp(filter_b)
+        });
+
+        log::debug!(
+            "Exporting {} enemy rows from {} enemies",
+            processed.len(),
+            enemies.len()
+        );
+        Self::write_enemy_rows(processed, writer, item_db)
+    }
+
+    /// Export enemies to one CSV per source file, named `<filter>.csv`, in `dir`.
+    ///
+    /// Pass the per-file enemies from
+    /// [`GameRoot::read_enemies_per_file`](crate::GameRoot::read_enemies_per_file):
+    /// every row keeps its own file as the filter and no entries are merged
+    /// or marked global, which makes it easy to check the dedup in
+    /// `enemy.csv` against the source files. Returns the written paths.
+    pub fn export_enemies_by_file(
+        enemies: &[Enemy],
+        dir: &Path,
+        item_db: &ItemDatabase,
+    ) -> Result<Vec<PathBuf>> {
+        let mut by_file: BTreeMap<&str, Vec<(String, &Enemy)>> = BTreeMap::new();
+        for enemy in enemies {
+            by_file
+                .entry(enemy.filter.as_str())
+                .or_default()
+                .push((enemy.filter.clone(), enemy));
+        }
+
+        std::fs::create_dir_all(dir)?;
+        let mut paths = Vec::with_capacity(by_file.len());
+        for (filter, mut rows) in by_file {
+            rows.sort_by_key(|(_, e)| e.id);
+            let path = dir.join(format!("{}.csv", filter));
+            log::debug!("Exporting {} enemies to {}", rows.len(), path.display());
+            Self::write_enemy_rows(rows, File::create(&path)?, item_db)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Write enemy rows (filter to show, enemy) in the `enemy.csv` format.
+    fn write_enemy_rows<W: Write>(
+        rows: Vec<(String, &Enemy)>,
+        writer: W,
+        item_db: &ItemDatabase,
     ) -> Result<()> {
         let mut wtr = csv::Writer::from_writer(writer);
 
@@ -1687,29 +1730,7 @@ impl CsvExporter {
             "[Item 4 Name]",
         ])?;
 
-        // Process enemies: determine which entries should be marked as global ('*')
-        // and sort by ID, then by file order
-        let mut processed = process_enemy_filters(enemies);
-
-        // Sort by ID, then global first, then by file name
-        processed.sort_by(|(filter_a, a), (filter_b, b)| {
-            let id_cmp = a.id.cmp(&b.id);
-            if id_cmp != std::cmp::Ordering::Equal {
-                return id_cmp;
-            }
-            // '*' filter comes first
-            let a_is_global = *filter_a == "*";
-            let b_is_global = *filter_b == "*";
-            if a_is_global != b_is_global {
-                return b_is_global.cmp(&a_is_global);
-            }
-            // Then by file name
-            filter_a.cmp(filter_b)
-        });
-
-        let sorted = processed;
-
-        for (filter, e) in sorted {
+        for (filter, e) in rows {
             // Movement flags as individual columns
             let may_dodge = if e.may_dodge() { "X" } else { "" };
             let unk_damage = if e.unk_damage() { "X" } else { "" };
@@ -1725,7 +1746,7 @@ impl CsvExporter {
             let may_move = if e.may_move() { "X" } else { "" };
 
             // US name lookup
-            let us_name = enemy_names.get(&e.id).map(|s| s.as_str()).unwrap_or("???");
+            let us_name = enemy_us_name(e.id);
 
             // Item name lookups
             let item_name = |drop: &crate::entries::EnemyItemDrop| -> String {
@@ -1842,9 +1863,7 @@ impl CsvExporter {
         enemy_super_moves: &std::collections::HashMap<u32, String>,
         writer: W,
     ) -> Result<()> {
-        use crate::lookups::{
-            action_name, action_param_name, branch_name, branch_param_name, enemy_name,
-        };
+        use crate::lookups::{action_name, action_param_name, branch_name, branch_param_name};
         use std::collections::HashMap;
 
         let mut wtr = csv::Writer::from_writer(writer);
@@ -1870,7 +1889,7 @@ impl CsvExporter {
         for e in enemies {
             enemy_jp_names.entry(e.id).or_insert_with(|| {
                 // Use vocabulary lookup for US name
-                enemy_us_names.insert(e.id, enemy_name(e.id).to_string());
+                enemy_us_names.insert(e.id, enemy_us_name(e.id).to_string());
                 e.name_jp.clone()
             });
         }
@@ -1897,16 +1916,8 @@ impl CsvExporter {
                 .join("|")
         }
 
-        // Helper to determine filter priority (ENP=0, EVP=1, DAT=2)
-        fn filter_priority(f: &str) -> u8 {
-            if f == "*" || f.ends_with(".enp") {
-                0
-            } else if f.ends_with(".evp") {
-                1
-            } else {
-                2 // DAT files
-            }
-        }
+        // Filter priority by source file (ENP=0, EVP=1, DAT=2)
+        let filter_priority = crate::io::EnemyFileKind::filter_order;
 
         // Group by enemy_id
         let mut by_enemy: HashMap<u32, Vec<(String, Vec<&EnemyTask>)>> = HashMap::new();
@@ -2275,6 +2286,163 @@ impl CsvExporter {
         wtr.flush()?;
         Ok(())
     }
+
+    /// Export one kind of entry from a [`GameData`] snapshot.
+    ///
+    /// Item names come from `item_db`, and enemy task actions are named from
+    /// the snapshot's enemy magic and super moves, so edits to those show up
+    /// in the CSV. The US enemy names in the enemy, encounter and event CSVs
+    /// come from the built-in [`enemy_us_name`] table instead. Returns the
+    /// number of entries exported.
+    ///
+    /// Only the CSV is written; ENP/EVP JSON dumps need the ISO.
+    pub fn export_kind<W: Write>(
+        kind: EntryKind,
+        data: &GameData,
+        item_db: &ItemDatabase,
+        writer: W,
+    ) -> Result<usize> {
+        let count = match kind {
+            EntryKind::Accessory => {
+                Self::export_accessories(&data.accessories, writer)?;
+                data.accessories.len()
+            }
+            EntryKind::Armor => {
+                Self::export_armors(&data.armors, writer)?;
+                data.armors.len()
+            }
+            EntryKind::Weapon => {
+                Self::export_weapons(&data.weapons, writer, &data.weapon_effects)?;
+                data.weapons.len()
+            }
+            EntryKind::UsableItem => {
+                Self::export_usable_items(&data.usable_items, writer)?;
+                data.usable_items.len()
+            }
+            EntryKind::SpecialItem => {
+                Self::export_special_items(&data.special_items, writer)?;
+                data.special_items.len()
+            }
+            EntryKind::Character => {
+                Self::export_characters(&data.characters, item_db, writer)?;
+                data.characters.len()
+            }
+            EntryKind::CharacterMagic => {
+                Self::export_character_magic(&data.character_magic, writer)?;
+                data.character_magic.len()
+            }
+            EntryKind::CharacterSuperMove => {
+                Self::export_character_super_moves(&data.character_super_moves, writer)?;
+                data.character_super_moves.len()
+            }
+            EntryKind::Shop => {
+                Self::export_shops(&data.shops, writer, item_db)?;
+                data.shops.len()
+            }
+            EntryKind::TreasureChest => {
+                Self::export_treasure_chests(&data.treasure_chests, writer, item_db)?;
+                data.treasure_chests.len()
+            }
+            EntryKind::CrewMember => {
+                Self::export_crew_members(&data.crew_members, writer)?;
+                data.crew_members.len()
+            }
+            EntryKind::PlayableShip => {
+                Self::export_playable_ships(&data.playable_ships, writer)?;
+                data.playable_ships.len()
+            }
+            EntryKind::ShipCannon => {
+                Self::export_ship_cannons(&data.ship_cannons, writer)?;
+                data.ship_cannons.len()
+            }
+            EntryKind::ShipAccessory => {
+                Self::export_ship_accessories(&data.ship_accessories, writer)?;
+                data.ship_accessories.len()
+            }
+            EntryKind::ShipItem => {
+                Self::export_ship_items(&data.ship_items, writer)?;
+                data.ship_items.len()
+            }
+            EntryKind::EnemyShip => {
+                Self::export_enemy_ships(&data.enemy_ships, writer)?;
+                data.enemy_ships.len()
+            }
+            EntryKind::EnemyMagic => {
+                Self::export_enemy_magic(&data.enemy_magic, writer)?;
+                data.enemy_magic.len()
+            }
+            EntryKind::EnemySuperMove => {
+                Self::export_enemy_super_moves(&data.enemy_super_moves, writer)?;
+                data.enemy_super_moves.len()
+            }
+            EntryKind::Swashbuckler => {
+                Self::export_swashbucklers(&data.swashbucklers, writer)?;
+                data.swashbucklers.len()
+            }
+            EntryKind::SpiritCurve => {
+                Self::export_spirit_curves(&data.spirit_curves, writer)?;
+                data.spirit_curves.len()
+            }
+            EntryKind::ExpBoost => {
+                Self::export_exp_boosts(&data.exp_boosts, writer)?;
+                data.exp_boosts.len()
+            }
+            EntryKind::ExpCurve => {
+                Self::export_exp_curves(&data.exp_curves, writer)?;
+                data.exp_curves.len()
+            }
+            EntryKind::MagicExpCurve => {
+                Self::export_magic_exp_curves(&data.magic_exp_curves, writer)?;
+                data.magic_exp_curves.len()
+            }
+            EntryKind::Enemy => {
+                Self::export_enemies(&data.enemies, writer, item_db)?;
+                data.enemies.len()
+            }
+            EntryKind::EnemyTask => {
+                let (magic_names, super_move_names) = data.enemy_task_name_maps();
+                Self::export_enemy_tasks(
+                    &data.enemy_tasks,
+                    &data.enemies,
+                    &magic_names,
+                    &super_move_names,
+                    writer,
+                )?;
+                data.enemy_tasks.len()
+            }
+            EntryKind::EnemyEncounter => {
+                Self::export_enemy_encounters(
+                    &data.enemy_encounters,
+                    writer,
+                    &encounter_enemy_names(&data.enemies),
+                )?;
+                data.enemy_encounters.len()
+            }
+            EntryKind::EnemyEvent => {
+                Self::export_enemy_events(
+                    &data.enemy_events,
+                    writer,
+                    &encounter_enemy_names(&data.enemies),
+                )?;
+                data.enemy_events.len()
+            }
+        };
+        Ok(count)
+    }
+}
+
+/// Map enemy IDs to their (Japanese, US) names for encounter and event
+/// exports.
+fn encounter_enemy_names(enemies: &[Enemy]) -> std::collections::HashMap<u32, (String, String)> {
+    enemies
+        .iter()
+        .map(|enemy| {
+            (
+                enemy.id,
+                (enemy.name_jp.clone(), enemy_us_name(enemy.id).to_string()),
+            )
+        })
+        .collect()
 }
 
 /// Character names for event battles
@@ -2291,26 +2459,11 @@ fn character_name(id: i8) -> String {
     }
 }
 
-/// Determine file order based on file type (matching Ruby ALX logic).
-/// - ENP files or '*': order 0
-/// - EVP files: order 1
-/// - EB/EC/DAT files: order 2
-fn file_order(filter: &str) -> u8 {
-    let lower = filter.to_lowercase();
-    if filter == "*" || lower.ends_with("_ep.enp") || lower.ends_with("_ep.bin") {
-        0
-    } else if lower.ends_with(".evp") {
-        1
-    } else if lower.ends_with(".eb") || lower.ends_with(".ec") || lower.ends_with(".dat") {
-        2
-    } else {
-        // Default order for other files
-        3
-    }
-}
-
 /// Process enemy filters to mark global entries with '*'.
-/// For each enemy ID, the entry with the lowest file order gets marked as global.
+/// For each enemy ID, the entry with the lowest
+/// [`filter_order`](crate::io::EnemyFileKind::filter_order) gets marked as global.
+/// Ties go to the lowest filter name, then the earliest entry, so the choice
+/// doesn't depend on input order.
 fn process_enemy_filters(enemies: &[Enemy]) -> Vec<(String, &Enemy)> {
     use std::collections::HashMap;
 
@@ -2318,19 +2471,18 @@ fn process_enemy_filters(enemies: &[Enemy]) -> Vec<(String, &Enemy)> {
     let mut best_per_id: HashMap<u32, (u8, usize)> = HashMap::new(); // id -> (best_order, index)
 
     for (idx, enemy) in enemies.iter().enumerate() {
-        let order = file_order(&enemy.filter);
+        let order = crate::io::EnemyFileKind::filter_order(&enemy.filter);
         let entry = best_per_id.entry(enemy.id).or_insert((order, idx));
-        if order < entry.0 {
+        let better =
+            order < entry.0 || (order == entry.0 && enemy.filter < enemies[entry.1].filter);
+        if better {
             *entry = (order, idx);
         }
     }
 
     // Determine which indices should become global
-    let global_indices: std::collections::HashSet<usize> = best_per_id
-        .values()
-        .filter(|(order, _)| *order <= 2) // Only mark as global if order <= 2
-        .map(|(_, idx)| *idx)
-        .collect();
+    let global_indices: std::collections::HashSet<usize> =
+        best_per_id.values().map(|(_, idx)| *idx).collect();
 
     // Build result with updated filters
     enemies
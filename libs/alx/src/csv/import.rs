@@ -1,15 +1,19 @@
 //! CSV import functionality for reading data back from CSVs.
 
+use std::collections::HashMap;
 use std::io::Read;
 
 use crate::entries::{
-    Accessory, Armor, Character, CharacterFlags, CharacterMagic, CharacterSuperMove, CrewMember,
-    EnemyEncounter, EnemyMagic, EnemyShip, EnemySlot, EnemySuperMove, ExpBoost, ExpCurve,
-    MagicExpCurve, OccasionFlags, PlayableShip, ShipAccessory, ShipCannon, ShipItem, Shop,
-    SpecialItem, SpiritCurve, SpiritLevel, Swashbuckler, Trait, TreasureChest, UsableItem, Weapon,
-    MAX_ENEMY_SLOTS,
+    Accessory, Armor, Character, CharacterMagic, CharacterSuperMove, CrewMember, EnemyEncounter,
+    EnemyMagic, EnemyShip, EnemySlot, EnemySuperMove, EntryKind, ExpBoost, ExpCurve, MagicExpCurve,
+    PlayableShip, ShipAccessory, ShipCannon, ShipItem, Shop, SpecialItem, SpiritCurve, SpiritLevel,
+    Swashbuckler, Trait, TreasureChest, UsableItem, Weapon, MAX_ENEMY_SLOTS,
 };
 use crate::error::{Error, Result};
+use crate::game::GameData;
+use crate::items::ItemDatabase;
+
+use super::export::CsvExporter;
 
 /// CSV importer for game data.
 pub struct CsvImporter;
@@ -70,10 +74,111 @@ fn parse_binary_i16(s: &str) -> i16 {
     i16::from_str_radix(s, 2).unwrap_or(0)
 }
 
+/// Column positions of a CSV, by header name.
+///
+/// Importers read cells at the positions the exporter writes them at. This
+/// maps each of those to the column with the same header in the CSV being
+/// read, so columns can be reordered and unknown ones (e.g. notes) are
+/// ignored. Every exported header must be in the CSV, except the ones a
+/// merge importer keeps existing values for; those read as a missing cell,
+/// like a row that stops short.
+struct Columns(Vec<Option<usize>>);
+
+impl Columns {
+    /// Map the columns of a CSV that must have every exported header.
+    fn new<R: Read>(kind: EntryKind, rdr: &mut csv::Reader<R>) -> Result<Self> {
+        Self::with_optional(kind, rdr, usize::MAX..)
+    }
+
+    /// Map the columns of a CSV that may leave out the exported headers
+    /// from position `optional` on.
+    fn with_optional<R: Read>(
+        kind: EntryKind,
+        rdr: &mut csv::Reader<R>,
+        optional: std::ops::RangeFrom<usize>,
+    ) -> Result<Self> {
+        let headers = rdr.headers().map_err(|e| Error::ParseError {
+            offset: 0,
+            message: format!("CSV parse error: {}", e),
+        })?;
+        let mut found: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, name) in headers.iter().enumerate() {
+            found.entry(name.trim()).or_default().push(i);
+        }
+
+        // A repeated header matches the same repeat in the CSV
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let mut positions = Vec::new();
+        for (index, name) in exported_headers(kind)?.into_iter().enumerate() {
+            let repeat = seen.entry(name.clone()).or_default();
+            let position = found
+                .get(name.as_str())
+                .and_then(|p| p.get(*repeat).copied());
+            *repeat += 1;
+            if position.is_none() && !optional.contains(&index) {
+                return Err(Error::ValidationError(format!(
+                    "{} CSV is missing the '{}' column",
+                    kind, name
+                )));
+            }
+            positions.push(position);
+        }
+        Ok(Self(positions))
+    }
+
+    /// View `record` with its cells at the exported positions.
+    fn row<'r>(&'r self, record: &'r csv::StringRecord) -> Row<'r> {
+        Row {
+            columns: self,
+            record,
+        }
+    }
+}
+
+/// Get the header row the exporter writes for `kind`.
+fn exported_headers(kind: EntryKind) -> Result<Vec<String>> {
+    let mut header = Vec::new();
+    CsvExporter::export_kind(
+        kind,
+        &GameData::default(),
+        &ItemDatabase::new(),
+        &mut header,
+    )?;
+    let mut rdr = csv::Reader::from_reader(header.as_slice());
+    Ok(rdr
+        .headers()?
+        .iter()
+        .map(|name| name.trim().to_string())
+        .collect())
+}
+
+/// One CSV row, read through [`Columns`].
+struct Row<'r> {
+    columns: &'r Columns,
+    record: &'r csv::StringRecord,
+}
+
+impl<'r> Row<'r> {
+    /// Get the cell at exported position `index`.
+    fn get(&self, index: usize) -> Option<&'r str> {
+        let position = self.columns.0.get(index).copied().flatten()?;
+        self.record.get(position)
+    }
+}
+
+/// Overwrite `field` with the cell at `index`, unless the row has no such
+/// cell or it is empty.
+fn set_if_present<T>(field: &mut T, record: &Row, index: usize, parse: fn(&str) -> T) {
+    if let Some(value) = record.get(index).filter(|v| !v.trim().is_empty()) {
+        *field = parse(value);
+    }
+}
+
 impl CsvImporter {
     /// Import accessories from CSV.
     pub fn import_accessories<R: Read>(reader: R) -> Result<Vec<Accessory>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::Accessory, &mut rdr)?;
         let mut accessories = Vec::new();
 
         for result in rdr.records() {
@@ -81,10 +186,11 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
             let name = record.get(1).unwrap_or("").to_string();
-            let char_flags = parse_binary(record.get(2).unwrap_or("0"));
+            let character_flags = record.get(2).unwrap_or("0").parse()?;
             let sell_percent: i8 = parse_or_default(record.get(9).unwrap_or("0"));
             let order1: i8 = parse_or_default(record.get(10).unwrap_or("0"));
             let order2: i8 = parse_or_default(record.get(11).unwrap_or("0"));
@@ -105,7 +211,7 @@ impl CsvImporter {
             let accessory = Accessory {
                 id,
                 name,
-                character_flags: CharacterFlags(char_flags),
+                character_flags,
                 sell_percent,
                 order1,
                 order2,
@@ -155,6 +261,7 @@ impl CsvImporter {
     /// Import armors from CSV.
     pub fn import_armors<R: Read>(reader: R) -> Result<Vec<Armor>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::Armor, &mut rdr)?;
         let mut armors = Vec::new();
 
         for result in rdr.records() {
@@ -162,10 +269,11 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
             let name = record.get(1).unwrap_or("").to_string();
-            let char_flags = parse_binary(record.get(2).unwrap_or("0"));
+            let character_flags = record.get(2).unwrap_or("0").parse()?;
             let sell_percent: i8 = parse_or_default(record.get(9).unwrap_or("0"));
             let order1: i8 = parse_or_default(record.get(10).unwrap_or("0"));
             let order2: i8 = parse_or_default(record.get(11).unwrap_or("0"));
@@ -185,7 +293,7 @@ impl CsvImporter {
             let armor = Armor {
                 id,
                 name,
-                character_flags: CharacterFlags(char_flags),
+                character_flags,
                 sell_percent,
                 order1,
                 order2,
@@ -231,6 +339,7 @@ impl CsvImporter {
     /// Import weapons from CSV.
     pub fn import_weapons<R: Read>(reader: R) -> Result<Vec<Weapon>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::Weapon, &mut rdr)?;
         let mut weapons = Vec::new();
 
         for result in rdr.records() {
@@ -238,6 +347,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
             let name = record.get(1).unwrap_or("").to_string();
@@ -314,16 +424,17 @@ impl CsvImporter {
     ///
     /// CSV columns (matching export):
     /// 0: Entry ID, 1: Entry US Name, 2: Occasion Flags, 3-5: [M/B/S],
-    /// 6: Effect ID, 7: [Effect Name], 8: Scope ID, 9: [Scope Name],
-    /// 10: Element ID, 11: [Element Name], 12: Sell%, 13: US Order 1, 14: US Order 2,
-    /// 15: Pad 1, 16: Buy, 17: Effect Base, 18: Type ID, 19: [Type Name],
-    /// 20: State ID, 21: [State Name], 22: State Miss%, 23-25: Pads,
-    /// 26: [US Descr Pos], 27: [US Descr Size], 28: US Descr Str
+    /// 6: Effect ID, 7: [Effect Name], 8: Scope ID, 9: [Scope Name], 10: Consume%,
+    /// 11: Element ID, 12: [Element Name], 13: Sell%, 14: US Order 1, 15: US Order 2,
+    /// 16: Pad 1, 17: Buy, 18: Effect Base, 19: Type ID, 20: [Type Name],
+    /// 21: State ID, 22: [State Name], 23: State Miss%, 24-26: Pads,
+    /// 27: [US Descr Pos], 28: [US Descr Size], 29: US Descr Str
     pub fn import_usable_items<R: Read>(
         reader: R,
         existing: &[UsableItem],
     ) -> Result<Vec<UsableItem>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::UsableItem, &mut rdr)?;
         let mut items: Vec<UsableItem> = existing.to_vec();
 
         for result in rdr.records() {
@@ -331,32 +442,26 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
             if let Some(item) = items.iter_mut().find(|i| i.id == id) {
-                // Skip name - strings are read-only
-                item.occasion_flags = OccasionFlags(parse_binary(record.get(2).unwrap_or("0")));
-                // Skip [M], [B], [S] at 3-5
+                // Skip name, [M]/[B]/[S], the [...] name columns, pads and
+                // descriptions - strings are read-only
+                item.occasion_flags = record.get(2).unwrap_or("0").parse()?;
                 item.effect_id = parse_or_default(record.get(6).unwrap_or("-1"));
-                // Skip [Effect Name] at 7
                 item.scope_id = parse_or_default(record.get(8).unwrap_or("0"));
-                // Skip [Scope Name] at 9
-                item.element_id = parse_or_default(record.get(10).unwrap_or("-1"));
-                // Skip [Element Name] at 11
-                item.sell_percent = parse_or_default(record.get(12).unwrap_or("0"));
-                item.order1 = parse_or_default(record.get(13).unwrap_or("0"));
-                item.order2 = parse_or_default(record.get(14).unwrap_or("0"));
-                // Skip Pad 1 at 15
-                item.buy_price = parse_or_default(record.get(16).unwrap_or("0"));
-                item.effect_base = parse_or_default(record.get(17).unwrap_or("0"));
-                item.type_id = parse_or_default(record.get(18).unwrap_or("0"));
-                // Skip [Type Name] at 19
-                item.state_id = parse_or_default(record.get(20).unwrap_or("0"));
-                // Skip [State Name] at 21
-                item.state_miss = parse_or_default(record.get(22).unwrap_or("0"));
-                // Skip Pads at 23-25, description pos/size at 26-27
-                // Skip description - strings are read-only
+                item.consume_percent = parse_or_default(record.get(10).unwrap_or("0"));
+                item.element_id = parse_or_default(record.get(11).unwrap_or("-1"));
+                item.sell_percent = parse_or_default(record.get(13).unwrap_or("0"));
+                item.order1 = parse_or_default(record.get(14).unwrap_or("0"));
+                item.order2 = parse_or_default(record.get(15).unwrap_or("0"));
+                item.buy_price = parse_or_default(record.get(17).unwrap_or("0"));
+                item.effect_base = parse_or_default(record.get(18).unwrap_or("0"));
+                item.type_id = parse_or_default(record.get(19).unwrap_or("0"));
+                item.state_id = parse_or_default(record.get(21).unwrap_or("0"));
+                item.state_miss = parse_or_default(record.get(23).unwrap_or("0"));
             }
         }
 
@@ -366,6 +471,7 @@ impl CsvImporter {
     /// Import special items from CSV.
     pub fn import_special_items<R: Read>(reader: R) -> Result<Vec<SpecialItem>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::SpecialItem, &mut rdr)?;
         let mut items = Vec::new();
 
         for result in rdr.records() {
@@ -373,6 +479,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
             let name = record.get(1).unwrap_or("").to_string();
@@ -416,8 +523,14 @@ impl CsvImporter {
     /// 40-45: Element resistances (6), 46-60: State resistances (15),
     /// 61: Danger, 62: Power, 63: Will, 64: Vigor, 65: Agile, 66: Quick, 67: Pad 3,
     /// 68-72: Growth rates (5), 73-78: Magic EXP (6)
+    ///
+    /// Rows are matched to `existing` by Entry ID, so they may be in any
+    /// order and characters without a row are left as they are. Missing or
+    /// empty cells keep the existing value.
     pub fn import_characters<R: Read>(reader: R, existing: &[Character]) -> Result<Vec<Character>> {
-        let mut rdr = csv::Reader::from_reader(reader);
+        // Rows may stop short of the header
+        let mut rdr = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+        let columns = Columns::with_optional(EntryKind::Character, &mut rdr, 2..)?;
         let mut characters: Vec<Character> = existing.to_vec();
 
         for result in rdr.records() {
@@ -425,64 +538,75 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
             if let Some(c) = characters.iter_mut().find(|c| c.id == id) {
                 // Skip name at column 1 - strings are read-only
-                c.age = parse_or_default(record.get(2).unwrap_or("0"));
-                c.gender_id = parse_or_default(record.get(3).unwrap_or("0"));
+                set_if_present(&mut c.age, &record, 2, parse_or_default);
+                set_if_present(&mut c.gender_id, &record, 3, parse_or_default);
                 // Skip [Gender Name] at 4
-                c.width = parse_or_default(record.get(5).unwrap_or("0"));
-                c.depth = parse_or_default(record.get(6).unwrap_or("0"));
-                c.max_mp = parse_or_default(record.get(7).unwrap_or("0"));
-                c.element_id = parse_or_default(record.get(8).unwrap_or("0"));
+                set_if_present(&mut c.width, &record, 5, parse_or_default);
+                set_if_present(&mut c.depth, &record, 6, parse_or_default);
+                set_if_present(&mut c.max_mp, &record, 7, parse_or_default);
+                set_if_present(&mut c.element_id, &record, 8, parse_or_default);
                 // Skip [Element Name] at 9, Pad 1 at 10
-                c.weapon_id = parse_or_default(record.get(11).unwrap_or("0"));
+                set_if_present(&mut c.weapon_id, &record, 11, parse_or_default);
                 // Skip [Weapon Name] at 12
-                c.armor_id = parse_or_default(record.get(13).unwrap_or("0"));
+                set_if_present(&mut c.armor_id, &record, 13, parse_or_default);
                 // Skip [Armor Name] at 14
-                c.accessory_id = parse_or_default(record.get(15).unwrap_or("0"));
+                set_if_present(&mut c.accessory_id, &record, 15, parse_or_default);
                 // Skip [Accessory Name] at 16
-                c.movement_flags = parse_binary_i16(record.get(17).unwrap_or("0"));
+                set_if_present(&mut c.movement_flags, &record, 17, parse_binary_i16);
                 // Skip flag columns 18-29
-                c.hp = parse_or_default(record.get(30).unwrap_or("0"));
-                c.max_hp = parse_or_default(record.get(31).unwrap_or("0"));
-                c.max_hp_growth = parse_or_default(record.get(32).unwrap_or("0"));
-                c.sp = parse_or_default(record.get(33).unwrap_or("0"));
-                c.max_sp = parse_or_default(record.get(34).unwrap_or("0"));
-                c.counter_percent = parse_or_default(record.get(35).unwrap_or("0"));
+                set_if_present(&mut c.hp, &record, 30, parse_or_default);
+                set_if_present(&mut c.max_hp, &record, 31, parse_or_default);
+                set_if_present(&mut c.max_hp_growth, &record, 32, parse_or_default);
+                set_if_present(&mut c.sp, &record, 33, parse_or_default);
+                set_if_present(&mut c.max_sp, &record, 34, parse_or_default);
+                set_if_present(&mut c.counter_percent, &record, 35, parse_or_default);
                 // Skip Pad 2 at 36
-                c.exp = parse_or_default(record.get(37).unwrap_or("0"));
-                c.max_mp_growth = parse_or_default(record.get(38).unwrap_or("0"));
-                c.unknown1 = parse_or_default(record.get(39).unwrap_or("0"));
+                set_if_present(&mut c.exp, &record, 37, parse_or_default);
+                set_if_present(&mut c.max_mp_growth, &record, 38, parse_or_default);
+                set_if_present(&mut c.unknown1, &record, 39, parse_or_default);
 
                 // Element resistances (6 values at 40-45)
                 for i in 0..6 {
-                    c.element_resistances[i] = parse_or_default(record.get(40 + i).unwrap_or("0"));
+                    set_if_present(
+                        &mut c.element_resistances[i],
+                        &record,
+                        40 + i,
+                        parse_or_default,
+                    );
                 }
 
                 // State resistances (15 values at 46-60)
                 for i in 0..15 {
-                    c.state_resistances[i] = parse_or_default(record.get(46 + i).unwrap_or("0"));
+                    set_if_present(
+                        &mut c.state_resistances[i],
+                        &record,
+                        46 + i,
+                        parse_or_default,
+                    );
                 }
 
-                c.danger = parse_or_default(record.get(61).unwrap_or("0"));
-                c.power = parse_or_default(record.get(62).unwrap_or("0"));
-                c.will = parse_or_default(record.get(63).unwrap_or("0"));
-                c.vigor = parse_or_default(record.get(64).unwrap_or("0"));
-                c.agile = parse_or_default(record.get(65).unwrap_or("0"));
-                c.quick = parse_or_default(record.get(66).unwrap_or("0"));
+                set_if_present(&mut c.danger, &record, 61, parse_or_default);
+                set_if_present(&mut c.power, &record, 62, parse_or_default);
+                set_if_present(&mut c.will, &record, 63, parse_or_default);
+                set_if_present(&mut c.vigor, &record, 64, parse_or_default);
+                set_if_present(&mut c.agile, &record, 65, parse_or_default);
+                set_if_present(&mut c.quick, &record, 66, parse_or_default);
                 // Skip Pad 3 at 67
-                c.power_growth = parse_or_default(record.get(68).unwrap_or("0"));
-                c.will_growth = parse_or_default(record.get(69).unwrap_or("0"));
-                c.vigor_growth = parse_or_default(record.get(70).unwrap_or("0"));
-                c.agile_growth = parse_or_default(record.get(71).unwrap_or("0"));
-                c.quick_growth = parse_or_default(record.get(72).unwrap_or("0"));
+                set_if_present(&mut c.power_growth, &record, 68, parse_or_default);
+                set_if_present(&mut c.will_growth, &record, 69, parse_or_default);
+                set_if_present(&mut c.vigor_growth, &record, 70, parse_or_default);
+                set_if_present(&mut c.agile_growth, &record, 71, parse_or_default);
+                set_if_present(&mut c.quick_growth, &record, 72, parse_or_default);
 
                 // Magic EXP (6 values at 73-78)
                 for i in 0..6 {
-                    c.magic_exp[i] = parse_or_default(record.get(73 + i).unwrap_or("0"));
+                    set_if_present(&mut c.magic_exp[i], &record, 73 + i, parse_or_default);
                 }
             }
         }
@@ -503,11 +627,16 @@ impl CsvImporter {
     /// 31: Ship Eff ID, 32: [Ship Eff Name], 33: Ship Eff SP, 34: Ship Eff Turns,
     /// 35: Ship Eff Base, 36: Unk, 37-39: Pad,
     /// 40-42: [US Descr], 43-45: [Ship Descr]
+    ///
+    /// Ship descriptions aren't read from the game, so unlike the US
+    /// description they are taken from the CSV when present. Neither is
+    /// written back to the DOL.
     pub fn import_character_magic<R: Read>(
         reader: R,
         existing: &[CharacterMagic],
     ) -> Result<Vec<CharacterMagic>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::with_optional(EntryKind::CharacterMagic, &mut rdr, 43..)?;
         let mut magic: Vec<CharacterMagic> = existing.to_vec();
 
         for result in rdr.records() {
@@ -515,6 +644,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -539,7 +669,10 @@ impl CsvImporter {
                 m.ship_effect_turns = parse_or_default(record.get(34).unwrap_or("0"));
                 m.ship_effect_base = parse_or_default(record.get(35).unwrap_or("0"));
                 m.unknown = parse_or_default(record.get(36).unwrap_or("-1"));
-                // Skip descriptions - strings are read-only
+                // Skip US description - strings are read-only
+                set_if_present(&mut m.ship_description_pos, &record, 43, parse_hex);
+                set_if_present(&mut m.ship_description_size, &record, 44, parse_or_default);
+                set_if_present(&mut m.ship_description, &record, 45, str::to_string);
             }
         }
 
@@ -563,6 +696,7 @@ impl CsvImporter {
         existing: &[CharacterSuperMove],
     ) -> Result<Vec<CharacterSuperMove>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::CharacterSuperMove, &mut rdr)?;
         let mut moves: Vec<CharacterSuperMove> = existing.to_vec();
 
         for result in rdr.records() {
@@ -570,6 +704,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -608,6 +743,7 @@ impl CsvImporter {
     /// 49: [US Descr Pos], 50: [US Descr Size], 51: US Descr Str
     pub fn import_shops<R: Read>(reader: R, existing: &[Shop]) -> Result<Vec<Shop>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::Shop, &mut rdr)?;
         let mut shops: Vec<Shop> = existing.to_vec();
 
         for result in rdr.records() {
@@ -615,6 +751,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u16 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -640,6 +777,7 @@ impl CsvImporter {
     /// Import treasure chests from CSV.
     pub fn import_treasure_chests<R: Read>(reader: R) -> Result<Vec<TreasureChest>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::TreasureChest, &mut rdr)?;
         let mut chests = Vec::new();
 
         for result in rdr.records() {
@@ -647,6 +785,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
             let item_id: i32 = parse_or_default(record.get(1).unwrap_or("0"));
@@ -672,11 +811,15 @@ impl CsvImporter {
     /// 4: Trait ID, 5: [Trait Name], 6: Trait Value,
     /// 7: Ship Eff ID, 8: Ship Eff SP, 9: Ship Eff Turns, 10: Ship Eff Base,
     /// 11: [US Descr Pos], 12: [US Descr Size], 13: US Descr Str
+    ///
+    /// If the Position ID cell is empty, the position is looked up from
+    /// [Position Name] instead, and an unknown name is an error.
     pub fn import_crew_members<R: Read>(
         reader: R,
         existing: &[CrewMember],
     ) -> Result<Vec<CrewMember>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::CrewMember, &mut rdr)?;
         let mut members: Vec<CrewMember> = existing.to_vec();
 
         for result in rdr.records() {
@@ -684,12 +827,13 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
             if let Some(m) = members.iter_mut().find(|m| m.id == id) {
                 // Skip name - strings are read-only
-                m.position_id = parse_or_default(record.get(2).unwrap_or("0"));
+                m.position_id = Self::crew_position(id, &record)?;
                 m.trait_id = parse_or_default(record.get(4).unwrap_or("-1"));
                 m.trait_value = parse_or_default(record.get(6).unwrap_or("0"));
                 m.ship_effect_id = parse_or_default(record.get(7).unwrap_or("0"));
@@ -703,18 +847,39 @@ impl CsvImporter {
         Ok(members)
     }
 
+    /// Read a crew member's position from its ID cell, or from its name cell
+    /// if the ID cell is empty.
+    fn crew_position(id: u32, record: &Row) -> Result<i8> {
+        let position_id = record.get(2).unwrap_or("").trim();
+        if !position_id.is_empty() {
+            return Ok(parse_or_default(position_id));
+        }
+        let name = record.get(3).unwrap_or("").trim();
+        if name.is_empty() {
+            return Ok(0);
+        }
+        crate::lookups::position_id_by_name(name).ok_or_else(|| {
+            Error::ValidationError(format!(
+                "Crew member {} has unknown position name '{}'",
+                id, name
+            ))
+        })
+    }
+
     /// Import playable ships from CSV.
     /// Import playable ships from CSV, merging with existing data.
     ///
     /// CSV columns (matching export):
     /// 0: Entry ID, 1: Entry US Name, 2: MAXHP, 3: MAXSP, 4: SP, 5: Defense,
     /// 6: MagDef, 7: Quick, 8: Dodge%, 9-14: Elements (6),
-    /// 15-19: Cannon IDs (5), 20-22: Accessory IDs (3)
+    /// 15-19: Cannon IDs (5), 20-22: Accessory IDs (3), 23: Value,
+    /// 24-30: Growth (MAXHP, MAXSP, SP, Defense, MagDef, Quick, Dodge%)
     pub fn import_playable_ships<R: Read>(
         reader: R,
         existing: &[PlayableShip],
     ) -> Result<Vec<PlayableShip>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::with_optional(EntryKind::PlayableShip, &mut rdr, 23..)?;
         let mut ships: Vec<PlayableShip> = existing.to_vec();
 
         for result in rdr.records() {
@@ -722,6 +887,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -746,6 +912,17 @@ impl CsvImporter {
                 for i in 0..3 {
                     ship.accessory_ids[i] = parse_or_default(record.get(20 + i).unwrap_or("-1"));
                 }
+
+                // Older exports stop at the accessories, so keep the values
+                // they don't have
+                set_if_present(&mut ship.value, &record, 23, parse_or_default);
+                set_if_present(&mut ship.max_hp_growth, &record, 24, parse_or_default);
+                set_if_present(&mut ship.max_sp_growth, &record, 25, parse_or_default);
+                set_if_present(&mut ship.sp_growth, &record, 26, parse_or_default);
+                set_if_present(&mut ship.defense_growth, &record, 27, parse_or_default);
+                set_if_present(&mut ship.mag_def_growth, &record, 28, parse_or_default);
+                set_if_present(&mut ship.quick_growth, &record, 29, parse_or_default);
+                set_if_present(&mut ship.dodge_growth, &record, 30, parse_or_default);
             }
         }
 
@@ -766,6 +943,7 @@ impl CsvImporter {
         existing: &[ShipCannon],
     ) -> Result<Vec<ShipCannon>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::ShipCannon, &mut rdr)?;
         let mut cannons: Vec<ShipCannon> = existing.to_vec();
 
         for result in rdr.records() {
@@ -773,6 +951,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -811,6 +990,7 @@ impl CsvImporter {
         existing: &[ShipAccessory],
     ) -> Result<Vec<ShipAccessory>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::ShipAccessory, &mut rdr)?;
         let mut accessories: Vec<ShipAccessory> = existing.to_vec();
 
         for result in rdr.records() {
@@ -818,6 +998,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -855,6 +1036,7 @@ impl CsvImporter {
     /// 16: Unk 1, 17: Unk 2, 18: Hit%, 19-21: Description
     pub fn import_ship_items<R: Read>(reader: R, existing: &[ShipItem]) -> Result<Vec<ShipItem>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::ShipItem, &mut rdr)?;
         let mut items: Vec<ShipItem> = existing.to_vec();
 
         for result in rdr.records() {
@@ -862,6 +1044,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -899,6 +1082,7 @@ impl CsvImporter {
         existing: &[EnemyShip],
     ) -> Result<Vec<EnemyShip>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::EnemyShip, &mut rdr)?;
         let mut ships: Vec<EnemyShip> = existing.to_vec();
 
         for result in rdr.records() {
@@ -906,6 +1090,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -964,6 +1149,7 @@ impl CsvImporter {
         existing: &[EnemyMagic],
     ) -> Result<Vec<EnemyMagic>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::EnemyMagic, &mut rdr)?;
         let mut magic: Vec<EnemyMagic> = existing.to_vec();
 
         for result in rdr.records() {
@@ -971,6 +1157,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -1007,6 +1194,7 @@ impl CsvImporter {
         existing: &[EnemySuperMove],
     ) -> Result<Vec<EnemySuperMove>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::EnemySuperMove, &mut rdr)?;
         let mut moves: Vec<EnemySuperMove> = existing.to_vec();
 
         for result in rdr.records() {
@@ -1014,6 +1202,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -1039,6 +1228,7 @@ impl CsvImporter {
     /// Import swashbucklers from CSV.
     pub fn import_swashbucklers<R: Read>(reader: R) -> Result<Vec<Swashbuckler>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::Swashbuckler, &mut rdr)?;
         let mut swashbucklers = Vec::new();
 
         for result in rdr.records() {
@@ -1046,6 +1236,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
             let name = record.get(1).unwrap_or("").to_string();
@@ -1074,6 +1265,7 @@ impl CsvImporter {
     /// Import spirit curves from CSV.
     pub fn import_spirit_curves<R: Read>(reader: R) -> Result<Vec<SpiritCurve>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::SpiritCurve, &mut rdr)?;
         let mut curves = Vec::new();
 
         for result in rdr.records() {
@@ -1081,6 +1273,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -1111,6 +1304,7 @@ impl CsvImporter {
     /// Import exp boosts from CSV.
     pub fn import_exp_boosts<R: Read>(reader: R) -> Result<Vec<ExpBoost>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::ExpBoost, &mut rdr)?;
         let mut boosts = Vec::new();
 
         for result in rdr.records() {
@@ -1118,6 +1312,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
             let character_name = record.get(1).unwrap_or("").to_string();
@@ -1152,6 +1347,7 @@ impl CsvImporter {
     /// CSV columns: 0: Entry ID, 1: [PC Name], 2-100: EXP 1-99
     pub fn import_exp_curves<R: Read>(reader: R, existing: &[ExpCurve]) -> Result<Vec<ExpCurve>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::ExpCurve, &mut rdr)?;
         let mut curves: Vec<ExpCurve> = existing.to_vec();
 
         for result in rdr.records() {
@@ -1159,6 +1355,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -1188,6 +1385,7 @@ impl CsvImporter {
         existing: &[MagicExpCurve],
     ) -> Result<Vec<MagicExpCurve>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::MagicExpCurve, &mut rdr)?;
         let mut curves: Vec<MagicExpCurve> = existing.to_vec();
 
         for result in rdr.records() {
@@ -1195,6 +1393,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
 
@@ -1245,6 +1444,7 @@ impl CsvImporter {
         existing: &[EnemyEncounter],
     ) -> Result<Vec<EnemyEncounter>> {
         let mut rdr = csv::Reader::from_reader(reader);
+        let columns = Columns::new(EntryKind::EnemyEncounter, &mut rdr)?;
         let mut encounters: Vec<EnemyEncounter> = existing.to_vec();
 
         // Build a map for quick lookup by (id, filter)
@@ -1259,6 +1459,7 @@ impl CsvImporter {
                 offset: 0,
                 message: format!("CSV parse error: {}", e),
             })?;
+            let record = columns.row(&record);
 
             let id: u32 = parse_or_default(record.get(0).unwrap_or("0"));
             let filter = record.get(1).unwrap_or("").to_string();
@@ -1299,4 +1500,105 @@ impl CsvImporter {
 
         Ok(encounters)
     }
+
+    /// Import one kind of entry into a [`GameData`] snapshot.
+    ///
+    /// Kinds whose importer keeps values for missing columns are merged
+    /// with the entries already in `data`. Enemy data is imported from
+    /// ENP/EVP JSON, not CSV, so those kinds are an error. Returns the
+    /// number of entries imported.
+    pub fn import_kind<R: Read>(kind: EntryKind, reader: R, data: &mut GameData) -> Result<usize> {
+        macro_rules! import {
+            ($field:ident, $import:expr) => {{
+                data.$field = $import?;
+                data.$field.len()
+            }};
+        }
+
+        let count = match kind {
+            EntryKind::Accessory => import!(accessories, Self::import_accessories(reader)),
+            EntryKind::Armor => import!(armors, Self::import_armors(reader)),
+            EntryKind::Weapon => import!(weapons, Self::import_weapons(reader)),
+            EntryKind::UsableItem => import!(
+                usable_items,
+                Self::import_usable_items(reader, &data.usable_items)
+            ),
+            EntryKind::SpecialItem => import!(special_items, Self::import_special_items(reader)),
+            EntryKind::Character => {
+                import!(
+                    characters,
+                    Self::import_characters(reader, &data.characters)
+                )
+            }
+            EntryKind::CharacterMagic => import!(
+                character_magic,
+                Self::import_character_magic(reader, &data.character_magic)
+            ),
+            EntryKind::CharacterSuperMove => import!(
+                character_super_moves,
+                Self::import_character_super_moves(reader, &data.character_super_moves)
+            ),
+            EntryKind::Shop => import!(shops, Self::import_shops(reader, &data.shops)),
+            EntryKind::TreasureChest => {
+                import!(treasure_chests, Self::import_treasure_chests(reader))
+            }
+            EntryKind::CrewMember => import!(
+                crew_members,
+                Self::import_crew_members(reader, &data.crew_members)
+            ),
+            EntryKind::PlayableShip => import!(
+                playable_ships,
+                Self::import_playable_ships(reader, &data.playable_ships)
+            ),
+            EntryKind::ShipCannon => import!(
+                ship_cannons,
+                Self::import_ship_cannons(reader, &data.ship_cannons)
+            ),
+            EntryKind::ShipAccessory => import!(
+                ship_accessories,
+                Self::import_ship_accessories(reader, &data.ship_accessories)
+            ),
+            EntryKind::ShipItem => {
+                import!(
+                    ship_items,
+                    Self::import_ship_items(reader, &data.ship_items)
+                )
+            }
+            EntryKind::EnemyShip => import!(
+                enemy_ships,
+                Self::import_enemy_ships(reader, &data.enemy_ships)
+            ),
+            EntryKind::EnemyMagic => import!(
+                enemy_magic,
+                Self::import_enemy_magic(reader, &data.enemy_magic)
+            ),
+            EntryKind::EnemySuperMove => import!(
+                enemy_super_moves,
+                Self::import_enemy_super_moves(reader, &data.enemy_super_moves)
+            ),
+            EntryKind::Swashbuckler => import!(swashbucklers, Self::import_swashbucklers(reader)),
+            EntryKind::SpiritCurve => import!(spirit_curves, Self::import_spirit_curves(reader)),
+            EntryKind::ExpBoost => import!(exp_boosts, Self::import_exp_boosts(reader)),
+            EntryKind::ExpCurve => {
+                import!(
+                    exp_curves,
+                    Self::import_exp_curves(reader, &data.exp_curves)
+                )
+            }
+            EntryKind::MagicExpCurve => import!(
+                magic_exp_curves,
+                Self::import_magic_exp_curves(reader, &data.magic_exp_curves)
+            ),
+            EntryKind::Enemy
+            | EntryKind::EnemyTask
+            | EntryKind::EnemyEncounter
+            | EntryKind::EnemyEvent => {
+                return Err(Error::ValidationError(format!(
+                    "{} are imported from ENP/EVP JSON, not CSV",
+                    kind.label()
+                )))
+            }
+        };
+        Ok(count)
+    }
 }
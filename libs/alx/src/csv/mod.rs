@@ -2,6 +2,8 @@
 
 mod export;
 mod import;
+mod progress;
 
 pub use export::CsvExporter;
 pub use import::CsvImporter;
+pub use progress::{ExportProgress, ImportProgress, ProgressTracker};
@@ -0,0 +1,100 @@
+//! Progress reports for multi-kind exports and imports.
+
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+/// Progress of an export, reported before each stage and once when done.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportProgress {
+    /// Stage about to run, e.g. an entry kind name, or "done"
+    pub stage: String,
+    /// Number of stages finished, from 0 to `total`
+    pub index: usize,
+    pub total: usize,
+    /// Entries written by the finished stages
+    pub entries_done: usize,
+    /// Rough seconds left, from the average time per finished stage
+    pub eta_secs: Option<f64>,
+}
+
+/// Progress of an import; same fields as [`ExportProgress`].
+pub type ImportProgress = ExportProgress;
+
+impl ExportProgress {
+    /// Check if every stage has finished.
+    pub fn is_done(&self) -> bool {
+        self.index >= self.total
+    }
+}
+
+/// Builds [`ExportProgress`] reports for a run of `total` stages.
+#[derive(Debug, Clone)]
+pub struct ProgressTracker {
+    started: Instant,
+    total: usize,
+    entries_done: usize,
+}
+
+impl ProgressTracker {
+    /// Start timing a run of `total` stages.
+    pub fn new(total: usize) -> Self {
+        Self {
+            started: Instant::now(),
+            total,
+            entries_done: 0,
+        }
+    }
+
+    /// Count the entries written by a finished stage.
+    pub fn add_entries(&mut self, count: usize) {
+        self.entries_done += count;
+    }
+
+    /// Report that `index` stages have finished and `stage` is next.
+    pub fn progress(&self, stage: &str, index: usize) -> ExportProgress {
+        let index = index.min(self.total);
+        let eta_secs = (index > 0).then(|| {
+            let per_stage = self.started.elapsed().as_secs_f64() / index as f64;
+            per_stage * (self.total - index) as f64
+        });
+        ExportProgress {
+            stage: stage.to_string(),
+            index,
+            total: self.total,
+            entries_done: self.entries_done,
+            eta_secs,
+        }
+    }
+
+    /// Report that every stage has finished.
+    pub fn done(&self) -> ExportProgress {
+        self.progress("done", self.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_eta() {
+        let mut tracker = ProgressTracker::new(2);
+        let first = tracker.progress("weapon", 0);
+        assert_eq!(first.eta_secs, None);
+        assert!(!first.is_done());
+
+        tracker.add_entries(5);
+        let second = tracker.progress("armor", 1);
+        assert_eq!(second.entries_done, 5);
+        assert!(second.eta_secs.unwrap() >= 0.0);
+
+        let done = tracker.done();
+        assert_eq!(
+            (done.stage.as_str(), done.index, done.total),
+            ("done", 2, 2)
+        );
+        assert_eq!(done.eta_secs, Some(0.0));
+        assert!(done.is_done());
+    }
+}
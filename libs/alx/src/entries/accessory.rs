@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
 use super::armor::{Armor, CharacterFlags};
+use super::check_write_id;
 use super::traits::Trait;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
@@ -13,7 +14,9 @@ use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
 
 /// Accessory entry (uses same structure as Armor).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct Accessory {
     /// Entry ID
     pub id: u32,
@@ -139,7 +142,7 @@ impl Accessory {
     }
 
     /// Get entry size for a specific version.
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 1,
             _ => Self::ENTRY_SIZE,
@@ -165,6 +168,11 @@ impl Accessory {
         }
     }
 
+    /// Check that the ID is in the accessory table (160-239).
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Accessory", self.id, &id_ranges::ACCESSORY)
+    }
+
     /// Patch all accessory entries into a buffer.
     pub fn patch_all(accessories: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
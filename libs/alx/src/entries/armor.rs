@@ -2,16 +2,19 @@
 
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
+use std::str::FromStr;
 
 use super::traits::Trait;
-use crate::error::Result;
+use super::{check_write_id, parse_binary_flags};
+use crate::error::{Error, Result};
 use crate::game::offsets::id_ranges;
 use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
 
 /// Character flags for equipment.
 /// Bit 5 = Vyse, Bit 4 = Aika, Bit 3 = Fina, Bit 2 = Drachma, Bit 1 = Enrique, Bit 0 = Gilder
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CharacterFlags(pub u8);
 
 impl CharacterFlags {
@@ -23,6 +26,16 @@ impl CharacterFlags {
     pub const ENRIQUE: u8 = 0x02;
     pub const GILDER: u8 = 0x01;
 
+    /// Flag bits indexed by character (Vyse = 0 ... Gilder = 5).
+    pub const CHARACTERS: [u8; 6] = [
+        Self::VYSE,
+        Self::AIKA,
+        Self::FINA,
+        Self::DRACHMA,
+        Self::ENRIQUE,
+        Self::GILDER,
+    ];
+
     pub fn can_equip_vyse(&self) -> bool {
         self.0 & Self::VYSE != 0
     }
@@ -42,6 +55,32 @@ impl CharacterFlags {
         self.0 & Self::GILDER != 0
     }
 
+    /// Check whether the character at `index` (see [`Self::CHARACTERS`]) can
+    /// equip. Indices past Gilder are never set.
+    pub fn can_equip(&self, index: usize) -> bool {
+        Self::CHARACTERS
+            .get(index)
+            .is_some_and(|&bit| self.0 & bit != 0)
+    }
+
+    /// Allow or forbid the character at `index` to equip. Indices past
+    /// Gilder are ignored.
+    pub fn set_character(&mut self, index: usize, can_equip: bool) {
+        if let Some(&bit) = Self::CHARACTERS.get(index) {
+            if can_equip {
+                self.0 |= bit;
+            } else {
+                self.0 &= !bit;
+            }
+        }
+    }
+
+    /// Builder form of [`set_character`](Self::set_character).
+    pub fn with_character(mut self, index: usize, can_equip: bool) -> Self {
+        self.set_character(index, can_equip);
+        self
+    }
+
     /// Format as binary string for CSV output (e.g., "0b00111010")
     pub fn as_binary_string(&self) -> String {
         format!("0b{:08b}", self.0)
@@ -60,8 +99,19 @@ impl CharacterFlags {
     }
 }
 
+impl FromStr for CharacterFlags {
+    type Err = Error;
+
+    /// Parse the CSV binary-string format (e.g. `"0b00111010"`).
+    fn from_str(s: &str) -> Result<Self> {
+        parse_binary_flags("character flags", s).map(Self)
+    }
+}
+
 /// Armor entry (also used as base for Accessory).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct Armor {
     /// Entry ID
     pub id: u32,
@@ -188,7 +238,7 @@ impl Armor {
     }
 
     /// Get entry size for a specific version.
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 1, // EU has extra padding
             _ => Self::ENTRY_SIZE,
@@ -212,6 +262,11 @@ impl Armor {
         }
     }
 
+    /// Check that the ID is in the armor table (80-159).
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Armor", self.id, &id_ranges::ARMOR)
+    }
+
     /// Patch all armor entries into a buffer.
     pub fn patch_all(armors: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
@@ -231,6 +286,35 @@ mod tests {
     use super::*;
     use std::io::Cursor;
 
+    #[test]
+    fn test_character_flags_builder_and_parse() {
+        let flags = CharacterFlags::default()
+            .with_character(0, true)
+            .with_character(2, true)
+            .with_character(5, true)
+            .with_character(6, true);
+        assert_eq!(
+            flags.0,
+            CharacterFlags::VYSE | CharacterFlags::FINA | CharacterFlags::GILDER
+        );
+        assert!(flags.can_equip(2));
+        assert!(!flags.can_equip(1));
+        assert!(!flags.can_equip(6));
+
+        let flags = flags.with_character(0, false);
+        assert!(!flags.can_equip_vyse());
+
+        // Round-trip through the CSV format
+        for value in [0u8, 0b00111010, 0b00111111] {
+            let flags = CharacterFlags(value);
+            let parsed: CharacterFlags = flags.as_binary_string().parse().unwrap();
+            assert_eq!(parsed, flags);
+        }
+        assert_eq!("101".parse::<CharacterFlags>().unwrap().0, 0b101);
+        assert!("0b2".parse::<CharacterFlags>().is_err());
+        assert!("0b111111111".parse::<CharacterFlags>().is_err());
+    }
+
     #[test]
     fn test_character_flags() {
         // 0b00111010: V=1, A=1, F=1, D=0, E=1, G=0
@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::GameVersion;
 use crate::io::BinaryReader;
 
 /// Playable character entry.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct Character {
     /// Entry ID (0-5 for the 6 playable characters)
     pub id: u32,
@@ -366,6 +369,11 @@ impl Character {
         }
     }
 
+    /// Check that the ID is one of the six playable characters.
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Character", self.id, &id_ranges::CHARACTER)
+    }
+
     /// Patch all character entries into a buffer.
     /// Buffer must be the original DOL section data.
     pub fn patch_all(chars: &[Self], buf: &mut [u8]) {
@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
 
 /// Character magic/spell entry.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct CharacterMagic {
     /// Entry ID
     pub id: u32,
@@ -247,7 +250,7 @@ impl CharacterMagic {
         Ok(magics)
     }
 
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 1,
             _ => Self::ENTRY_SIZE,
@@ -279,6 +282,11 @@ impl CharacterMagic {
         buf[Self::OFF_UNKNOWN] = self.unknown as u8;
     }
 
+    /// Check that the ID is one of the 36 character magic slots (0-35).
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Character magic", self.id, &id_ranges::CHARACTER_MAGIC)
+    }
+
     /// Patch all magic entries into a buffer.
     pub fn patch_all(entries: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
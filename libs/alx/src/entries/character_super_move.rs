@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
 
 /// A character super move (S-Move) in the game.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct CharacterSuperMove {
     /// Entry ID
     pub id: u32,
@@ -173,7 +176,7 @@ impl CharacterSuperMove {
         Ok(entries)
     }
 
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 1,
             _ => Self::ENTRY_SIZE,
@@ -220,6 +223,15 @@ impl CharacterSuperMove {
         buf[Self::OFF_UNKNOWN] = self.unknown as u8;
     }
 
+    /// Check that the ID is in the character super move table (36-61).
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id(
+            "Character super move",
+            self.id,
+            &id_ranges::CHARACTER_SUPER_MOVE,
+        )
+    }
+
     /// Patch all CharacterSuperMove entries into a buffer.
     pub fn patch_all(entries: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
 
 /// A crew member in the game.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct CrewMember {
     /// Entry ID
     pub id: u32,
@@ -57,20 +60,7 @@ impl CrewMember {
 
     /// Get position name.
     pub fn position_name(&self) -> &'static str {
-        match self.position_id {
-            0 => "Helmsman",
-            1 => "Engineer",
-            2 => "Gunner",
-            3 => "Artisan",
-            4 => "Sailor",
-            5 => "Cook",
-            6 => "Merchant",
-            7 => "Builder",
-            8 => "Lookout",
-            9 => "Jester",
-            10 => "Delegate",
-            _ => "???",
-        }
+        crate::lookups::position_name(self.position_id)
     }
 
     /// Read a single crew member from binary data.
@@ -135,7 +125,7 @@ impl CrewMember {
         Ok(entries)
     }
 
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 1,
             _ => Self::ENTRY_SIZE,
@@ -156,6 +146,11 @@ impl CrewMember {
         buf[Self::OFF_UNKNOWN..Self::OFF_UNKNOWN + 2].copy_from_slice(&self.unknown.to_be_bytes());
     }
 
+    /// Check that the ID is one of the 22 crew members.
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Crew member", self.id, &id_ranges::CREW_MEMBER)
+    }
+
     /// Patch all crew member entries into a buffer.
     pub fn patch_all(entries: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
@@ -9,7 +9,9 @@ use crate::io::BinaryReader;
 use crate::lookups::{EFFECT_NAMES, ELEMENT_NAMES, STATE_NAMES};
 
 /// An item drop from an enemy.
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct EnemyItemDrop {
     /// Drop probability
     pub probability: i16,
@@ -20,7 +22,9 @@ pub struct EnemyItemDrop {
 }
 
 /// An enemy in the game.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct Enemy {
     /// Entry ID
     pub id: u32,
@@ -196,55 +200,6 @@ impl Enemy {
     pub fn state_name(&self) -> &'static str {
         STATE_NAMES.get(self.state_id)
     }
-
-    /// Check movement flag: May Dodge
-    pub fn may_dodge(&self) -> bool {
-        (self.movement_flags & 0x800) != 0
-    }
-    /// Check movement flag: Unknown Damage
-    pub fn unk_damage(&self) -> bool {
-        (self.movement_flags & 0x400) != 0
-    }
-    /// Check movement flag: Unknown Ranged
-    pub fn unk_ranged(&self) -> bool {
-        (self.movement_flags & 0x200) != 0
-    }
-    /// Check movement flag: Unknown Melee
-    pub fn unk_melee(&self) -> bool {
-        (self.movement_flags & 0x100) != 0
-    }
-    /// Check movement flag: Ranged Attack
-    pub fn ranged_atk(&self) -> bool {
-        (self.movement_flags & 0x080) != 0
-    }
-    /// Check movement flag: Melee Attack
-    pub fn melee_atk(&self) -> bool {
-        (self.movement_flags & 0x040) != 0
-    }
-    /// Check movement flag: Ranged Only
-    pub fn ranged_only(&self) -> bool {
-        (self.movement_flags & 0x020) != 0
-    }
-    /// Check movement flag: Take Cover
-    pub fn take_cover(&self) -> bool {
-        (self.movement_flags & 0x010) != 0
-    }
-    /// Check movement flag: In Air
-    pub fn in_air(&self) -> bool {
-        (self.movement_flags & 0x008) != 0
-    }
-    /// Check movement flag: On Ground
-    pub fn on_ground(&self) -> bool {
-        (self.movement_flags & 0x004) != 0
-    }
-    /// Check movement flag: Reserved
-    pub fn reserved(&self) -> bool {
-        (self.movement_flags & 0x002) != 0
-    }
-    /// Check movement flag: May Move
-    pub fn may_move(&self) -> bool {
-        (self.movement_flags & 0x001) != 0
-    }
 }
 
 #[cfg(test)]
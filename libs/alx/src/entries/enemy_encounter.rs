@@ -6,14 +6,19 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::io::BinaryReader;
 
 /// Maximum number of enemy slots per encounter
 pub const MAX_ENEMY_SLOTS: usize = 8;
 
+/// Enemy ID marking an empty slot
+pub const EMPTY_SLOT_ID: u8 = 255;
+
 /// Represents an enemy slot in an encounter (enemy ID or 255 for none)
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct EnemySlot {
     /// Enemy ID (255 = none/empty slot)
     pub enemy_id: u8,
@@ -28,12 +33,14 @@ pub struct EnemySlot {
 impl EnemySlot {
     /// Check if this slot is empty (no enemy)
     pub fn is_empty(&self) -> bool {
-        self.enemy_id == 255
+        self.enemy_id == EMPTY_SLOT_ID
     }
 }
 
 /// An enemy encounter defining a battle formation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct EnemyEncounter {
     /// Entry ID (0-based within the ENP segment)
     pub id: u32,
@@ -69,6 +76,28 @@ impl EnemyEncounter {
         Self::default()
     }
 
+    /// Set the initiative, clamped to 0-255.
+    ///
+    /// Returns the value actually stored. Use this instead of writing the
+    /// field from computed values so they can't wrap around.
+    pub fn set_initiative(&mut self, initiative: i32) -> u8 {
+        self.initiative = clamp_to_byte(initiative);
+        self.initiative
+    }
+
+    /// Set the magic EXP reward, clamped to 0-255.
+    ///
+    /// Returns the value actually stored.
+    pub fn set_magic_exp(&mut self, magic_exp: i32) -> u8 {
+        self.magic_exp = clamp_to_byte(magic_exp);
+        self.magic_exp
+    }
+
+    /// Start building a new encounter with all slots empty.
+    pub fn builder() -> EnemyEncounterBuilder {
+        EnemyEncounterBuilder::new()
+    }
+
     /// Read a single encounter from binary data.
     pub fn read_one(cursor: &mut Cursor<&[u8]>, id: u32, filter: &str) -> Result<Self> {
         let initiative = cursor.read_u8()?;
@@ -133,6 +162,99 @@ impl EnemyEncounter {
     }
 }
 
+/// Builder for creating encounters programmatically.
+///
+/// Values are validated as they are set, so a successful `build()` always
+/// produces an encounter that can be written back as-is.
+#[derive(Debug, Clone)]
+pub struct EnemyEncounterBuilder {
+    encounter: EnemyEncounter,
+    slot_count: usize,
+}
+
+impl EnemyEncounterBuilder {
+    /// Create a builder for an encounter with all slots empty.
+    pub fn new() -> Self {
+        let mut encounter = EnemyEncounter::default();
+        for slot in &mut encounter.enemy_slots {
+            slot.enemy_id = EMPTY_SLOT_ID;
+        }
+        Self {
+            encounter,
+            slot_count: 0,
+        }
+    }
+
+    /// Set the entry ID.
+    pub fn id(mut self, id: u32) -> Self {
+        self.encounter.id = id;
+        self
+    }
+
+    /// Set the source file filter.
+    pub fn filter(mut self, filter: &str) -> Self {
+        self.encounter.filter = filter.to_string();
+        self
+    }
+
+    /// Set the initiative value (must fit in a byte).
+    pub fn initiative(mut self, initiative: i32) -> Result<Self> {
+        self.encounter.initiative = to_byte("Initiative", initiative)?;
+        Ok(self)
+    }
+
+    /// Set the magic EXP reward (must fit in a byte).
+    pub fn magic_exp(mut self, magic_exp: i32) -> Result<Self> {
+        self.encounter.magic_exp = to_byte("Magic EXP", magic_exp)?;
+        Ok(self)
+    }
+
+    /// Add an enemy to the next free slot.
+    pub fn add_enemy(mut self, enemy_id: u8) -> Result<Self> {
+        if enemy_id == EMPTY_SLOT_ID {
+            return Err(Error::ValidationError(format!(
+                "Enemy ID {} is reserved for empty slots",
+                EMPTY_SLOT_ID
+            )));
+        }
+        if self.slot_count >= MAX_ENEMY_SLOTS {
+            return Err(Error::ValidationError(format!(
+                "Encounter already has the maximum of {} enemies",
+                MAX_ENEMY_SLOTS
+            )));
+        }
+        self.encounter.enemy_slots[self.slot_count].enemy_id = enemy_id;
+        self.slot_count += 1;
+        Ok(self)
+    }
+
+    /// Finish building the encounter.
+    pub fn build(self) -> EnemyEncounter {
+        self.encounter
+    }
+}
+
+impl Default for EnemyEncounterBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Clamp a value into an unsigned byte field.
+fn clamp_to_byte(value: i32) -> u8 {
+    value.clamp(0, u8::MAX as i32) as u8
+}
+
+/// Check that a value fits in an unsigned byte field.
+fn to_byte(field: &str, value: i32) -> Result<u8> {
+    u8::try_from(value).map_err(|_| {
+        Error::ValidationError(format!(
+            "{} must be between 0 and 255, got {}",
+            field, value
+        ))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,6 +264,22 @@ mod tests {
         assert_eq!(EnemyEncounter::ENTRY_SIZE, 10);
     }
 
+    #[test]
+    fn test_setters_clamp_at_boundaries() {
+        let mut encounter = EnemyEncounter::new();
+
+        assert_eq!(encounter.set_initiative(-1), 0);
+        assert_eq!(encounter.set_initiative(0), 0);
+        assert_eq!(encounter.set_initiative(255), 255);
+        assert_eq!(encounter.set_initiative(256), 255);
+        assert_eq!(encounter.initiative, 255);
+
+        assert_eq!(encounter.set_magic_exp(i32::MIN), 0);
+        assert_eq!(encounter.set_magic_exp(7), 7);
+        assert_eq!(encounter.set_magic_exp(i32::MAX), 255);
+        assert_eq!(encounter.magic_exp, 255);
+    }
+
     #[test]
     fn test_read_write_roundtrip() {
         let original = EnemyEncounter {
@@ -269,4 +407,46 @@ mod tests {
         assert!(empty.is_empty());
         assert!(!filled.is_empty());
     }
+
+    #[test]
+    fn test_builder_valid() {
+        let enc = EnemyEncounter::builder()
+            .id(3)
+            .filter("a101b_ep.enp")
+            .initiative(102)
+            .unwrap()
+            .magic_exp(2)
+            .unwrap()
+            .add_enemy(42)
+            .unwrap()
+            .add_enemy(7)
+            .unwrap()
+            .build();
+
+        assert_eq!(enc.id, 3);
+        assert_eq!(enc.filter, "a101b_ep.enp");
+        assert_eq!(enc.initiative, 102);
+        assert_eq!(enc.magic_exp, 2);
+        assert_eq!(enc.enemy_slots[0].enemy_id, 42);
+        assert_eq!(enc.enemy_slots[1].enemy_id, 7);
+        assert!(enc.enemy_slots[2..].iter().all(|s| s.is_empty()));
+        assert_eq!(enc.enemy_count(), 2);
+    }
+
+    #[test]
+    fn test_builder_rejects_too_many_slots() {
+        let mut builder = EnemyEncounter::builder();
+        for i in 0..MAX_ENEMY_SLOTS {
+            builder = builder.add_enemy(i as u8).unwrap();
+        }
+        assert!(builder.add_enemy(0).is_err());
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_values() {
+        assert!(EnemyEncounter::builder().initiative(256).is_err());
+        assert!(EnemyEncounter::builder().initiative(-1).is_err());
+        assert!(EnemyEncounter::builder().magic_exp(300).is_err());
+        assert!(EnemyEncounter::builder().add_enemy(EMPTY_SLOT_ID).is_err());
+    }
 }
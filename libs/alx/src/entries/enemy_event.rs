@@ -18,6 +18,8 @@ pub const MAX_EVENT_ENEMIES: usize = 7;
 
 /// A character slot in an event (party member position)
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct EventCharacterSlot {
     /// Character ID (-1 = none/empty slot)
     pub character_id: i8,
@@ -39,6 +41,8 @@ impl EventCharacterSlot {
 
 /// An enemy slot in an event (enemy position)
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct EventEnemySlot {
     /// Enemy ID (255 = none/empty slot)
     pub enemy_id: u8,
@@ -77,7 +81,9 @@ pub const ESCAPE_CONDITIONS: [&str; 3] = [
 ];
 
 /// An enemy event defining a scripted battle scenario.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct EnemyEvent {
     /// Entry ID (0-based within the EVP file)
     pub id: u32,
@@ -10,7 +10,9 @@ use crate::io::{BinaryReader, BinaryWriter};
 use crate::lookups::{EFFECT_NAMES, ELEMENT_NAMES, STATE_NAMES};
 
 /// An enemy magic spell.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct EnemyMagic {
     /// Entry ID
     pub id: u32,
@@ -116,7 +118,7 @@ impl EnemyMagic {
         Ok(entries)
     }
 
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE_EU,
             _ => Self::ENTRY_SIZE_US_JP,
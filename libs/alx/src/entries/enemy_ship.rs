@@ -2,14 +2,17 @@
 
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
+use std::ops::RangeInclusive;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::game::offsets::id_ranges;
 use crate::game::region::GameVersion;
 use crate::io::{BinaryReader, BinaryWriter};
 
 /// An armament slot on an enemy ship.
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct ShipArmament {
     /// Type ID (0=Main Cannon, 1=Secondary, 2=Torpedo)
     pub type_id: i16,
@@ -24,7 +27,9 @@ pub struct ShipArmament {
 }
 
 /// An item drop from an enemy ship.
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct ShipItemDrop {
     /// Drop rate ID
     pub drop_id: i16,
@@ -32,8 +37,63 @@ pub struct ShipItemDrop {
     pub item_id: i16,
 }
 
+impl ShipArmament {
+    /// Valid type IDs; -1 marks an empty slot.
+    pub const TYPE_IDS: RangeInclusive<i16> = -1..=3;
+
+    /// Valid element IDs (-1=None through 6=Neutral).
+    pub const ELEMENT_IDS: RangeInclusive<i16> = -1..=6;
+
+    /// Create an armament, checking every field is in range.
+    ///
+    /// Attack and range must not be negative and hit is a percentage.
+    pub fn new(type_id: i16, attack: i16, range: i16, hit: i16, element_id: i16) -> Result<Self> {
+        check_range("Armament type ID", type_id, Self::TYPE_IDS)?;
+        check_range("Armament attack", attack, 0..=i16::MAX)?;
+        check_range("Armament range", range, 0..=i16::MAX)?;
+        check_range("Armament hit", hit, 0..=100)?;
+        check_range("Armament element ID", element_id, Self::ELEMENT_IDS)?;
+        Ok(Self {
+            type_id,
+            attack,
+            range,
+            hit,
+            element_id,
+        })
+    }
+}
+
+impl ShipItemDrop {
+    /// Valid item IDs: every item up to the last ship item; -1 is no drop.
+    pub const ITEM_IDS: RangeInclusive<i16> = -1..=(id_ranges::SHIP_ITEM.end as i16 - 1);
+
+    /// Create an item drop, checking the item ID and that the drop rate ID
+    /// is -1 (none) or more.
+    pub fn new(drop_id: i16, item_id: i16) -> Result<Self> {
+        check_range("Drop rate ID", drop_id, -1..=i16::MAX)?;
+        check_range("Drop item ID", item_id, Self::ITEM_IDS)?;
+        Ok(Self { drop_id, item_id })
+    }
+}
+
+fn check_range(what: &str, value: i16, range: RangeInclusive<i16>) -> Result<()> {
+    if range.contains(&value) {
+        Ok(())
+    } else {
+        Err(Error::ValidationError(format!(
+            "{} {} is outside {}..={}",
+            what,
+            value,
+            range.start(),
+            range.end()
+        )))
+    }
+}
+
 /// An enemy ship in the game.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct EnemyShip {
     /// Entry ID
     pub id: u32,
@@ -150,7 +210,7 @@ impl EnemyShip {
         Ok(entries)
     }
 
-    fn entry_size_for_version(_version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(_version: &GameVersion) -> usize {
         Self::ENTRY_SIZE
     }
 
@@ -202,4 +262,38 @@ mod tests {
     fn test_entry_size() {
         assert_eq!(EnemyShip::ENTRY_SIZE, 120);
     }
+
+    #[test]
+    fn test_armament_new() {
+        let armament = ShipArmament::new(0, 400, 3, 90, 1).unwrap();
+        assert_eq!(armament.attack, 400);
+        assert_eq!(ShipArmament::new(3, 400, 3, 90, 1).unwrap().type_id, 3);
+        assert_eq!(
+            ShipArmament::new(-1, 0, 0, 0, -1).unwrap(),
+            ShipArmament {
+                type_id: -1,
+                element_id: -1,
+                ..Default::default()
+            }
+        );
+
+        for (type_id, attack, range, hit, element_id) in [
+            (4, 400, 3, 90, 1),
+            (-2, 400, 3, 90, 1),
+            (0, -1, 3, 90, 1),
+            (0, 400, -1, 90, 1),
+            (0, 400, 3, 101, 1),
+            (0, 400, 3, 90, 7),
+        ] {
+            assert!(ShipArmament::new(type_id, attack, range, hit, element_id).is_err());
+        }
+    }
+
+    #[test]
+    fn test_item_drop_new() {
+        assert_eq!(ShipItemDrop::new(2, 0x1fd).unwrap().item_id, 0x1fd);
+        assert!(ShipItemDrop::new(-1, -1).is_ok());
+        assert!(ShipItemDrop::new(0, 0x1fe).is_err());
+        assert!(ShipItemDrop::new(-2, 0).is_err());
+    }
 }
@@ -11,6 +11,7 @@ use crate::lookups::{EFFECT_NAMES, ELEMENT_NAMES, STATE_NAMES};
 
 /// Category ID for enemy skills.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[repr(i8)]
 pub enum EnemySkillCategory {
     SMove = 0,
@@ -34,7 +35,9 @@ impl EnemySkillCategory {
 }
 
 /// An enemy super move (S-Move) or magic spell.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct EnemySuperMove {
     /// Entry ID
     pub id: u32,
@@ -141,7 +144,7 @@ impl EnemySuperMove {
         Ok(entries)
     }
 
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE_EU,
             _ => Self::ENTRY_SIZE_US_JP,
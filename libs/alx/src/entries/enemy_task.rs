@@ -10,7 +10,9 @@ use crate::lookups::{
 };
 
 /// An enemy AI task/action.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct EnemyTask {
     /// Task slot ID (1-based)
     pub id: u32,
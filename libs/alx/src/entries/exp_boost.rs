@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::GameVersion;
 use crate::io::{BinaryReader, BinaryWriter};
 
 /// Experience boost for a character that joins late.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct ExpBoost {
     /// Entry ID (character ID: 3=Drachma, 4=Enrique, 5=Gilder)
     pub id: u32,
@@ -98,9 +101,38 @@ impl ExpBoost {
         Ok(())
     }
 
+    /// Patch a single exp boost entry in a mutable buffer.
+    pub fn patch_entry(&self, buf: &mut [u8]) {
+        let values = [
+            self.exp,
+            self.green_exp,
+            self.red_exp,
+            self.purple_exp,
+            self.blue_exp,
+            self.yellow_exp,
+            self.silver_exp,
+        ];
+        for (chunk, value) in buf.chunks_exact_mut(4).zip(values) {
+            chunk.copy_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    /// Check that the ID is one of the EXP boost entries (3-5). Only builds
+    /// with an `exp_boost_data` table have them.
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Exp boost", self.id, &id_ranges::EXP_BOOST)
+    }
+
     /// Patch all exp boost entries into a buffer.
-    pub fn patch_all(_entries: &[Self], _buf: &mut [u8], _version: &GameVersion) {
-        // TODO: Implement proper patching
+    pub fn patch_all(entries: &[Self], buf: &mut [u8], _version: &GameVersion) {
+        for e in entries {
+            let idx = (e.id - id_ranges::EXP_BOOST.start) as usize;
+            let start = idx * Self::ENTRY_SIZE;
+            let end = start + Self::ENTRY_SIZE;
+            if end <= buf.len() {
+                e.patch_entry(&mut buf[start..end]);
+            }
+        }
     }
 }
 
@@ -112,4 +144,21 @@ mod tests {
     fn test_entry_size() {
         assert_eq!(ExpBoost::ENTRY_SIZE, 28);
     }
+
+    #[test]
+    fn test_patch_all_roundtrip() {
+        let version = GameVersion::from_game_id("GEAE8P").unwrap();
+        let data: Vec<u8> = (0..ExpBoost::ENTRY_SIZE * 3).map(|i| i as u8).collect();
+        let mut boosts = ExpBoost::read_all_data(&data, &version).unwrap();
+        boosts[1].exp = 12345;
+        boosts[2].silver_exp = 0;
+
+        let mut patched = data.clone();
+        ExpBoost::patch_all(&boosts, &mut patched, &version);
+        assert_eq!(ExpBoost::read_all_data(&patched, &version).unwrap(), boosts);
+        assert_eq!(
+            patched[..ExpBoost::ENTRY_SIZE],
+            data[..ExpBoost::ENTRY_SIZE]
+        );
+    }
 }
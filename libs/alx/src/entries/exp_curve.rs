@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
-use crate::error::Result;
+use super::check_write_id;
+use crate::error::{Error, Result};
 use crate::game::offsets::id_ranges;
 use crate::game::region::GameVersion;
 use crate::io::{BinaryReader, BinaryWriter};
 
 /// EXP curve for a character (EXP requirements for levels 1-99).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct ExpCurve {
     /// Entry ID (character ID)
     pub id: u32,
@@ -24,6 +27,9 @@ impl ExpCurve {
     /// 99 levels * 4 bytes (i32) = 396 bytes
     pub const ENTRY_SIZE: usize = 396;
 
+    /// Number of levels in a curve.
+    pub const MAX_LEVEL: usize = 99;
+
     /// Read a single EXP curve from binary data.
     pub fn read_one(cursor: &mut Cursor<&[u8]>, id: u32, _version: &GameVersion) -> Result<Self> {
         let mut exp_values = Vec::with_capacity(99);
@@ -98,6 +104,21 @@ impl ExpCurve {
         }
     }
 
+    /// Check that the ID is one of the six characters' EXP curves and that
+    /// there are no more than [`MAX_LEVEL`](Self::MAX_LEVEL) level values.
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("EXP curve", self.id, &id_ranges::EXP_CURVE)?;
+        if self.exp_values.len() > Self::MAX_LEVEL {
+            return Err(Error::ValidationError(format!(
+                "EXP curve ID {} has {} levels (max {})",
+                self.id,
+                self.exp_values.len(),
+                Self::MAX_LEVEL
+            )));
+        }
+        Ok(())
+    }
+
     /// Patch all EXP curve entries into a buffer.
     pub fn patch_all(entries: &[Self], buf: &mut [u8]) {
         for e in entries {
@@ -119,4 +140,21 @@ mod tests {
     fn test_entry_size() {
         assert_eq!(ExpCurve::ENTRY_SIZE, 396);
     }
+
+    #[test]
+    fn test_validate_for_write() {
+        let mut curve = ExpCurve {
+            id: 0,
+            character_name: String::new(),
+            exp_values: vec![0; ExpCurve::MAX_LEVEL],
+        };
+        assert!(curve.validate_for_write().is_ok());
+
+        curve.exp_values.push(0);
+        assert!(curve.validate_for_write().is_err());
+
+        curve.exp_values.truncate(ExpCurve::MAX_LEVEL);
+        curve.id = id_ranges::EXP_CURVE.end;
+        assert!(curve.validate_for_write().is_err());
+    }
 }
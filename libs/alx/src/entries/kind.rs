@@ -0,0 +1,288 @@
+//! Entry kind - the set of data types that can be exported/imported.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// A kind of game data entry.
+///
+/// This is the single source of truth for the data types handled by the
+/// CLI and the desktop app, and for their CSV filenames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    Accessory,
+    Armor,
+    Weapon,
+    UsableItem,
+    SpecialItem,
+    Character,
+    CharacterMagic,
+    CharacterSuperMove,
+    Shop,
+    TreasureChest,
+    CrewMember,
+    PlayableShip,
+    ShipCannon,
+    ShipAccessory,
+    ShipItem,
+    EnemyShip,
+    EnemyMagic,
+    EnemySuperMove,
+    Swashbuckler,
+    SpiritCurve,
+    ExpBoost,
+    ExpCurve,
+    MagicExpCurve,
+    Enemy,
+    EnemyTask,
+    EnemyEncounter,
+    EnemyEvent,
+}
+
+impl EntryKind {
+    /// All entry kinds, in export order.
+    pub const ALL: [EntryKind; 27] = [
+        EntryKind::Accessory,
+        EntryKind::Armor,
+        EntryKind::Weapon,
+        EntryKind::UsableItem,
+        EntryKind::SpecialItem,
+        EntryKind::Character,
+        EntryKind::CharacterMagic,
+        EntryKind::CharacterSuperMove,
+        EntryKind::Shop,
+        EntryKind::TreasureChest,
+        EntryKind::CrewMember,
+        EntryKind::PlayableShip,
+        EntryKind::ShipCannon,
+        EntryKind::ShipAccessory,
+        EntryKind::ShipItem,
+        EntryKind::EnemyShip,
+        EntryKind::EnemyMagic,
+        EntryKind::EnemySuperMove,
+        EntryKind::Swashbuckler,
+        EntryKind::SpiritCurve,
+        EntryKind::ExpBoost,
+        EntryKind::ExpCurve,
+        EntryKind::MagicExpCurve,
+        EntryKind::Enemy,
+        EntryKind::EnemyTask,
+        EntryKind::EnemyEncounter,
+        EntryKind::EnemyEvent,
+    ];
+
+    /// Get the snake_case name (e.g. `"usable_item"`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            EntryKind::Accessory => "accessory",
+            EntryKind::Armor => "armor",
+            EntryKind::Weapon => "weapon",
+            EntryKind::UsableItem => "usable_item",
+            EntryKind::SpecialItem => "special_item",
+            EntryKind::Character => "character",
+            EntryKind::CharacterMagic => "character_magic",
+            EntryKind::CharacterSuperMove => "character_super_move",
+            EntryKind::Shop => "shop",
+            EntryKind::TreasureChest => "treasure_chest",
+            EntryKind::CrewMember => "crew_member",
+            EntryKind::PlayableShip => "playable_ship",
+            EntryKind::ShipCannon => "ship_cannon",
+            EntryKind::ShipAccessory => "ship_accessory",
+            EntryKind::ShipItem => "ship_item",
+            EntryKind::EnemyShip => "enemy_ship",
+            EntryKind::EnemyMagic => "enemy_magic",
+            EntryKind::EnemySuperMove => "enemy_super_move",
+            EntryKind::Swashbuckler => "swashbuckler",
+            EntryKind::SpiritCurve => "spirit_curve",
+            EntryKind::ExpBoost => "exp_boost",
+            EntryKind::ExpCurve => "exp_curve",
+            EntryKind::MagicExpCurve => "magic_exp_curve",
+            EntryKind::Enemy => "enemy",
+            EntryKind::EnemyTask => "enemy_task",
+            EntryKind::EnemyEncounter => "enemy_encounter",
+            EntryKind::EnemyEvent => "enemy_event",
+        }
+    }
+
+    /// Get the human-readable plural label (e.g. `"usable items"`).
+    pub fn label(&self) -> &'static str {
+        match self {
+            EntryKind::Accessory => "accessories",
+            EntryKind::Armor => "armors",
+            EntryKind::Weapon => "weapons",
+            EntryKind::UsableItem => "usable items",
+            EntryKind::SpecialItem => "special items",
+            EntryKind::Character => "characters",
+            EntryKind::CharacterMagic => "character magic",
+            EntryKind::CharacterSuperMove => "character super moves",
+            EntryKind::Shop => "shops",
+            EntryKind::TreasureChest => "treasure chests",
+            EntryKind::CrewMember => "crew members",
+            EntryKind::PlayableShip => "playable ships",
+            EntryKind::ShipCannon => "ship cannons",
+            EntryKind::ShipAccessory => "ship accessories",
+            EntryKind::ShipItem => "ship items",
+            EntryKind::EnemyShip => "enemy ships",
+            EntryKind::EnemyMagic => "enemy magic",
+            EntryKind::EnemySuperMove => "enemy super moves",
+            EntryKind::Swashbuckler => "swashbucklers",
+            EntryKind::SpiritCurve => "spirit curves",
+            EntryKind::ExpBoost => "exp boosts",
+            EntryKind::ExpCurve => "exp curves",
+            EntryKind::MagicExpCurve => "magic exp curves",
+            EntryKind::Enemy => "enemies",
+            EntryKind::EnemyTask => "enemy tasks",
+            EntryKind::EnemyEncounter => "enemy encounters",
+            EntryKind::EnemyEvent => "enemy events",
+        }
+    }
+
+    /// Get the CSV filename (e.g. `"usableitem.csv"`).
+    pub fn csv_filename(&self) -> String {
+        format!("{}.csv", self.name().replace('_', ""))
+    }
+
+    /// Generate the JSON Schema for this kind's entry type.
+    #[cfg(feature = "schema")]
+    pub fn json_schema(&self) -> schemars::schema::RootSchema {
+        use super::*;
+        use schemars::schema_for;
+
+        match self {
+            EntryKind::Accessory => schema_for!(Accessory),
+            EntryKind::Armor => schema_for!(Armor),
+            EntryKind::Weapon => schema_for!(Weapon),
+            EntryKind::UsableItem => schema_for!(UsableItem),
+            EntryKind::SpecialItem => schema_for!(SpecialItem),
+            EntryKind::Character => schema_for!(Character),
+            EntryKind::CharacterMagic => schema_for!(CharacterMagic),
+            EntryKind::CharacterSuperMove => schema_for!(CharacterSuperMove),
+            EntryKind::Shop => schema_for!(Shop),
+            EntryKind::TreasureChest => schema_for!(TreasureChest),
+            EntryKind::CrewMember => schema_for!(CrewMember),
+            EntryKind::PlayableShip => schema_for!(PlayableShip),
+            EntryKind::ShipCannon => schema_for!(ShipCannon),
+            EntryKind::ShipAccessory => schema_for!(ShipAccessory),
+            EntryKind::ShipItem => schema_for!(ShipItem),
+            EntryKind::EnemyShip => schema_for!(EnemyShip),
+            EntryKind::EnemyMagic => schema_for!(EnemyMagic),
+            EntryKind::EnemySuperMove => schema_for!(EnemySuperMove),
+            EntryKind::Swashbuckler => schema_for!(Swashbuckler),
+            EntryKind::SpiritCurve => schema_for!(SpiritCurve),
+            EntryKind::ExpBoost => schema_for!(ExpBoost),
+            EntryKind::ExpCurve => schema_for!(ExpCurve),
+            EntryKind::MagicExpCurve => schema_for!(MagicExpCurve),
+            EntryKind::Enemy => schema_for!(Enemy),
+            EntryKind::EnemyTask => schema_for!(EnemyTask),
+            EntryKind::EnemyEncounter => schema_for!(EnemyEncounter),
+            EntryKind::EnemyEvent => schema_for!(EnemyEvent),
+        }
+    }
+
+    /// Check if this kind is read from ENP/EVP files rather than the DOL
+    /// or level file.
+    pub fn is_enemy_file_data(&self) -> bool {
+        matches!(
+            self,
+            EntryKind::Enemy
+                | EntryKind::EnemyTask
+                | EntryKind::EnemyEncounter
+                | EntryKind::EnemyEvent
+        )
+    }
+}
+
+impl fmt::Display for EntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for EntryKind {
+    type Err = Error;
+
+    /// Parse a kind from its name, ignoring case and `_`/`-`/space
+    /// separators, so `"usable_item"`, `"UsableItem"` and `"usableitem"`
+    /// (the CSV stem) all work.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized: String = s
+            .trim()
+            .trim_end_matches(".csv")
+            .chars()
+            .filter(|c| !matches!(c, '_' | '-' | ' '))
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        EntryKind::ALL
+            .into_iter()
+            .find(|kind| kind.name().replace('_', "") == normalized)
+            .ok_or_else(|| Error::ValidationError(format!("Unknown entry kind: {}", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_roundtrip() {
+        let kind = EntryKind::from_str("weapon").unwrap();
+        assert_eq!(kind, EntryKind::Weapon);
+        assert_eq!(kind.to_string(), "weapon");
+        assert_eq!(kind.csv_filename(), "weapon.csv");
+
+        for kind in EntryKind::ALL {
+            assert_eq!(EntryKind::from_str(&kind.to_string()).unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn test_from_str_variants() {
+        assert_eq!(
+            EntryKind::from_str("usable_item").unwrap(),
+            EntryKind::UsableItem
+        );
+        assert_eq!(
+            EntryKind::from_str("UsableItem").unwrap(),
+            EntryKind::UsableItem
+        );
+        assert_eq!(
+            EntryKind::from_str("usableitem.csv").unwrap(),
+            EntryKind::UsableItem
+        );
+        assert!(EntryKind::from_str("not_a_kind").is_err());
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_weapon_schema_fields() {
+        let schema = serde_json::to_value(EntryKind::Weapon.json_schema()).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+        for field in [
+            "id",
+            "name",
+            "character_id",
+            "attack",
+            "hit_percent",
+            "effect_id",
+        ] {
+            assert!(properties.contains_key(field), "missing field {}", field);
+        }
+    }
+
+    #[test]
+    fn test_csv_filenames() {
+        assert_eq!(EntryKind::UsableItem.csv_filename(), "usableitem.csv");
+        assert_eq!(
+            EntryKind::CharacterSuperMove.csv_filename(),
+            "charactersupermove.csv"
+        );
+        assert_eq!(EntryKind::MagicExpCurve.csv_filename(), "magicexpcurve.csv");
+        assert_eq!(EntryKind::Enemy.csv_filename(), "enemy.csv");
+    }
+}
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::GameVersion;
@@ -10,7 +11,9 @@ use crate::io::BinaryReader;
 
 /// Magic EXP curve for a character (EXP requirements for each magic element).
 /// Each element has 6 levels.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct MagicExpCurve {
     /// Entry ID (character ID)
     pub id: u32,
@@ -135,6 +138,11 @@ impl MagicExpCurve {
         }
     }
 
+    /// Check that the ID is one of the six characters' magic EXP curves.
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Magic EXP curve", self.id, &id_ranges::MAGIC_EXP_CURVE)
+    }
+
     /// Patch all Magic EXP curve entries into a buffer.
     pub fn patch_all(entries: &[Self], buf: &mut [u8]) {
         for e in entries {
@@ -17,7 +17,9 @@ mod enemy_super_move;
 mod enemy_task;
 mod exp_boost;
 mod exp_curve;
+mod kind;
 mod magic_exp_curve;
+mod movement;
 mod playable_ship;
 mod ship_accessory;
 mod ship_cannon;
@@ -39,7 +41,9 @@ pub use character_magic::CharacterMagic;
 pub use character_super_move::CharacterSuperMove;
 pub use crew_member::CrewMember;
 pub use enemy::{Enemy, EnemyItemDrop};
-pub use enemy_encounter::{EnemyEncounter, EnemySlot, MAX_ENEMY_SLOTS};
+pub use enemy_encounter::{
+    EnemyEncounter, EnemyEncounterBuilder, EnemySlot, EMPTY_SLOT_ID, MAX_ENEMY_SLOTS,
+};
 pub use enemy_event::{
     EnemyEvent, EventCharacterSlot, EventEnemySlot, DEFEAT_CONDITIONS, ESCAPE_CONDITIONS,
     MAX_EVENT_CHARACTERS, MAX_EVENT_ENEMIES,
@@ -50,6 +54,7 @@ pub use enemy_super_move::{EnemySkillCategory, EnemySuperMove};
 pub use enemy_task::EnemyTask;
 pub use exp_boost::ExpBoost;
 pub use exp_curve::ExpCurve;
+pub use kind::EntryKind;
 pub use magic_exp_curve::MagicExpCurve;
 pub use playable_ship::PlayableShip;
 pub use ship_accessory::ShipAccessory;
@@ -64,3 +69,32 @@ pub use treasure_chest::TreasureChest;
 pub use usable_item::{OccasionFlags, UsableItem};
 pub use weapon::Weapon;
 pub use weapon_effect::WeaponEffect;
+
+use std::ops::Range;
+
+use crate::error::{Error, Result};
+
+/// Parse a flag byte written as a binary string (`"0b00111010"`, with or
+/// without the `0b` prefix), as used in the CSVs.
+pub(crate) fn parse_binary_flags(kind: &str, s: &str) -> Result<u8> {
+    let s = s.trim();
+    let digits = s
+        .strip_prefix("0b")
+        .or_else(|| s.strip_prefix("0B"))
+        .unwrap_or(s);
+    u8::from_str_radix(digits, 2)
+        .map_err(|_| Error::ValidationError(format!("Invalid {}: {:?}", kind, s)))
+}
+
+/// Check that an entry ID falls inside its table's ID range, so that writing
+/// it can't wrap around to another entry's index.
+pub(crate) fn check_write_id(kind: &str, id: u32, range: &Range<u32>) -> Result<()> {
+    if range.contains(&id) {
+        Ok(())
+    } else {
+        Err(Error::ValidationError(format!(
+            "{} ID {} is outside the writable range {}..{}",
+            kind, id, range.start, range.end
+        )))
+    }
+}
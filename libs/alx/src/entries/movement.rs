@@ -0,0 +1,93 @@
+//! Movement flag accessors shared by characters and enemies.
+//!
+//! Both [`Character`] and [`Enemy`] store their battle movement behaviour in
+//! a 12-bit `movement_flags` field with the same bit layout.
+
+use super::character::Character;
+use super::enemy::Enemy;
+
+/// Generate a getter and a setter for each movement flag bit.
+macro_rules! movement_flags {
+    ($ty:ty { $($name:literal: $get:ident, $set:ident = $bit:literal;)* }) => {
+        impl $ty {
+            $(
+                #[doc = concat!("Check movement flag: ", $name)]
+                pub fn $get(&self) -> bool {
+                    (self.movement_flags & $bit) != 0
+                }
+
+                #[doc = concat!("Set or clear movement flag: ", $name)]
+                pub fn $set(&mut self, value: bool) {
+                    if value {
+                        self.movement_flags |= $bit;
+                    } else {
+                        self.movement_flags &= !$bit;
+                    }
+                }
+            )*
+        }
+    };
+}
+
+/// Implement the flag accessors for each type that has `movement_flags`.
+macro_rules! impl_movement_flags {
+    ($($ty:ty),*) => {
+        $(
+            movement_flags!($ty {
+                "May Dodge": may_dodge, set_may_dodge = 0x800;
+                "Unknown Damage": unk_damage, set_unk_damage = 0x400;
+                "Unknown Ranged": unk_ranged, set_unk_ranged = 0x200;
+                "Unknown Melee": unk_melee, set_unk_melee = 0x100;
+                "Ranged Attack": ranged_atk, set_ranged_atk = 0x080;
+                "Melee Attack": melee_atk, set_melee_atk = 0x040;
+                "Ranged Only": ranged_only, set_ranged_only = 0x020;
+                "Take Cover": take_cover, set_take_cover = 0x010;
+                "In Air": in_air, set_in_air = 0x008;
+                "On Ground": on_ground, set_on_ground = 0x004;
+                "Reserved": reserved, set_reserved = 0x002;
+                "May Move": may_move, set_may_move = 0x001;
+            });
+        )*
+    };
+}
+
+impl_movement_flags!(Character, Enemy);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::region::GameVersion;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_toggle_character_flags() {
+        let mut character = Character::default();
+        character.set_may_dodge(true);
+        character.set_in_air(true);
+        assert!(character.may_dodge());
+        assert!(character.in_air());
+        assert!(!character.on_ground());
+        assert_eq!(character.movement_flags, 0x808);
+
+        character.set_may_dodge(false);
+        assert!(!character.may_dodge());
+        assert!(character.in_air());
+        assert_eq!(character.movement_flags, 0x008);
+    }
+
+    #[test]
+    fn test_toggle_enemy_flags_leaves_other_bits() {
+        let data = [0u8; Enemy::ENTRY_SIZE];
+        let version = GameVersion::from_game_id("GEAE8P").unwrap();
+        let mut enemy =
+            Enemy::read_one(&mut Cursor::new(&data[..]), 0, "test_ep.enp", &version).unwrap();
+        enemy.movement_flags = 0x0FFF;
+        enemy.set_take_cover(false);
+        assert!(!enemy.take_cover());
+        assert_eq!(enemy.movement_flags, 0x0FEF);
+
+        enemy.set_take_cover(true);
+        enemy.set_take_cover(true);
+        assert_eq!(enemy.movement_flags, 0x0FFF);
+    }
+}
@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::GameVersion;
 use crate::io::BinaryReader;
 
 /// A playable ship in the game.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct PlayableShip {
     /// Entry ID
     pub id: u32,
@@ -163,7 +166,7 @@ impl PlayableShip {
         Ok(entries)
     }
 
-    fn entry_size_for_version(_version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(_version: &GameVersion) -> usize {
         Self::ENTRY_SIZE
     }
 
@@ -205,6 +208,11 @@ impl PlayableShip {
             .copy_from_slice(&self.dodge_growth.to_be_bytes());
     }
 
+    /// Check that the ID is one of the five playable ships.
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Playable ship", self.id, &id_ranges::PLAYABLE_SHIP)
+    }
+
     /// Patch all ship entries into a buffer.
     pub fn patch_all(entries: &[Self], buf: &mut [u8]) {
         for e in entries {
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::entries::Trait;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
@@ -10,7 +11,9 @@ use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
 
 /// A ship accessory in the game.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct ShipAccessory {
     /// Entry ID
     pub id: u32,
@@ -109,7 +112,7 @@ impl ShipAccessory {
         Ok(entries)
     }
 
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 1,
             _ => Self::ENTRY_SIZE,
@@ -137,6 +140,11 @@ impl ShipAccessory {
         buf[Self::OFF_ORDER2] = self.order2 as u8;
     }
 
+    /// Check that the ID is in the ship accessory table (440-479).
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Ship accessory", self.id, &id_ranges::SHIP_ACCESSORY)
+    }
+
     /// Patch all accessory entries into a buffer.
     pub fn patch_all(entries: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
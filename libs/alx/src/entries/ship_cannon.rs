@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
 
 /// A ship cannon in the game.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct ShipCannon {
     /// Entry ID
     pub id: u32,
@@ -145,7 +148,7 @@ impl ShipCannon {
         Ok(entries)
     }
 
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 1,
             _ => Self::ENTRY_SIZE,
@@ -178,6 +181,11 @@ impl ShipCannon {
         buf[Self::OFF_ORDER2] = self.order2 as u8;
     }
 
+    /// Check that the ID is in the ship cannon table (400-439).
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Ship cannon", self.id, &id_ranges::SHIP_CANNON)
+    }
+
     /// Patch all cannon entries into a buffer.
     pub fn patch_all(entries: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
 
 /// A ship item (consumable) in the game.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct ShipItem {
     /// Entry ID
     pub id: u32,
@@ -137,7 +140,7 @@ impl ShipItem {
         Ok(entries)
     }
 
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 2,
             _ => Self::ENTRY_SIZE,
@@ -178,6 +181,11 @@ impl ShipItem {
         buf[Self::OFF_HIT..Self::OFF_HIT + 2].copy_from_slice(&self.hit.to_be_bytes());
     }
 
+    /// Check that the ID is in the ship item table (480-509).
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Ship item", self.id, &id_ranges::SHIP_ITEM)
+    }
+
     /// Patch all ship item entries into a buffer.
     pub fn patch_all(entries: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
-use crate::error::Result;
+use super::check_write_id;
+use crate::error::{Error, Result};
 use crate::game::offsets::id_ranges;
 use crate::game::region::GameVersion;
 use crate::io::BinaryReader;
 
 /// Shop entry with up to 48 item slots.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct Shop {
     /// Entry ID
     pub id: u16,
@@ -29,6 +32,9 @@ impl Shop {
     /// Size of one shop entry in bytes.
     pub const ENTRY_SIZE: usize = 104;
 
+    /// Number of item slots in a shop.
+    pub const MAX_ITEMS: usize = 48;
+
     // Field offsets
     // 0-1 = id, 2-3 = pad, 4-7 = sot_pos (don't change these)
     const OFF_ITEMS: usize = 8; // 48 items * 2 bytes each
@@ -87,15 +93,31 @@ impl Shop {
     }
 
     /// Patch a single shop entry in a mutable buffer.
-    /// Only patches item IDs - id, pad, sot_pos are untouched.
+    /// Only patches item IDs - id, pad, sot_pos are untouched. Slots past
+    /// the end of `item_ids` are written as empty (-1).
     pub fn patch_entry(&self, buf: &mut [u8]) {
-        for i in 0..48 {
+        for i in 0..Self::MAX_ITEMS {
             let item_id = self.item_ids.get(i).copied().unwrap_or(-1);
             let off = Self::OFF_ITEMS + i * 2;
             buf[off..off + 2].copy_from_slice(&item_id.to_be_bytes());
         }
     }
 
+    /// Check that the ID is one of the 43 shops and that the item list
+    /// fits the shop's [`MAX_ITEMS`](Self::MAX_ITEMS) slots.
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Shop", self.id as u32, &id_ranges::SHOP)?;
+        if self.item_ids.len() > Self::MAX_ITEMS {
+            return Err(Error::ValidationError(format!(
+                "Shop ID {} has {} items (max {})",
+                self.id,
+                self.item_ids.len(),
+                Self::MAX_ITEMS
+            )));
+        }
+        Ok(())
+    }
+
     /// Patch all shop entries into a buffer.
     pub fn patch_all(shops: &[Self], buf: &mut [u8]) {
         for (idx, shop) in shops.iter().enumerate() {
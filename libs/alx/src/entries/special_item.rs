@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
 
 /// Special item entry (key items, moon crystals, etc.)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct SpecialItem {
     /// Entry ID
     pub id: u32,
@@ -109,7 +112,7 @@ impl SpecialItem {
         Ok(items)
     }
 
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 4, // EU has extra padding
             _ => Self::ENTRY_SIZE,
@@ -125,6 +128,11 @@ impl SpecialItem {
             .copy_from_slice(&self.buy_price.to_be_bytes());
     }
 
+    /// Check that the ID is in the special item table (320-399).
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Special item", self.id, &id_ranges::SPECIAL_ITEM)
+    }
+
     /// Patch all special item entries into a buffer.
     pub fn patch_all(items: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
@@ -9,7 +9,9 @@ use crate::game::region::GameVersion;
 use crate::io::{BinaryReader, BinaryWriter};
 
 /// SP and MAXSP at a given level.
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct SpiritLevel {
     /// SP at this level
     pub sp: i8,
@@ -18,7 +20,9 @@ pub struct SpiritLevel {
 }
 
 /// Spirit curve for a character (SP/MAXSP progression levels 1-99).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct SpiritCurve {
     /// Entry ID (character ID)
     pub id: u32,
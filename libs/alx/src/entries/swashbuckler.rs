@@ -3,13 +3,16 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
 
 /// A swashbuckler rating in the game.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct Swashbuckler {
     /// Entry ID
     pub id: u32,
@@ -88,7 +91,7 @@ impl Swashbuckler {
         Ok(entries)
     }
 
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 4,
             _ => Self::ENTRY_SIZE,
@@ -106,6 +109,11 @@ impl Swashbuckler {
         buf[Self::OFF_RUN..Self::OFF_RUN + 2].copy_from_slice(&self.run.to_be_bytes());
     }
 
+    /// Check that the ID is one of the 24 GameCube Swashbuckler ratings.
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Swashbuckler", self.id, &id_ranges::SWASHBUCKLER_GC)
+    }
+
     /// Patch all swashbuckler entries into a buffer.
     pub fn patch_all(entries: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
@@ -6,7 +6,9 @@ use serde::{Deserialize, Serialize};
 pub type TraitId = i8;
 
 /// A trait (stat modifier) on equipment.
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct Trait {
     /// Trait ID (-1 = None)
     pub id: TraitId,
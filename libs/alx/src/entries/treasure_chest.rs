@@ -2,14 +2,18 @@
 
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
+use std::ops::RangeInclusive;
 
-use crate::error::Result;
+use super::check_write_id;
+use crate::error::{Error, Result};
 use crate::game::offsets::id_ranges;
 use crate::game::region::GameVersion;
 use crate::io::BinaryReader;
 
 /// Treasure chest entry.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct TreasureChest {
     /// Entry ID (corresponds to chest location)
     pub id: u32,
@@ -27,6 +31,15 @@ impl TreasureChest {
     const OFF_ITEM_ID: usize = 0;
     const OFF_ITEM_AMOUNT: usize = 4;
 
+    /// Item ID of an empty chest.
+    pub const EMPTY_ITEM_ID: i32 = -1;
+
+    /// Valid amounts for a chest holding an item (an inventory stack).
+    pub const ITEM_AMOUNT_RANGE: RangeInclusive<i32> = 1..=99;
+
+    /// Valid amounts for a gold chest.
+    pub const GOLD_AMOUNT_RANGE: RangeInclusive<i32> = 1..=999_999;
+
     /// Check if this chest contains gold.
     pub fn is_gold(&self) -> bool {
         self.item_id >= 0x200
@@ -44,6 +57,29 @@ impl TreasureChest {
         }
     }
 
+    /// Get the valid amounts for this chest's contents, or `None` for an
+    /// empty chest, whose amount is unused.
+    pub fn amount_range(&self) -> Option<RangeInclusive<i32>> {
+        if self.item_id == Self::EMPTY_ITEM_ID {
+            None
+        } else if self.is_gold() {
+            Some(Self::GOLD_AMOUNT_RANGE)
+        } else {
+            Some(Self::ITEM_AMOUNT_RANGE)
+        }
+    }
+
+    /// Clamp the amount to [`amount_range`](Self::amount_range).
+    ///
+    /// Returns the value actually stored. Writes reject out-of-range
+    /// amounts, so call this first to fix them up instead.
+    pub fn clamp_amount(&mut self) -> i32 {
+        if let Some(range) = self.amount_range() {
+            self.item_amount = self.item_amount.clamp(*range.start(), *range.end());
+        }
+        self.item_amount
+    }
+
     /// Get the item name for display.
     pub fn item_name(&self) -> &'static str {
         if self.item_id == -1 {
@@ -92,6 +128,31 @@ impl TreasureChest {
             .copy_from_slice(&self.item_amount.to_be_bytes());
     }
 
+    /// Check that the ID is one of the 119 chests, the item ID is a real item
+    /// or the empty marker, and the amount is inside
+    /// [`amount_range`](Self::amount_range).
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Treasure chest", self.id, &id_ranges::TREASURE_CHEST)?;
+        if self.item_id < Self::EMPTY_ITEM_ID {
+            return Err(Error::ValidationError(format!(
+                "Treasure chest {} has invalid item ID {}",
+                self.id, self.item_id
+            )));
+        }
+        match self.amount_range() {
+            Some(range) if !range.contains(&self.item_amount) => {
+                Err(Error::ValidationError(format!(
+                    "Treasure chest {} amount must be between {} and {}, got {}",
+                    self.id,
+                    range.start(),
+                    range.end(),
+                    self.item_amount
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Patch all treasure chest entries into a buffer.
     pub fn patch_all(chests: &[Self], buf: &mut [u8]) {
         for chest in chests {
@@ -104,3 +165,39 @@ impl TreasureChest {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chest(item_id: i32, item_amount: i32) -> TreasureChest {
+        TreasureChest {
+            id: 0,
+            item_id,
+            item_amount,
+        }
+    }
+
+    #[test]
+    fn test_clamp_amount() {
+        assert_eq!(chest(240, 500).clamp_amount(), 99);
+        assert_eq!(chest(240, -3).clamp_amount(), 1);
+        assert_eq!(chest(0x200, 150).clamp_amount(), 150);
+        assert_eq!(chest(0x200, i32::MAX).clamp_amount(), 999_999);
+        // Empty chests keep whatever amount they had
+        assert_eq!(chest(-1, 0).clamp_amount(), 0);
+    }
+
+    #[test]
+    fn test_validate_for_write() {
+        assert!(chest(240, 3).validate_for_write().is_ok());
+        assert!(chest(-1, 0).validate_for_write().is_ok());
+        assert!(chest(240, 100).validate_for_write().is_err());
+        assert!(chest(0x200, 0).validate_for_write().is_err());
+        assert!(chest(-2, 1).validate_for_write().is_err());
+
+        let mut fixed = chest(240, 100);
+        fixed.clamp_amount();
+        assert!(fixed.validate_for_write().is_ok());
+    }
+}
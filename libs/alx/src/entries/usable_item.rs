@@ -2,28 +2,59 @@
 
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
+use std::str::FromStr;
 
-use crate::error::Result;
+use super::{check_write_id, parse_binary_flags};
+use crate::error::{Error, Result};
 use crate::game::offsets::id_ranges;
 use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
 
 /// Occasion flags for when an item can be used.
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct OccasionFlags(pub u8);
 
 impl OccasionFlags {
+    /// Occasion flag bits: Menu=0x04, Battle=0x02, Ship=0x01
+    pub const MENU: u8 = 0x04;
+    pub const BATTLE: u8 = 0x02;
+    pub const SHIP: u8 = 0x01;
+
     /// Can be used in menu.
     pub fn can_use_menu(&self) -> bool {
-        (self.0 & 0x04) != 0
+        (self.0 & Self::MENU) != 0
     }
     /// Can be used in battle.
     pub fn can_use_battle(&self) -> bool {
-        (self.0 & 0x02) != 0
+        (self.0 & Self::BATTLE) != 0
     }
     /// Can be used in ship battle.
     pub fn can_use_ship(&self) -> bool {
-        (self.0 & 0x01) != 0
+        (self.0 & Self::SHIP) != 0
+    }
+
+    /// Set or clear `bit`.
+    fn with_bit(mut self, bit: u8, value: bool) -> Self {
+        if value {
+            self.0 |= bit;
+        } else {
+            self.0 &= !bit;
+        }
+        self
+    }
+
+    /// Allow or forbid use in the menu.
+    pub fn with_menu(self, value: bool) -> Self {
+        self.with_bit(Self::MENU, value)
+    }
+    /// Allow or forbid use in battle.
+    pub fn with_battle(self, value: bool) -> Self {
+        self.with_bit(Self::BATTLE, value)
+    }
+    /// Allow or forbid use in ship battle.
+    pub fn with_ship(self, value: bool) -> Self {
+        self.with_bit(Self::SHIP, value)
     }
 
     /// Format as binary string (e.g., "0b0110").
@@ -32,8 +63,19 @@ impl OccasionFlags {
     }
 }
 
+impl FromStr for OccasionFlags {
+    type Err = Error;
+
+    /// Parse the CSV binary-string format (e.g. `"0b0110"`).
+    fn from_str(s: &str) -> Result<Self> {
+        parse_binary_flags("occasion flags", s).map(Self)
+    }
+}
+
 /// Usable item entry (potions, crystals, etc.)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct UsableItem {
     /// Entry ID
     pub id: u32,
@@ -224,7 +266,7 @@ impl UsableItem {
         Ok(items)
     }
 
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 1,
             _ => Self::ENTRY_SIZE,
@@ -252,6 +294,11 @@ impl UsableItem {
             .copy_from_slice(&self.state_miss.to_be_bytes());
     }
 
+    /// Check that the ID is in the usable item table (240-319).
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Usable item", self.id, &id_ranges::USABLE_ITEM)
+    }
+
     /// Patch all usable item entries into a buffer.
     pub fn patch_all(items: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
@@ -265,3 +312,29 @@ impl UsableItem {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_occasion_flags_builder_and_parse() {
+        let flags = OccasionFlags::default().with_menu(true).with_ship(true);
+        assert!(flags.can_use_menu());
+        assert!(!flags.can_use_battle());
+        assert!(flags.can_use_ship());
+        assert_eq!(flags.as_binary_string(), "0b0101");
+
+        let flags = flags.with_menu(false).with_battle(true);
+        assert_eq!(flags.0, OccasionFlags::BATTLE | OccasionFlags::SHIP);
+
+        // Round-trip through the CSV format
+        for value in 0..=0x07u8 {
+            let flags = OccasionFlags(value);
+            let parsed: OccasionFlags = flags.as_binary_string().parse().unwrap();
+            assert_eq!(parsed, flags);
+        }
+        assert!(" 0B0110 ".parse::<OccasionFlags>().is_ok());
+        assert!("menu".parse::<OccasionFlags>().is_err());
+    }
+}
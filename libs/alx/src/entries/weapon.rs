@@ -3,11 +3,13 @@
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use super::check_write_id;
 use super::traits::Trait;
 use crate::error::Result;
 use crate::game::offsets::id_ranges;
 use crate::game::region::{GameVersion, Region};
 use crate::io::BinaryReader;
+use crate::lookups;
 
 /// Weapon entry.
 ///
@@ -24,7 +26,9 @@ use crate::io::BinaryReader;
 /// - Trait ID: i8
 /// - Padding: i8
 /// - Trait Value: i16
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct Weapon {
     /// Entry ID
     pub id: u32,
@@ -98,12 +102,7 @@ impl Weapon {
     /// Get the character name for this weapon's character ID.
     pub fn character_name(&self) -> &'static str {
         match self.character_id {
-            0 => "Vyse",
-            1 => "Aika",
-            2 => "Fina",
-            3 => "Drachma",
-            4 => "Enrique",
-            5 => "Gilder",
+            0..=5 => lookups::character_name(self.character_id),
             _ => "Unknown",
         }
     }
@@ -182,7 +181,7 @@ impl Weapon {
     }
 
     /// Get entry size for a specific version.
-    fn entry_size_for_version(version: &GameVersion) -> usize {
+    pub(crate) fn entry_size_for_version(version: &GameVersion) -> usize {
         match version.region {
             Region::Eu => Self::ENTRY_SIZE + 1, // EU has extra padding
             _ => Self::ENTRY_SIZE,
@@ -206,6 +205,11 @@ impl Weapon {
             .copy_from_slice(&self.trait_data.value.to_be_bytes());
     }
 
+    /// Check that the ID is in the weapon table (0-79).
+    pub fn validate_for_write(&self) -> Result<()> {
+        check_write_id("Weapon", self.id, &id_ranges::WEAPON)
+    }
+
     /// Patch all weapon entries into a buffer.
     pub fn patch_all(weapons: &[Self], buf: &mut [u8], version: &GameVersion) {
         let entry_size = Self::entry_size_for_version(version);
@@ -9,7 +9,9 @@ use crate::io::{BinaryReader, BinaryWriter};
 use crate::lookups::STATE_NAMES;
 
 /// A weapon effect in the game.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct WeaponEffect {
     /// Entry ID
     pub id: u32,
@@ -28,15 +30,22 @@ impl WeaponEffect {
     /// 17 (name) + 1 (effect_id) + 1 (state_id) + 1 (state_miss) = 20 bytes
     pub const ENTRY_SIZE: usize = 20;
 
+    /// Get the name of the state this effect inflicts, if it inflicts one.
+    pub fn state_name(&self) -> Option<&'static str> {
+        (self.effect_id > 0 && self.state_id != -1).then(|| STATE_NAMES.get(self.state_id))
+    }
+
+    /// Get the chance in percent that the state is inflicted.
+    pub fn hit_percent(&self) -> i32 {
+        100 - self.state_miss as i32
+    }
+
     /// Get the description string for this effect.
     /// Format: "{State Name} by {100 - state_miss}%" or "None"
     pub fn description(&self) -> String {
-        if self.effect_id > 0 && self.state_id != -1 {
-            let hit = 100 - self.state_miss as i32;
-            let state_name = STATE_NAMES.get(self.state_id);
-            format!("{} by {}%", state_name, hit)
-        } else {
-            "None".to_string()
+        match self.state_name() {
+            Some(state_name) => format!("{} by {}%", state_name, self.hit_percent()),
+            None => "None".to_string(),
         }
     }
 
@@ -17,6 +17,10 @@ pub enum Error {
     #[error("Invalid ISO: {0}")]
     InvalidIso(String),
 
+    /// The ISO file is smaller than its header/FST says it should be.
+    #[error("ISO appears truncated: expected at least {expected_min} bytes, found {actual}")]
+    TruncatedIso { expected_min: u64, actual: u64 },
+
     /// The game version is not supported.
     #[error("Unsupported game version: {0}")]
     UnsupportedVersion(String),
@@ -41,6 +45,17 @@ pub enum Error {
     #[error("Validation error: {0}")]
     ValidationError(String),
 
+    /// A feature can't be used with this ISO (e.g. a required file is missing).
+    #[error("{feature} unavailable: {reason}")]
+    FeatureUnavailable {
+        feature: &'static str,
+        reason: String,
+    },
+
+    /// A write was attempted on a game opened read-only.
+    #[error("ISO was opened read-only")]
+    ReadOnly,
+
     /// gc_fst ISO operation error.
     #[error("ISO operation error: {0}")]
     IsoOperationError(String),
@@ -0,0 +1,136 @@
+//! A parsed snapshot of all game data.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::entries::{
+    Accessory, Armor, Character, CharacterMagic, CharacterSuperMove, CrewMember, Enemy,
+    EnemyEncounter, EnemyEvent, EnemyMagic, EnemyShip, EnemySuperMove, EnemyTask, ExpBoost,
+    ExpCurve, MagicExpCurve, PlayableShip, ShipAccessory, ShipCannon, ShipItem, Shop, SpecialItem,
+    SpiritCurve, Swashbuckler, TreasureChest, UsableItem, Weapon, WeaponEffect,
+};
+use crate::items::ItemCategory;
+
+/// Every entry type read from the game, as plain owned data.
+///
+/// Read it in one go with [`GameRoot::read_game_data`](super::GameRoot::read_game_data).
+/// Tools and randomizer passes can then work on (and clone) it without
+/// holding on to the ISO.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct GameData {
+    pub accessories: Vec<Accessory>,
+    pub armors: Vec<Armor>,
+    pub weapons: Vec<Weapon>,
+    pub weapon_effects: Vec<WeaponEffect>,
+    pub usable_items: Vec<UsableItem>,
+    pub special_items: Vec<SpecialItem>,
+    pub characters: Vec<Character>,
+    pub character_magic: Vec<CharacterMagic>,
+    pub character_super_moves: Vec<CharacterSuperMove>,
+    pub shops: Vec<Shop>,
+    pub treasure_chests: Vec<TreasureChest>,
+    pub crew_members: Vec<CrewMember>,
+    pub playable_ships: Vec<PlayableShip>,
+    pub ship_cannons: Vec<ShipCannon>,
+    pub ship_accessories: Vec<ShipAccessory>,
+    pub ship_items: Vec<ShipItem>,
+    pub enemy_ships: Vec<EnemyShip>,
+    pub enemy_magic: Vec<EnemyMagic>,
+    pub enemy_super_moves: Vec<EnemySuperMove>,
+    pub swashbucklers: Vec<Swashbuckler>,
+    pub spirit_curves: Vec<SpiritCurve>,
+    pub exp_boosts: Vec<ExpBoost>,
+    pub exp_curves: Vec<ExpCurve>,
+    pub magic_exp_curves: Vec<MagicExpCurve>,
+    pub enemies: Vec<Enemy>,
+    pub enemy_tasks: Vec<EnemyTask>,
+    pub enemy_encounters: Vec<EnemyEncounter>,
+    pub enemy_events: Vec<EnemyEvent>,
+}
+
+/// One item of any type, as yielded by [`GameData::all_items`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemRef<'a> {
+    pub id: i32,
+    pub name: &'a str,
+    /// Table the item comes from
+    pub category: ItemCategory,
+}
+
+impl GameData {
+    /// Iterate over every item of every type (weapons, armors, accessories,
+    /// usable and special items, then the ship types), in table order.
+    pub fn all_items(&self) -> impl Iterator<Item = ItemRef<'_>> {
+        fn items<'a, T>(
+            entries: &'a [T],
+            category: ItemCategory,
+            fields: fn(&T) -> (u32, &str),
+        ) -> impl Iterator<Item = ItemRef<'a>> {
+            entries.iter().map(move |entry| {
+                let (id, name) = fields(entry);
+                ItemRef {
+                    id: id as i32,
+                    name,
+                    category,
+                }
+            })
+        }
+
+        items(&self.weapons, ItemCategory::Weapon, |e| {
+            (e.id, e.name.as_str())
+        })
+        .chain(items(&self.armors, ItemCategory::Armor, |e| {
+            (e.id, e.name.as_str())
+        }))
+        .chain(items(&self.accessories, ItemCategory::Accessory, |e| {
+            (e.id, e.name.as_str())
+        }))
+        .chain(items(&self.usable_items, ItemCategory::UsableItem, |e| {
+            (e.id, e.name.as_str())
+        }))
+        .chain(items(&self.special_items, ItemCategory::SpecialItem, |e| {
+            (e.id, e.name.as_str())
+        }))
+        .chain(items(&self.ship_cannons, ItemCategory::ShipCannon, |e| {
+            (e.id, e.name.as_str())
+        }))
+        .chain(items(
+            &self.ship_accessories,
+            ItemCategory::ShipAccessory,
+            |e| (e.id, e.name.as_str()),
+        ))
+        .chain(items(&self.ship_items, ItemCategory::ShipItem, |e| {
+            (e.id, e.name.as_str())
+        }))
+    }
+
+    /// Find an item of any type by ID.
+    pub fn item(&self, id: i32) -> Option<ItemRef<'_>> {
+        self.all_items().find(|item| item.id == id)
+    }
+
+    /// Map enemy magic and super move IDs to their names, for naming the
+    /// actions of enemy tasks.
+    pub fn enemy_task_name_maps(&self) -> (HashMap<u32, String>, HashMap<u32, String>) {
+        enemy_task_name_maps(&self.enemy_magic, &self.enemy_super_moves)
+    }
+
+    /// Generate the JSON Schema for the whole snapshot.
+    #[cfg(feature = "schema")]
+    pub fn json_schema() -> schemars::schema::RootSchema {
+        schemars::schema_for!(GameData)
+    }
+}
+
+/// Map enemy magic and super move IDs to their names.
+pub(super) fn enemy_task_name_maps(
+    magic: &[EnemyMagic],
+    super_moves: &[EnemySuperMove],
+) -> (HashMap<u32, String>, HashMap<u32, String>) {
+    let magic_names = magic.iter().map(|m| (m.id, m.name.clone())).collect();
+    let super_move_names = super_moves.iter().map(|s| (s.id, s.name.clone())).collect();
+    (magic_names, super_move_names)
+}
@@ -0,0 +1,74 @@
+//! Dump verification against known-good Start.dol checksums.
+
+use std::fmt;
+
+use super::region::Region;
+
+/// CRC32s of unmodified Start.dol files from verified dumps.
+///
+/// Only add entries checked against a clean disc; an entry here is what
+/// tells users their ISO is safe to randomize.
+pub const KNOWN_DOL_CRCS: &[(u32, Region)] = &[];
+
+/// Whether a DOL matches a known-good build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpQuality {
+    /// The DOL matches a known-good build for this region
+    Known(Region),
+    /// The DOL doesn't match any known build (modified, bad dump or unlisted)
+    Unknown,
+}
+
+impl DumpQuality {
+    /// Classify a DOL by its CRC32.
+    pub fn from_crc(crc: u32) -> Self {
+        KNOWN_DOL_CRCS
+            .iter()
+            .find(|(known, _)| *known == crc)
+            .map(|(_, region)| DumpQuality::Known(*region))
+            .unwrap_or(DumpQuality::Unknown)
+    }
+
+    /// Classify a DOL by its contents.
+    pub fn from_dol(dol: &[u8]) -> Self {
+        Self::from_crc(dol_crc32(dol))
+    }
+
+    /// Check if the DOL matches a known-good build.
+    pub fn is_known(&self) -> bool {
+        matches!(self, DumpQuality::Known(_))
+    }
+}
+
+impl fmt::Display for DumpQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DumpQuality::Known(region) => write!(f, "Known good ({})", region),
+            DumpQuality::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// Compute the CRC32 of a DOL image.
+pub fn dol_crc32(dol: &[u8]) -> u32 {
+    crc32fast::hash(dol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_crcs_resolve_to_their_region() {
+        for &(crc, region) in KNOWN_DOL_CRCS {
+            assert_eq!(DumpQuality::from_crc(crc), DumpQuality::Known(region));
+        }
+    }
+
+    #[test]
+    fn test_unlisted_dol_is_unknown() {
+        let quality = DumpQuality::from_dol(b"not a real dol");
+        assert_eq!(quality, DumpQuality::Unknown);
+        assert!(!quality.is_known());
+    }
+}
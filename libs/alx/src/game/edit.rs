@@ -0,0 +1,547 @@
+//! Partial, batched edits to [`GameData`] entries.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::data::GameData;
+use crate::entries::{
+    Accessory, Armor, Character, CharacterMagic, CharacterSuperMove, CrewMember, EntryKind,
+    ExpBoost, ExpCurve, MagicExpCurve, PlayableShip, ShipAccessory, ShipCannon, ShipItem, Shop,
+    SpecialItem, Swashbuckler, TreasureChest, UsableItem, Weapon,
+};
+use crate::error::{Error, Result};
+
+/// A partial update to the entries of one kind with one ID.
+///
+/// `fields` is merged into the entry's serialized form like a JSON merge
+/// patch: objects are merged key by key and any other value replaces the
+/// field. Every entry with `id` is updated, so all filter variants of an
+/// enemy change together.
+///
+/// IDs of some kinds only count within a parent, so their edits also name
+/// it: enemy tasks need `enemy_id`, and encounters and events need the
+/// `filter` of the file they're in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Edit {
+    pub kind: EntryKind,
+    pub id: u32,
+    /// Enemy an `EnemyTask` edit applies to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enemy_id: Option<u32>,
+    /// Source file an `EnemyEncounter` or `EnemyEvent` edit applies to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    pub fields: Value,
+}
+
+impl Edit {
+    /// Create an edit of the entries of `kind` with `id`.
+    pub fn new(kind: EntryKind, id: u32, fields: Value) -> Self {
+        Self {
+            kind,
+            id,
+            enemy_id: None,
+            filter: None,
+            fields,
+        }
+    }
+
+    /// Limit an `EnemyTask` edit to the tasks of one enemy.
+    pub fn for_enemy(mut self, enemy_id: u32) -> Self {
+        self.enemy_id = Some(enemy_id);
+        self
+    }
+
+    /// Limit an `EnemyEncounter` or `EnemyEvent` edit to one source file.
+    pub fn in_file(mut self, filter: &str) -> Self {
+        self.filter = Some(filter.to_string());
+        self
+    }
+
+    /// The serialized fields an entry must match to be edited, `id` first.
+    fn key(&self) -> Result<Vec<(&'static str, Value)>> {
+        let mut key = vec![("id", Value::from(self.id))];
+        let missing = |field: &str| {
+            Error::ValidationError(format!(
+                "Edit of {} {} needs a {}",
+                self.kind, self.id, field
+            ))
+        };
+        match self.kind {
+            EntryKind::EnemyTask => {
+                let enemy_id = self.enemy_id.ok_or_else(|| missing("enemy_id"))?;
+                key.push(("enemy_id", Value::from(enemy_id)));
+            }
+            EntryKind::EnemyEncounter | EntryKind::EnemyEvent => {
+                let filter = self.filter.as_deref().ok_or_else(|| missing("filter"))?;
+                key.push(("filter", Value::from(filter)));
+            }
+            _ => {}
+        }
+        Ok(key)
+    }
+}
+
+/// One entry's serialized value before and after an applied edit.
+///
+/// Only the changed entries are recorded, which keeps edit history small.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryChange {
+    pub kind: EntryKind,
+    /// Index of the entry in its `GameData` list
+    pub index: usize,
+    pub before: Value,
+    pub after: Value,
+}
+
+impl EntryChange {
+    /// Approximate memory used by this change, in bytes.
+    pub(crate) fn size(&self) -> usize {
+        self.before.to_string().len() + self.after.to_string().len()
+    }
+}
+
+/// One field that differs between two versions of an entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDiff {
+    pub kind: EntryKind,
+    /// ID of the entry
+    pub id: u32,
+    /// Enemy of an `EnemyTask` entry, as in [`Edit::enemy_id`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enemy_id: Option<u32>,
+    /// Source file of an `EnemyEncounter` or `EnemyEvent` entry, as in
+    /// [`Edit::filter`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// Index of the entry in its `GameData` list
+    pub index: usize,
+    /// Serialized field name (e.g. `"attack"`)
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+/// Evaluate `$body` with `$entries` bound to the `GameData` list for `$kind`
+/// and `$validate` to that entry type's write check.
+macro_rules! with_entries {
+    ($data:expr, $kind:expr, |$entries:ident, $validate:ident| $body:expr) => {
+        with_entries!(@dispatch $data, $kind, $entries, $validate, $body;
+            Accessory => accessories, Accessory::validate_for_write;
+            Armor => armors, Armor::validate_for_write;
+            Weapon => weapons, Weapon::validate_for_write;
+            UsableItem => usable_items, UsableItem::validate_for_write;
+            SpecialItem => special_items, SpecialItem::validate_for_write;
+            Character => characters, Character::validate_for_write;
+            CharacterMagic => character_magic, CharacterMagic::validate_for_write;
+            CharacterSuperMove => character_super_moves, CharacterSuperMove::validate_for_write;
+            Shop => shops, Shop::validate_for_write;
+            TreasureChest => treasure_chests, TreasureChest::validate_for_write;
+            CrewMember => crew_members, CrewMember::validate_for_write;
+            PlayableShip => playable_ships, PlayableShip::validate_for_write;
+            ShipCannon => ship_cannons, ShipCannon::validate_for_write;
+            ShipAccessory => ship_accessories, ShipAccessory::validate_for_write;
+            ShipItem => ship_items, ShipItem::validate_for_write;
+            EnemyShip => enemy_ships, no_validation;
+            EnemyMagic => enemy_magic, no_validation;
+            EnemySuperMove => enemy_super_moves, no_validation;
+            Swashbuckler => swashbucklers, Swashbuckler::validate_for_write;
+            SpiritCurve => spirit_curves, no_validation;
+            ExpBoost => exp_boosts, ExpBoost::validate_for_write;
+            ExpCurve => exp_curves, ExpCurve::validate_for_write;
+            MagicExpCurve => magic_exp_curves, MagicExpCurve::validate_for_write;
+            Enemy => enemies, no_validation;
+            EnemyTask => enemy_tasks, no_validation;
+            EnemyEncounter => enemy_encounters, no_validation;
+            EnemyEvent => enemy_events, no_validation;
+        )
+    };
+    (@dispatch $data:expr, $kind:expr, $entries:ident, $validate:ident, $body:expr;
+        $($variant:ident => $field:ident, $check:expr;)*) => {
+        match $kind {
+            $(EntryKind::$variant => {
+                let $entries = &mut $data.$field;
+                let $validate = validator($entries.as_slice(), $check);
+                $body
+            })*
+        }
+    };
+}
+
+impl GameData {
+    /// Apply a batch of edits, all or nothing.
+    ///
+    /// Each edited entry must still deserialize (so values stay in range for
+    /// their field types) and pass its `validate_for_write` check. If any
+    /// edit fails, none are applied and the first error is returned.
+    pub fn apply_edits(&mut self, edits: &[Edit]) -> Result<()> {
+        self.apply_edits_tracked(edits).map(|_| ())
+    }
+
+    /// Like [`apply_edits`](Self::apply_edits), also returning the entries
+    /// that changed so the batch can be undone with
+    /// [`revert_changes`](Self::revert_changes).
+    pub fn apply_edits_tracked(&mut self, edits: &[Edit]) -> Result<Vec<EntryChange>> {
+        let mut edited = self.clone();
+        let mut changes = Vec::new();
+        for edit in edits {
+            with_entries!(edited, edit.kind, |entries, validate| {
+                edit_entries(entries, edit, validate, &mut changes)
+            })?;
+        }
+        *self = edited;
+        Ok(changes)
+    }
+
+    /// Put the entries back the way they were before `changes`.
+    pub fn revert_changes(&mut self, changes: &[EntryChange]) -> Result<()> {
+        for change in changes.iter().rev() {
+            self.set_entry(change, &change.before)?;
+        }
+        Ok(())
+    }
+
+    /// Apply `changes` again after [`revert_changes`](Self::revert_changes).
+    pub fn reapply_changes(&mut self, changes: &[EntryChange]) -> Result<()> {
+        for change in changes {
+            self.set_entry(change, &change.after)?;
+        }
+        Ok(())
+    }
+
+    /// Run every entry's `validate_for_write` check, collecting all failures
+    /// instead of stopping at the first.
+    pub fn validate_for_write(&self) -> Vec<Error> {
+        let mut data = self.clone();
+        let mut errors = Vec::new();
+        for kind in EntryKind::ALL {
+            with_entries!(data, kind, |entries, validate| {
+                errors.extend(entries.iter().filter_map(|entry| validate(entry).err()))
+            });
+        }
+        errors
+    }
+
+    /// List the fields that differ from `self` to `other`, entry by entry.
+    ///
+    /// Entries are compared by their index in each list. An entry only in
+    /// one of them shows every field with `null` on the missing side.
+    pub fn diff(&self, other: &GameData) -> Result<Vec<FieldDiff>> {
+        let (mut before, mut after) = (self.clone(), other.clone());
+        let mut diffs = Vec::new();
+        for kind in EntryKind::ALL {
+            let old = with_entries!(before, kind, |entries, _validate| to_value(&*entries))?;
+            let new = with_entries!(after, kind, |entries, _validate| to_value(&*entries))?;
+            diff_entries(kind, &old, &new, &mut diffs);
+        }
+        Ok(diffs)
+    }
+
+    fn set_entry(&mut self, change: &EntryChange, value: &Value) -> Result<()> {
+        with_entries!(self, change.kind, |entries, _validate| {
+            let entry = entries.get_mut(change.index).ok_or_else(|| {
+                Error::ValidationError(format!("No {} at index {}", change.kind, change.index))
+            })?;
+            *entry = from_value(value.clone(), change.kind, change.index as u32)?;
+            Ok(())
+        })
+    }
+}
+
+/// Validation for entry types without a `validate_for_write`.
+fn no_validation<T>(_entry: &T) -> Result<()> {
+    Ok(())
+}
+
+/// Pin a write check to the entry type of `entries`.
+fn validator<T>(_entries: &[T], check: fn(&T) -> Result<()>) -> fn(&T) -> Result<()> {
+    check
+}
+
+/// Merge `edit.fields` into every entry matching the edit's key, recording
+/// each change.
+fn edit_entries<T>(
+    entries: &mut [T],
+    edit: &Edit,
+    validate: fn(&T) -> Result<()>,
+    changes: &mut Vec<EntryChange>,
+) -> Result<()>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let fields = edit.fields.as_object().ok_or_else(|| {
+        Error::ValidationError(format!(
+            "Edit of {} {}: fields must be an object",
+            edit.kind, edit.id
+        ))
+    })?;
+    let key = edit.key()?;
+    for (field, value) in &key {
+        if fields.get(*field).is_some_and(|new| new != value) {
+            return Err(Error::ValidationError(format!(
+                "Edit of {} {} can't change its {}",
+                edit.kind, edit.id, field
+            )));
+        }
+    }
+
+    let mut found = false;
+    for (index, entry) in entries.iter_mut().enumerate() {
+        let before = to_value(entry)?;
+        if key
+            .iter()
+            .any(|(field, value)| before.get(*field) != Some(value))
+        {
+            continue;
+        }
+        found = true;
+
+        for key in fields.keys() {
+            if before.get(key).is_none() {
+                return Err(Error::ValidationError(format!(
+                    "Edit of {} {}: unknown field {}",
+                    edit.kind, edit.id, key
+                )));
+            }
+        }
+        let mut after = before.clone();
+        merge(&mut after, &edit.fields);
+
+        let updated: T = from_value(after.clone(), edit.kind, edit.id)?;
+        validate(&updated)?;
+        *entry = updated;
+        if before != after {
+            changes.push(EntryChange {
+                kind: edit.kind,
+                index,
+                before,
+                after,
+            });
+        }
+    }
+
+    if !found {
+        return Err(Error::ValidationError(format!(
+            "No {} with ID {}",
+            edit.kind, edit.id
+        )));
+    }
+    Ok(())
+}
+
+/// Record the differing fields of two serialized entry lists.
+fn diff_entries(kind: EntryKind, before: &Value, after: &Value, diffs: &mut Vec<FieldDiff>) {
+    let empty = Vec::new();
+    let before = before.as_array().unwrap_or(&empty);
+    let after = after.as_array().unwrap_or(&empty);
+    let null = Value::Null;
+
+    for index in 0..before.len().max(after.len()) {
+        let (old, new) = (before.get(index), after.get(index));
+        if old == new {
+            continue;
+        }
+        let entry = new.or(old);
+        let id = entry
+            .and_then(|entry| entry.get("id"))
+            .and_then(Value::as_u64)
+            .unwrap_or_default() as u32;
+        let enemy_id = match kind {
+            EntryKind::EnemyTask => entry
+                .and_then(|entry| entry.get("enemy_id"))
+                .and_then(Value::as_u64)
+                .map(|enemy_id| enemy_id as u32),
+            _ => None,
+        };
+        let filter = match kind {
+            EntryKind::EnemyEncounter | EntryKind::EnemyEvent => entry
+                .and_then(|entry| entry.get("filter"))
+                .and_then(Value::as_str)
+                .map(String::from),
+            _ => None,
+        };
+
+        let mut fields: Vec<&String> = old
+            .and_then(Value::as_object)
+            .into_iter()
+            .chain(new.and_then(Value::as_object))
+            .flat_map(|entry| entry.keys())
+            .collect();
+        fields.sort();
+        fields.dedup();
+
+        for field in fields {
+            let old_value = old.and_then(|entry| entry.get(field)).unwrap_or(&null);
+            let new_value = new.and_then(|entry| entry.get(field)).unwrap_or(&null);
+            if old_value != new_value {
+                diffs.push(FieldDiff {
+                    kind,
+                    id,
+                    enemy_id,
+                    filter: filter.clone(),
+                    index,
+                    field: field.clone(),
+                    before: old_value.clone(),
+                    after: new_value.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn to_value<T: Serialize>(entry: &T) -> Result<Value> {
+    serde_json::to_value(entry).map_err(|e| Error::ValidationError(e.to_string()))
+}
+
+fn from_value<T: DeserializeOwned>(value: Value, kind: EntryKind, id: u32) -> Result<T> {
+    serde_json::from_value(value)
+        .map_err(|e| Error::ValidationError(format!("Edit of {} {}: {}", kind, id, e)))
+}
+
+/// Merge `patch` into `target`, recursing into objects.
+fn merge(target: &mut Value, patch: &Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                match target.get_mut(key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        target.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, patch) => *target = patch.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entries::EnemyEncounter;
+    use serde_json::json;
+
+    fn data() -> GameData {
+        GameData {
+            shops: vec![
+                Shop {
+                    id: 0,
+                    ..Default::default()
+                },
+                Shop {
+                    id: 1,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_apply_edits_merges_fields() {
+        let mut data = data();
+        let edits = [Edit::new(EntryKind::Shop, 1, json!({ "sot_pos": 7 }))];
+        data.apply_edits(&edits).unwrap();
+        assert_eq!(data.shops[1].sot_pos, 7);
+        assert_eq!(data.shops[0], Shop::default());
+    }
+
+    #[test]
+    fn test_diff_lists_edited_field() {
+        let saved = data();
+        let mut edited = saved.clone();
+        assert!(saved.diff(&edited).unwrap().is_empty());
+
+        edited
+            .apply_edits(&[Edit::new(EntryKind::Shop, 1, json!({ "sot_pos": 7 }))])
+            .unwrap();
+        assert_eq!(
+            saved.diff(&edited).unwrap(),
+            vec![FieldDiff {
+                kind: EntryKind::Shop,
+                id: 1,
+                enemy_id: None,
+                filter: None,
+                index: 1,
+                field: "sot_pos".to_string(),
+                before: json!(0),
+                after: json!(7),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_for_write_collects_all_errors() {
+        let mut data = data();
+        assert!(data.validate_for_write().is_empty());
+
+        for shop in &mut data.shops {
+            shop.item_ids = vec![1; Shop::MAX_ITEMS + 1];
+        }
+        assert_eq!(data.validate_for_write().len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_edit_aborts_batch() {
+        let mut data = data();
+        let before = data.clone();
+        let edits = [
+            Edit::new(EntryKind::Shop, 0, json!({ "sot_pos": 7 })),
+            // Out of range for the field type
+            Edit::new(EntryKind::Shop, 1, json!({ "sot_pos": -1 })),
+        ];
+        assert!(data.apply_edits(&edits).is_err());
+        assert_eq!(data, before);
+
+        // Fails the shop's write validation
+        let too_many_items = Edit::new(
+            EntryKind::Shop,
+            0,
+            json!({ "item_ids": vec![1; Shop::MAX_ITEMS + 1] }),
+        );
+        assert!(data.apply_edits(&[too_many_items]).is_err());
+
+        for fields in [json!({ "not_a_field": 1 }), json!({ "id": 5 }), json!(3)] {
+            let edit = Edit::new(EntryKind::Shop, 0, fields);
+            assert!(data.apply_edits(&[edit]).is_err());
+        }
+        let missing = Edit::new(EntryKind::Shop, 9, json!({}));
+        assert!(data.apply_edits(&[missing]).is_err());
+        assert_eq!(data, before);
+    }
+
+    #[test]
+    fn test_encounter_edits_are_keyed_by_file() {
+        let encounter = |filter: &str| EnemyEncounter {
+            filter: filter.to_string(),
+            ..Default::default()
+        };
+        let mut data = GameData {
+            enemy_encounters: vec![encounter("a_ep.enp"), encounter("b_ep.enp")],
+            ..Default::default()
+        };
+        let opened = data.clone();
+        let set_magic_exp = Edit::new(EntryKind::EnemyEncounter, 0, json!({ "magic_exp": 5 }));
+
+        assert!(data.apply_edits(&[set_magic_exp.clone()]).is_err());
+        let moved = Edit::new(
+            EntryKind::EnemyEncounter,
+            0,
+            json!({ "filter": "b_ep.enp" }),
+        )
+        .in_file("a_ep.enp");
+        assert!(data.apply_edits(&[moved]).is_err());
+
+        data.apply_edits(&[set_magic_exp.in_file("a_ep.enp")])
+            .unwrap();
+        assert_eq!(data.enemy_encounters[0].magic_exp, 5);
+        assert_eq!(data.enemy_encounters[1].magic_exp, 0);
+
+        // Diffs carry the file, so they can be turned back into edits
+        let diffs = opened.diff(&data).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].filter.as_deref(), Some("a_ep.enp"));
+        assert_eq!(diffs[0].enemy_id, None);
+    }
+}
@@ -0,0 +1,199 @@
+//! Undo/redo history for edits to [`GameData`].
+
+use std::collections::VecDeque;
+
+use super::data::GameData;
+use super::edit::{Edit, EntryChange};
+use crate::error::Result;
+
+/// Default memory budget for [`EditHistory`], in bytes.
+pub const DEFAULT_HISTORY_LIMIT: usize = 8 * 1024 * 1024;
+
+/// Undo/redo stacks of applied edit batches.
+///
+/// Each step only stores the entries that changed (before and after), not
+/// copies of the whole data. When the undo steps use more than the memory
+/// limit, the oldest ones are dropped.
+#[derive(Debug, Clone)]
+pub struct EditHistory {
+    undo: VecDeque<Vec<EntryChange>>,
+    redo: Vec<Vec<EntryChange>>,
+    /// Approximate bytes used by the undo steps
+    size: usize,
+    limit: usize,
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+impl EditHistory {
+    /// Create an empty history that keeps about `limit` bytes of undo steps.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            size: 0,
+            limit,
+        }
+    }
+
+    /// Apply a batch of edits to `data` (all or nothing) as one undo step.
+    ///
+    /// Clears the redo stack.
+    pub fn apply(&mut self, data: &mut GameData, edits: &[Edit]) -> Result<()> {
+        let changes = data.apply_edits_tracked(edits)?;
+        if changes.is_empty() {
+            return Ok(());
+        }
+        self.redo.clear();
+        self.push_undo(changes);
+        Ok(())
+    }
+
+    /// Undo the last step. Returns `false` if there was nothing to undo.
+    ///
+    /// If the step can't be reverted, `data` and the history are left as
+    /// they were.
+    pub fn undo(&mut self, data: &mut GameData) -> Result<bool> {
+        let Some(changes) = self.undo.back() else {
+            return Ok(false);
+        };
+        let mut reverted = data.clone();
+        reverted.revert_changes(changes)?;
+        *data = reverted;
+
+        if let Some(changes) = self.undo.pop_back() {
+            self.size -= step_size(&changes);
+            self.redo.push(changes);
+        }
+        Ok(true)
+    }
+
+    /// Redo the last undone step. Returns `false` if there was nothing to redo.
+    ///
+    /// If the step can't be reapplied, `data` and the history are left as
+    /// they were.
+    pub fn redo(&mut self, data: &mut GameData) -> Result<bool> {
+        let Some(changes) = self.redo.last() else {
+            return Ok(false);
+        };
+        let mut reapplied = data.clone();
+        reapplied.reapply_changes(changes)?;
+        *data = reapplied;
+
+        if let Some(changes) = self.redo.pop() {
+            self.push_undo(changes);
+        }
+        Ok(true)
+    }
+
+    /// Check if there is a step to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Check if there is a step to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forget all steps.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.size = 0;
+    }
+
+    fn push_undo(&mut self, changes: Vec<EntryChange>) {
+        self.size += step_size(&changes);
+        self.undo.push_back(changes);
+        // Always keep the newest step, even if it alone is over the limit
+        while self.size > self.limit && self.undo.len() > 1 {
+            if let Some(oldest) = self.undo.pop_front() {
+                self.size -= step_size(&oldest);
+            }
+        }
+    }
+}
+
+fn step_size(changes: &[EntryChange]) -> usize {
+    changes.iter().map(EntryChange::size).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entries::{EntryKind, Shop};
+    use serde_json::json;
+
+    fn data() -> GameData {
+        GameData {
+            shops: vec![Shop::default()],
+            ..Default::default()
+        }
+    }
+
+    fn set_sot_pos(value: u32) -> Edit {
+        Edit::new(EntryKind::Shop, 0, json!({ "sot_pos": value }))
+    }
+
+    #[test]
+    fn test_edit_undo_redo() {
+        let mut data = data();
+        let mut history = EditHistory::default();
+        assert!(!history.undo(&mut data).unwrap());
+
+        history.apply(&mut data, &[set_sot_pos(5)]).unwrap();
+        history.apply(&mut data, &[set_sot_pos(9)]).unwrap();
+        assert_eq!(data.shops[0].sot_pos, 9);
+
+        assert!(history.undo(&mut data).unwrap());
+        assert_eq!(data.shops[0].sot_pos, 5);
+        assert!(history.undo(&mut data).unwrap());
+        assert_eq!(data.shops[0].sot_pos, 0);
+        assert!(!history.can_undo());
+
+        assert!(history.redo(&mut data).unwrap());
+        assert_eq!(data.shops[0].sot_pos, 5);
+
+        // A new edit drops the remaining redo step
+        history.apply(&mut data, &[set_sot_pos(7)]).unwrap();
+        assert!(!history.can_redo());
+        assert!(!history.redo(&mut data).unwrap());
+        assert_eq!(data.shops[0].sot_pos, 7);
+        assert!(history.undo(&mut data).unwrap());
+        assert_eq!(data.shops[0].sot_pos, 5);
+    }
+
+    #[test]
+    fn test_failed_undo_keeps_the_step() {
+        let mut data = data();
+        let mut history = EditHistory::default();
+        history.apply(&mut data, &[set_sot_pos(5)]).unwrap();
+
+        // The edited shop is gone, so its change can't be reverted
+        let mut emptied = GameData::default();
+        assert!(history.undo(&mut emptied).is_err());
+        assert_eq!(emptied, GameData::default());
+        assert!(history.can_undo());
+        assert!(!history.can_redo());
+
+        assert!(history.undo(&mut data).unwrap());
+        assert_eq!(data.shops[0].sot_pos, 0);
+    }
+
+    #[test]
+    fn test_history_limit_drops_oldest() {
+        let mut data = data();
+        let mut history = EditHistory::new(1);
+        for value in 1..=3 {
+            history.apply(&mut data, &[set_sot_pos(value)]).unwrap();
+        }
+        assert!(history.undo(&mut data).unwrap());
+        assert_eq!(data.shops[0].sot_pos, 2);
+        assert!(!history.undo(&mut data).unwrap());
+    }
+}
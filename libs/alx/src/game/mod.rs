@@ -1,9 +1,19 @@
 //! Game root detection and context management.
 
+mod data;
+pub mod dump_quality;
+mod edit;
+mod history;
 pub mod offsets;
 pub mod region;
 mod root;
+mod snapshot;
 
-pub use offsets::{id_ranges, Offsets};
+pub use data::{GameData, ItemRef};
+pub use dump_quality::{DumpQuality, KNOWN_DOL_CRCS};
+pub use edit::{Edit, EntryChange, FieldDiff};
+pub use history::{EditHistory, DEFAULT_HISTORY_LIMIT};
+pub use offsets::{id_ranges, DescriptionAlignment, Offsets};
 pub use region::{GameVersion, Platform, Region};
 pub use root::GameRoot;
+pub use snapshot::GameSnapshot;
@@ -3,13 +3,20 @@
 //! These offsets are extracted from the original ALX dscrptr.rb configuration.
 //! They specify where various game data structures are located in Start.dol.
 
+use std::io::Read;
 use std::ops::Range;
 
+use serde::{Deserialize, Serialize};
+
 use super::region::{GameVersion, Region};
-use crate::error::Result;
+use crate::error::{Error, Result};
 
 /// Data offsets for a specific game version.
-#[derive(Debug, Clone)]
+///
+/// Offsets for other builds can be loaded from TOML with
+/// [`from_toml`](Self::from_toml).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Offsets {
     /// Accessory data range in Start.dol
     pub accessory_data: Range<usize>,
@@ -102,21 +109,197 @@ pub struct Offsets {
     pub exp_boost_data: Option<Range<usize>>,
 
     /// Level data file path (for EXP curves)
-    pub level_file: &'static str,
+    pub level_file: String,
     /// EXP curve data range within level file
     pub exp_curve_data: Range<usize>,
     /// Magic EXP curve data range within level file
     pub magic_exp_curve_data: Range<usize>,
+
+    /// Block alignment of the description strings for each type
+    #[serde(default)]
+    pub dscr_alignment: DescriptionAlignment,
+}
+
+/// Block alignment (in bytes) of the description strings for each entry type.
+///
+/// Each description is padded to a multiple of this size. All known builds
+/// use 4 bytes, which is the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DescriptionAlignment {
+    pub accessory: usize,
+    pub armor: usize,
+    pub weapon: usize,
+    pub usable_item: usize,
+    pub special_item: usize,
+    pub character_magic: usize,
+    pub character_super_move: usize,
+    pub ship_cannon: usize,
+    pub ship_accessory: usize,
+    pub ship_item: usize,
+    pub crew_member: usize,
+    pub shop: usize,
+}
+
+impl DescriptionAlignment {
+    /// Use the same alignment for every type.
+    pub const fn uniform(align: usize) -> Self {
+        Self {
+            accessory: align,
+            armor: align,
+            weapon: align,
+            usable_item: align,
+            special_item: align,
+            character_magic: align,
+            character_super_move: align,
+            ship_cannon: align,
+            ship_accessory: align,
+            ship_item: align,
+            crew_member: align,
+            shop: align,
+        }
+    }
+}
+
+impl Default for DescriptionAlignment {
+    fn default() -> Self {
+        Self::uniform(4)
+    }
 }
 
 impl Offsets {
     /// Get offsets for a specific game version.
+    ///
+    /// The built-in tables are for the first release (revision 0) of each
+    /// region. Other disc revisions fail with [`Error::FeatureUnavailable`]
+    /// until a table for them is added, since their data may have moved;
+    /// read one with [`from_toml`](Self::from_toml) and open the game with
+    /// [`GameRoot::open_with_offsets`](crate::GameRoot::open_with_offsets).
     pub fn for_version(version: &GameVersion) -> Result<Self> {
-        match version.region {
-            Region::Us => Ok(Self::gc_us()),
-            Region::Jp => Ok(Self::gc_jp()),
-            Region::Eu => Ok(Self::gc_eu()),
+        let offsets = match (version.region, version.revision) {
+            (Region::Us, 0) => Self::gc_us(),
+            (Region::Jp, 0) => Self::gc_jp(),
+            (Region::Eu, 0) => Self::gc_eu(),
+            (_, revision) => {
+                return Err(Error::FeatureUnavailable {
+                    feature: "offsets",
+                    reason: format!(
+                        "no offset table for {} rev {}",
+                        version.product_id, revision
+                    ),
+                })
+            }
+        };
+        offsets.validate_non_overlapping()?;
+        Ok(offsets)
+    }
+
+    /// Read an offset table from TOML, e.g. for a fan build the built-in
+    /// tables don't cover.
+    ///
+    /// Every field of [`Offsets`] is a key, and ranges are tables with a
+    /// `start` and an `end`:
+    ///
+    /// ```toml
+    /// weapon_data = { start = 0x2c2790, end = 0x2c3190 }
+    /// level_file = "battle/first.lmt"
+    /// ```
+    ///
+    /// `exp_boost_data` and `dscr_alignment` may be left out. The table is
+    /// checked with [`validate_non_overlapping`](Self::validate_non_overlapping).
+    pub fn from_toml<R: Read>(mut reader: R) -> Result<Self> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        let offsets: Self = toml::from_str(&text)
+            .map_err(|e| Error::ValidationError(format!("Invalid offsets file: {}", e)))?;
+        offsets.validate_non_overlapping()?;
+        Ok(offsets)
+    }
+
+    /// Write this offset table as TOML, readable by [`from_toml`](Self::from_toml).
+    ///
+    /// A built-in table makes a starting point for a new build's file.
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self)
+            .map_err(|e| Error::ValidationError(format!("Could not write offsets: {}", e)))
+    }
+
+    /// Check that no two data or description ranges overlap.
+    ///
+    /// Start.dol ranges are checked against each other, and the level file
+    /// ranges against each other. Empty ranges (e.g. EU descriptions, which
+    /// live in SOT files) are ignored.
+    pub fn validate_non_overlapping(&self) -> Result<()> {
+        let level_ranges = [
+            ("exp_curve_data", self.exp_curve_data.clone()),
+            ("magic_exp_curve_data", self.magic_exp_curve_data.clone()),
+        ];
+
+        for ranges in [&self.regions()[..], &level_ranges[..]] {
+            for (i, (name_a, a)) in ranges.iter().enumerate() {
+                for (name_b, b) in &ranges[i + 1..] {
+                    if !a.is_empty() && !b.is_empty() && a.start < b.end && b.start < a.end {
+                        return Err(Error::ValidationError(format!(
+                            "Offset ranges overlap: {} ({:#x}..{:#x}) and {} ({:#x}..{:#x})",
+                            name_a, a.start, a.end, name_b, b.start, b.end
+                        )));
+                    }
+                }
+            }
         }
+
+        Ok(())
+    }
+
+    /// All named data and description regions in Start.dol, sorted by
+    /// start offset.
+    pub fn regions(&self) -> Vec<(&'static str, Range<usize>)> {
+        let mut ranges = vec![
+            ("accessory_data", self.accessory_data.clone()),
+            ("accessory_dscr", self.accessory_dscr.clone()),
+            ("armor_data", self.armor_data.clone()),
+            ("armor_dscr", self.armor_dscr.clone()),
+            ("weapon_data", self.weapon_data.clone()),
+            ("weapon_dscr", self.weapon_dscr.clone()),
+            ("weapon_effect_data", self.weapon_effect_data.clone()),
+            ("usable_item_data", self.usable_item_data.clone()),
+            ("usable_item_dscr", self.usable_item_dscr.clone()),
+            ("special_item_data", self.special_item_data.clone()),
+            ("special_item_dscr", self.special_item_dscr.clone()),
+            ("character_data", self.character_data.clone()),
+            ("character_magic_data", self.character_magic_data.clone()),
+            ("character_magic_dscr", self.character_magic_dscr.clone()),
+            (
+                "character_super_move_data",
+                self.character_super_move_data.clone(),
+            ),
+            (
+                "character_super_move_dscr",
+                self.character_super_move_dscr.clone(),
+            ),
+            ("enemy_magic_data", self.enemy_magic_data.clone()),
+            ("enemy_super_move_data", self.enemy_super_move_data.clone()),
+            ("enemy_ship_data", self.enemy_ship_data.clone()),
+            ("playable_ship_data", self.playable_ship_data.clone()),
+            ("ship_cannon_data", self.ship_cannon_data.clone()),
+            ("ship_cannon_dscr", self.ship_cannon_dscr.clone()),
+            ("ship_accessory_data", self.ship_accessory_data.clone()),
+            ("ship_accessory_dscr", self.ship_accessory_dscr.clone()),
+            ("ship_item_data", self.ship_item_data.clone()),
+            ("ship_item_dscr", self.ship_item_dscr.clone()),
+            ("crew_member_data", self.crew_member_data.clone()),
+            ("crew_member_dscr", self.crew_member_dscr.clone()),
+            ("shop_data", self.shop_data.clone()),
+            ("shop_dscr", self.shop_dscr.clone()),
+            ("swashbuckler_data", self.swashbuckler_data.clone()),
+            ("treasure_chest_data", self.treasure_chest_data.clone()),
+            ("spirit_curve_data", self.spirit_curve_data.clone()),
+        ];
+        if let Some(range) = &self.exp_boost_data {
+            ranges.push(("exp_boost_data", range.clone()));
+        }
+        ranges.sort_by_key(|(name, range)| (range.start, range.end, *name));
+        ranges
     }
 
     /// Offsets for GC-US-GEA (2002-12-19 Final US Build).
@@ -167,9 +350,11 @@ impl Offsets {
             exp_boost_data: Some(0x2d1638..0x2d168c),
 
             // Level file (contains EXP curves)
-            level_file: "battle/first.lmt",
+            level_file: "battle/first.lmt".to_string(),
             exp_curve_data: 0x0..0x948,
             magic_exp_curve_data: 0x948..0xaf8,
+
+            dscr_alignment: DescriptionAlignment::default(),
         }
     }
 
@@ -221,9 +406,11 @@ impl Offsets {
             exp_boost_data: Some(0x2d1614..0x2d1668),
 
             // Level file (contains EXP curves)
-            level_file: "battle/first.lmt",
+            level_file: "battle/first.lmt".to_string(),
             exp_curve_data: 0x0..0x948,
             magic_exp_curve_data: 0x948..0xaf8,
+
+            dscr_alignment: DescriptionAlignment::default(),
         }
     }
 
@@ -275,9 +462,11 @@ impl Offsets {
             exp_boost_data: Some(0x2cffd0..0x2d0024),
 
             // Level file (contains EXP curves)
-            level_file: "battle/first.lmt",
+            level_file: "battle/first.lmt".to_string(),
             exp_curve_data: 0x0..0x948,
             magic_exp_curve_data: 0x948..0xaf8,
+
+            dscr_alignment: DescriptionAlignment::default(),
         }
     }
 }
@@ -381,4 +570,83 @@ mod tests {
         assert!(size > 0);
         assert_eq!(count, 80); // 0xA0 - 0x50 = 80 armors
     }
+
+    #[test]
+    fn test_default_description_alignment() {
+        for offsets in [Offsets::gc_us(), Offsets::gc_jp(), Offsets::gc_eu()] {
+            assert_eq!(offsets.dscr_alignment, DescriptionAlignment::uniform(4));
+        }
+    }
+
+    #[test]
+    fn test_offsets_per_revision() {
+        let mut header = b"GEAE8P".to_vec();
+        header.resize(0x20, 0);
+        let rev0 = GameVersion::from_boot_header(&header).unwrap();
+        assert_eq!(
+            Offsets::for_version(&rev0).unwrap().regions(),
+            Offsets::gc_us().regions()
+        );
+
+        header[7] = 1;
+        let rev1 = GameVersion::from_boot_header(&header).unwrap();
+        let err = Offsets::for_version(&rev1).unwrap_err().to_string();
+        assert!(err.contains("GEAE8P rev 1"), "{}", err);
+    }
+
+    #[test]
+    fn test_known_offsets_do_not_overlap() {
+        for offsets in [Offsets::gc_us(), Offsets::gc_jp(), Offsets::gc_eu()] {
+            offsets.validate_non_overlapping().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_regions_sorted_by_offset() {
+        let regions = Offsets::gc_us().regions();
+        assert!(!regions.is_empty());
+        assert!(regions.windows(2).all(|w| w[0].1.start <= w[1].1.start));
+        assert!(regions.iter().any(|(name, _)| *name == "weapon_data"));
+    }
+
+    #[test]
+    fn test_overlapping_offsets_rejected() {
+        let mut offsets = Offsets::gc_us();
+        // Point usable items into the middle of the weapon table
+        let start = offsets.weapon_data.start + 0x10;
+        offsets.usable_item_data = start..start + 0x20;
+
+        let err = offsets.validate_non_overlapping().unwrap_err().to_string();
+        assert!(err.contains("weapon_data"));
+        assert!(err.contains("usable_item_data"));
+    }
+
+    #[test]
+    fn test_toml_roundtrip() {
+        let offsets = Offsets::gc_eu();
+        let loaded = Offsets::from_toml(offsets.to_toml().unwrap().as_bytes()).unwrap();
+        assert_eq!(loaded.regions(), offsets.regions());
+        assert_eq!(loaded.exp_boost_data, offsets.exp_boost_data);
+        assert_eq!(loaded.level_file, offsets.level_file);
+        assert_eq!(loaded.exp_curve_data, offsets.exp_curve_data);
+        assert_eq!(loaded.dscr_alignment, offsets.dscr_alignment);
+    }
+
+    #[test]
+    fn test_from_toml_rejects_bad_tables() {
+        let text = Offsets::gc_us().to_toml().unwrap();
+
+        // Unknown keys are likely typos
+        let typo = format!("wepon_data = {{ start = 0, end = 4 }}\n{}", text);
+        assert!(matches!(
+            Offsets::from_toml(typo.as_bytes()),
+            Err(Error::ValidationError(_))
+        ));
+
+        let mut overlapping = Offsets::gc_us();
+        overlapping.armor_data = overlapping.weapon_data.clone();
+        let text = overlapping.to_toml().unwrap();
+        let err = Offsets::from_toml(text.as_bytes()).unwrap_err().to_string();
+        assert!(err.contains("overlap"), "{}", err);
+    }
 }
@@ -48,6 +48,8 @@ pub struct GameVersion {
     pub region: Region,
     pub product_id: String,
     pub build_date: Option<String>,
+    /// Disc revision from the boot header, 0 for the first release
+    pub revision: u8,
 }
 
 impl GameVersion {
@@ -58,9 +60,16 @@ impl GameVersion {
             region,
             product_id,
             build_date: None,
+            revision: 0,
         }
     }
 
+    /// Set the disc revision.
+    pub fn with_revision(mut self, revision: u8) -> Self {
+        self.revision = revision;
+        self
+    }
+
     /// Detect the game version from a game ID string.
     ///
     /// GameCube game IDs are 6 characters:
@@ -90,6 +99,16 @@ impl GameVersion {
         Some(Self::new(Platform::GameCube, region, game_id.to_string()))
     }
 
+    /// Detect the game version from a GameCube boot header.
+    ///
+    /// The game ID is at 0x00-0x05 (see [`from_game_id`](Self::from_game_id))
+    /// and the disc revision at 0x07.
+    pub fn from_boot_header(header: &[u8]) -> Option<Self> {
+        let game_id = std::str::from_utf8(header.get(0..6)?).ok()?;
+        let revision = *header.get(7)?;
+        Some(Self::from_game_id(game_id)?.with_revision(revision))
+    }
+
     /// Get a version key for offset lookups.
     pub fn version_key(&self) -> String {
         format!("GC-{}-GEA", self.region)
@@ -123,7 +142,11 @@ impl GameVersion {
 
 impl fmt::Display for GameVersion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}-{} ({})", self.platform, self.region, self.product_id)
+        write!(f, "{}-{} ({})", self.platform, self.region, self.product_id)?;
+        if self.revision != 0 {
+            write!(f, " rev {}", self.revision)?;
+        }
+        Ok(())
     }
 }
 
@@ -160,4 +183,21 @@ mod tests {
         assert!(GameVersion::from_game_id("XXXX8P").is_none());
         assert!(GameVersion::from_game_id("GEA").is_none());
     }
+
+    #[test]
+    fn test_detect_revision_from_boot_header() {
+        let mut header = b"GEAE8P".to_vec();
+        header.resize(0x20, 0);
+        let rev0 = GameVersion::from_boot_header(&header).unwrap();
+        assert_eq!(rev0.revision, 0);
+        assert_eq!(rev0.to_string(), "GameCube-US (GEAE8P)");
+
+        header[7] = 1;
+        let rev1 = GameVersion::from_boot_header(&header).unwrap();
+        assert_eq!(rev1.revision, 1);
+        assert!(rev1.is_gc_us());
+        assert_eq!(rev1.to_string(), "GameCube-US (GEAE8P) rev 1");
+
+        assert!(GameVersion::from_boot_header(b"GEAE8P").is_none());
+    }
 }
@@ -1,22 +1,29 @@
 //! Game root - main entry point for interacting with a game ISO.
 
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
 
-use super::offsets::Offsets;
+use super::data::GameData;
+use super::dump_quality::DumpQuality;
+use super::offsets::{id_ranges, Offsets};
 use super::region::GameVersion;
+use super::snapshot::GameSnapshot;
 use crate::entries::{
     Accessory, Armor, Character, CharacterMagic, CharacterSuperMove, CrewMember, Enemy,
-    EnemyEncounter, EnemyEvent, EnemyMagic, EnemyShip, EnemySuperMove, EnemyTask, ExpBoost,
-    ExpCurve, MagicExpCurve, PlayableShip, ShipAccessory, ShipCannon, ShipItem, Shop, SpecialItem,
-    SpiritCurve, Swashbuckler, TreasureChest, UsableItem, Weapon, WeaponEffect,
+    EnemyEncounter, EnemyEvent, EnemyMagic, EnemyShip, EnemySuperMove, EnemyTask, EntryKind,
+    ExpBoost, ExpCurve, MagicExpCurve, PlayableShip, ShipAccessory, ShipCannon, ShipItem, Shop,
+    SpecialItem, SpiritCurve, Swashbuckler, TreasureChest, UsableItem, Weapon, WeaponEffect,
 };
 use crate::error::{Error, Result};
 use crate::io::{
     compress_aklz, decompress_aklz, is_aklz, parse_dat_file, parse_enp, parse_evp,
-    patch_enp_encounters,
+    patch_enp_encounters, EnemyFileKind, EnpData,
 };
-use crate::io::{read_description_strings, IsoFile};
+use crate::io::{read_description_strings, write_atomically, IsoFile};
 use crate::items::ItemDatabase;
+use crate::patch::PatchList;
+use crate::randomizer::{self, RandomizeSummary, RandomizerConfig, SpoilerLog};
 
 /// Main interface for working with a Skies of Arcadia Legends ISO.
 pub struct GameRoot {
@@ -27,20 +34,102 @@ pub struct GameRoot {
     dol_data: Option<Vec<u8>>,
     /// Cached level file data (for EXP curves)
     level_data: Option<Vec<u8>>,
+    /// DOL as first read from the ISO, for `reset_to_vanilla`
+    vanilla_dol: Option<Vec<u8>>,
+    /// Level file as first read from the ISO, for `reset_to_vanilla`
+    vanilla_level: Option<Vec<u8>>,
+    /// Whether the cached DOL has writes not yet saved to the ISO
+    dol_dirty: bool,
+    /// Whether the cached level file has writes not yet saved to the ISO
+    level_dirty: bool,
+    /// Cached counts of the entry types that require parsing ENP/EVP files
+    enemy_counts: Option<HashMap<String, usize>>,
+    /// Whether a file has been written to the ISO since it was opened
+    iso_written: bool,
+    /// Ranges patched by `write_to_dol`/`write_to_level`, in write order
+    write_ranges: Vec<std::ops::Range<usize>>,
+    /// Decompressed ENP files by file name, read by
+    /// `build_enemy_database_for_file`
+    enp_cache: HashMap<String, Vec<u8>>,
+    /// Item database built by `item_database`, dropped on DOL writes
+    item_db: Option<ItemDatabase>,
 }
 
 impl GameRoot {
     /// Open a game ISO and detect its version.
+    ///
+    /// Fails with [`Error::FeatureUnavailable`] for a disc revision without
+    /// a built-in offset table; open those with
+    /// [`open_with_offsets`](Self::open_with_offsets).
     pub fn open(path: &Path) -> Result<Self> {
-        let mut iso = IsoFile::open(path)?;
+        Self::from_iso(IsoFile::open(path)?, None)
+    }
+
+    /// Open a game ISO with `offsets` instead of the built-in table for its
+    /// version.
+    pub fn open_with_offsets(path: &Path, offsets: Offsets) -> Result<Self> {
+        Self::from_iso(IsoFile::open(path)?, Some(offsets))
+    }
+
+    /// Open a game ISO for examining only.
+    ///
+    /// All `write_*` and `save_*` methods on the returned game fail with
+    /// [`Error::ReadOnly`], so the ISO can't be modified by accident.
+    pub fn open_readonly(path: &Path) -> Result<Self> {
+        Self::from_iso(IsoFile::open_readonly(path)?, None)
+    }
+
+    /// Open a game ISO for examining only, with `offsets` instead of the
+    /// built-in table for its version.
+    pub fn open_readonly_with_offsets(path: &Path, offsets: Offsets) -> Result<Self> {
+        Self::from_iso(IsoFile::open_readonly(path)?, Some(offsets))
+    }
+
+    /// Open a game that has been extracted to a directory instead of an ISO.
+    ///
+    /// See [`IsoFile::open_dir`] for the supported layouts. Reads and writes
+    /// work the same as for an ISO, on the extracted files;
+    /// [`save_as`](Self::save_as) needs an ISO.
+    pub fn open_fst_dir(path: &Path) -> Result<Self> {
+        Self::from_iso(IsoFile::open_dir(path)?, None)
+    }
+
+    /// Open a game ISO that is already in memory.
+    ///
+    /// Reads work without touching the file system, which suits tests and
+    /// targets without one. The game is read-only, like
+    /// [`open_readonly`](Self::open_readonly).
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        Self::from_iso(IsoFile::from_bytes(data)?, None)
+    }
 
+    /// Read a whole game ISO from `reader` into memory and open it.
+    ///
+    /// See [`from_bytes`](Self::from_bytes).
+    pub fn from_reader<R: Read + Seek>(mut reader: R) -> Result<Self> {
+        let mut data = Vec::new();
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_to_end(&mut data)?;
+        Self::from_bytes(data)
+    }
+
+    /// Detect the game version of an opened ISO and pick its offsets, unless
+    /// `offsets` are given.
+    fn from_iso(mut iso: IsoFile, offsets: Option<Offsets>) -> Result<Self> {
         // Read game ID to detect version
         let game_id = iso.read_game_id()?;
-        let version = GameVersion::from_game_id(&game_id).ok_or_else(|| {
-            Error::InvalidIso(format!("Not a Skies of Arcadia Legends ISO: {}", game_id))
-        })?;
-
-        let offsets = Offsets::for_version(&version)?;
+        let revision = iso.read_disc_revision()?;
+        let version = GameVersion::from_game_id(&game_id)
+            .ok_or_else(|| {
+                Error::InvalidIso(format!("Not a Skies of Arcadia Legends ISO: {}", game_id))
+            })?
+            .with_revision(revision);
+
+        let offsets = match offsets {
+            Some(offsets) => offsets,
+            None => Offsets::for_version(&version)?,
+        };
+        log::info!("Opened {} as {}", iso.path().display(), version);
 
         Ok(Self {
             iso,
@@ -48,9 +137,31 @@ impl GameRoot {
             offsets,
             dol_data: None,
             level_data: None,
+            vanilla_dol: None,
+            vanilla_level: None,
+            dol_dirty: false,
+            level_dirty: false,
+            enemy_counts: None,
+            iso_written: false,
+            write_ranges: Vec::new(),
+            enp_cache: HashMap::new(),
+            item_db: None,
         })
     }
 
+    /// Check if this game was opened with [`GameRoot::open_readonly`].
+    pub fn is_read_only(&self) -> bool {
+        self.iso.is_read_only()
+    }
+
+    /// Fail with [`Error::ReadOnly`] if this game was opened read-only.
+    fn ensure_writable(&self) -> Result<()> {
+        if self.is_read_only() {
+            return Err(Error::ReadOnly);
+        }
+        Ok(())
+    }
+
     /// Get the detected game version.
     pub fn version(&self) -> &GameVersion {
         &self.version
@@ -61,6 +172,16 @@ impl GameRoot {
         &self.offsets
     }
 
+    /// Replace the data offsets (e.g. for a modified or unusual build).
+    pub fn set_offsets(&mut self, offsets: Offsets) {
+        self.offsets = offsets;
+        self.level_data = None;
+        self.vanilla_level = None;
+        self.level_dirty = false;
+        self.item_db = None;
+        self.enemy_counts = None;
+    }
+
     /// List files in the ISO matching a pattern.
     pub fn list_iso_files_matching(
         &mut self,
@@ -74,6 +195,75 @@ impl GameRoot {
         self.iso.read_file_direct(entry)
     }
 
+    /// Read a file from the ISO by its path (e.g. `field/a101b_ep.enp`),
+    /// decompressing it if it is AKLZ compressed.
+    ///
+    /// Files that aren't compressed are returned as-is.
+    pub fn decompress_file(&mut self, iso_path: &str) -> Result<Vec<u8>> {
+        use crate::io::decompress_aklz;
+
+        let path = Path::new(iso_path.trim_start_matches('/'));
+        let entry = self
+            .iso
+            .list_files()?
+            .into_iter()
+            .find(|entry| entry.path == path)
+            .ok_or_else(|| Error::FileNotFound {
+                path: path.to_path_buf(),
+            })?;
+        decompress_aklz(&self.iso.read_file_direct(&entry)?)
+    }
+
+    /// Get the ENP files in the ISO by base filename: the `*_ep.enp` files
+    /// and the segment files baked into them (see [`EnemyFileKind::is_enp`]).
+    pub fn enp_file_entries(&mut self) -> Result<BTreeMap<String, crate::io::IsoFileEntry>> {
+        let mut entries = BTreeMap::new();
+        for kind in [EnemyFileKind::Enp, EnemyFileKind::EnpSegment] {
+            for entry in self.list_enemy_files(kind)? {
+                if let Some(name) = entry.path.file_name() {
+                    let name = name.to_string_lossy().to_string();
+                    entries.entry(name).or_insert(entry);
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// List the base filenames of all ENP files in the ISO (sorted, deduplicated).
+    pub fn list_enp_files(&mut self) -> Result<Vec<String>> {
+        Ok(self.enp_file_entries()?.into_keys().collect())
+    }
+
+    /// Read the decompressed contents of every ENP file, keyed by filename
+    /// in [`list_enp_files`](Self::list_enp_files) order.
+    pub fn read_all_enp_raw(&mut self) -> Result<Vec<(String, Vec<u8>)>> {
+        use crate::io::decompress_aklz;
+
+        let entries = self.enp_file_entries()?;
+        let mut files = Vec::with_capacity(entries.len());
+        for (name, entry) in entries {
+            let data = decompress_aklz(&self.iso.read_file_direct(&entry)?)?;
+            files.push((name, data));
+        }
+        Ok(files)
+    }
+
+    /// List the ISO files holding one kind of enemy data.
+    fn list_enemy_files(&mut self, kind: EnemyFileKind) -> Result<Vec<crate::io::IsoFileEntry>> {
+        Ok(self
+            .iso
+            .list_files()?
+            .into_iter()
+            .filter(|entry| {
+                entry
+                    .path
+                    .file_name()
+                    .and_then(|name| EnemyFileKind::classify(&name.to_string_lossy()))
+                    == Some(kind)
+            })
+            .collect())
+    }
+
     /// Get a reference to the ISO file.
     pub fn iso(&self) -> &IsoFile {
         &self.iso
@@ -91,11 +281,21 @@ impl GameRoot {
             // gc_fst uses "Start.dol" as a special path (not the filesystem path)
             let dol_path = Path::new("Start.dol");
             let data = self.iso.read_file(dol_path)?;
+            self.vanilla_dol = Some(data.clone());
             self.dol_data = Some(data);
         }
         Ok(self.dol_data.as_ref().unwrap())
     }
 
+    /// Check the current DOL against known-good builds.
+    ///
+    /// Reflects any unsaved DOL edits; a DOL that can't be read is `Unknown`.
+    pub fn dump_quality(&mut self) -> DumpQuality {
+        self.load_dol()
+            .map(DumpQuality::from_dol)
+            .unwrap_or(DumpQuality::Unknown)
+    }
+
     /// Get a slice of the DOL data at the given range.
     pub fn dol_slice(&mut self, range: std::ops::Range<usize>) -> Result<&[u8]> {
         let dol = self.load_dol()?;
@@ -115,16 +315,13 @@ impl GameRoot {
 
     /// Load the DOL data mutably (for writing).
     fn load_dol_mut(&mut self) -> Result<&mut Vec<u8>> {
-        if self.dol_data.is_none() {
-            let dol_path = Path::new("Start.dol");
-            let data = self.iso.read_file(dol_path)?;
-            self.dol_data = Some(data);
-        }
+        self.load_dol()?;
         Ok(self.dol_data.as_mut().unwrap())
     }
 
     /// Write bytes to a range in the DOL data.
     pub fn write_to_dol(&mut self, range: std::ops::Range<usize>, data: &[u8]) -> Result<()> {
+        self.ensure_writable()?;
         let dol = self.load_dol_mut()?;
         if range.end > dol.len() {
             return Err(Error::ParseError {
@@ -144,30 +341,109 @@ impl GameRoot {
                 range.len()
             )));
         }
-        dol[range].copy_from_slice(data);
+        dol[range.clone()].copy_from_slice(data);
+        self.write_ranges.push(range);
+        self.dol_dirty = true;
+        self.item_db = None;
+        Ok(())
+    }
+
+    /// Get the byte ranges patched by `write_to_dol` and `write_to_level`
+    /// since the game was opened (or since [`GameRoot::clear_write_ranges`]),
+    /// in write order.
+    ///
+    /// Each range is an offset into the file it was written to (Start.dol or
+    /// the level file). Useful for spotting accidental overlapping writes.
+    pub fn last_write_ranges(&self) -> &[std::ops::Range<usize>] {
+        &self.write_ranges
+    }
+
+    /// Forget the recorded write ranges.
+    pub fn clear_write_ranges(&mut self) {
+        self.write_ranges.clear();
+    }
+
+    /// Apply raw byte patches to the DOL data.
+    ///
+    /// For changes outside the structured data regions (e.g. ASM hacks).
+    /// Every patch is bounds-checked before any is applied, so a bad patch
+    /// leaves the DOL untouched.
+    pub fn apply_dol_patch(&mut self, patches: &PatchList) -> Result<()> {
+        self.ensure_writable()?;
+        let dol_len = self.load_dol_mut()?.len();
+        if let Some(patch) = patches.patches().iter().find(|p| p.range().end > dol_len) {
+            return Err(Error::ValidationError(format!(
+                "Patch at {:#x}..{:#x} exceeds DOL size {:#x}",
+                patch.offset,
+                patch.range().end,
+                dol_len
+            )));
+        }
+
+        for patch in patches.patches() {
+            self.write_to_dol(patch.range(), &patch.bytes)?;
+        }
         Ok(())
     }
 
     /// Save the modified DOL back to the ISO.
+    ///
+    /// If the write fails (disk full, file locked) the error is returned and
+    /// the DOL stays marked as unsaved, see
+    /// [`has_unsaved_changes`](Self::has_unsaved_changes). The ISO may then
+    /// be partly written, so prefer [`save_as`](Self::save_as), which never
+    /// touches the source.
     pub fn save_dol(&mut self) -> Result<()> {
+        self.ensure_writable()?;
         if let Some(ref dol_data) = self.dol_data {
             let dol_path = Path::new("Start.dol");
+            self.iso_written = true;
             self.iso.write_file(dol_path, dol_data)?;
+            log::info!("Saved Start.dol ({} bytes)", dol_data.len());
         }
+        self.dol_dirty = false;
         Ok(())
     }
 
+    /// Check if anything has been written to the ISO since it was opened,
+    /// including writes that failed partway.
+    pub fn has_written_iso(&self) -> bool {
+        self.iso_written
+    }
+
+    /// Check if the DOL or level file has writes that haven't been saved to
+    /// the ISO yet.
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.dol_dirty || self.level_dirty
+    }
+
     /// Load the level file (contains EXP curves) into memory.
     /// This is cached for subsequent reads.
     pub fn load_level_file(&mut self) -> Result<&[u8]> {
         if self.level_data.is_none() {
-            let level_path = Path::new(self.offsets.level_file);
+            let level_path = Path::new(&self.offsets.level_file);
             let data = self.iso.read_file(level_path)?;
+            self.vanilla_level = Some(data.clone());
             self.level_data = Some(data);
         }
         Ok(self.level_data.as_ref().unwrap())
     }
 
+    /// Ensure the level file is loaded, reporting a missing or unreadable
+    /// level file as [`Error::FeatureUnavailable`] for `feature`.
+    fn require_level_file(&mut self, feature: &'static str) -> Result<()> {
+        if let Err(e) = self.load_level_file() {
+            return Err(Error::FeatureUnavailable {
+                feature,
+                reason: format!(
+                    "level file {} could not be loaded ({})",
+                    self.offsets.level_file, e
+                ),
+            });
+        }
+        Ok(())
+    }
+
     /// Get a slice of the level file data at the given range.
     pub fn level_slice(&mut self, range: std::ops::Range<usize>) -> Result<&[u8]> {
         let level = self.load_level_file()?;
@@ -187,16 +463,13 @@ impl GameRoot {
 
     /// Load the level file data mutably (for writing).
     fn load_level_mut(&mut self) -> Result<&mut Vec<u8>> {
-        if self.level_data.is_none() {
-            let level_path = Path::new(self.offsets.level_file);
-            let data = self.iso.read_file(level_path)?;
-            self.level_data = Some(data);
-        }
+        self.load_level_file()?;
         Ok(self.level_data.as_mut().unwrap())
     }
 
     /// Write bytes to a range in the level file data.
     pub fn write_to_level(&mut self, range: std::ops::Range<usize>, data: &[u8]) -> Result<()> {
+        self.ensure_writable()?;
         let level = self.load_level_mut()?;
         if range.end > level.len() {
             return Err(Error::ParseError {
@@ -216,85 +489,205 @@ impl GameRoot {
                 range.len()
             )));
         }
-        level[range].copy_from_slice(data);
+        level[range.clone()].copy_from_slice(data);
+        self.write_ranges.push(range);
+        self.level_dirty = true;
         Ok(())
     }
 
     /// Save the modified level file back to the ISO.
+    ///
+    /// Like [`save_dol`](Self::save_dol), a failed write returns the error
+    /// and leaves the level file marked as unsaved.
     pub fn save_level(&mut self) -> Result<()> {
+        self.ensure_writable()?;
         if let Some(ref level_data) = self.level_data {
-            let level_path = Path::new(self.offsets.level_file);
+            let level_path = Path::new(&self.offsets.level_file);
+            self.iso_written = true;
             self.iso.write_file(level_path, level_data)?;
+            log::info!(
+                "Saved {} ({} bytes)",
+                self.offsets.level_file,
+                level_data.len()
+            );
         }
+        self.level_dirty = false;
         Ok(())
     }
 
+    /// Save the game to a new ISO at `dest`, leaving the source ISO untouched.
+    ///
+    /// The source ISO is copied to a `.tmp` sibling of `dest`, the cached DOL
+    /// and level file are written into the copy, and the copy is then renamed
+    /// over `dest`. An interrupted or failed save never leaves a half-written
+    /// `dest`. On success this `GameRoot` refers to `dest`.
+    ///
+    /// Only the cached DOL/level data is carried over. Files written directly
+    /// to the ISO (ENP/EVP) must already be in the source.
+    pub fn save_as(&mut self, dest: &Path) -> Result<()> {
+        self.ensure_writable()?;
+        write_atomically(dest, |temp_path| self.copy_with_cache(temp_path))?;
+
+        self.iso = IsoFile::open(dest)?;
+        self.dol_dirty = false;
+        self.level_dirty = false;
+        log::info!("Saved game to {}", dest.display());
+        Ok(())
+    }
+
+    /// Copy the source ISO to `path` and write the cached DOL and level
+    /// file into the copy.
+    fn copy_with_cache(&self, path: &Path) -> Result<()> {
+        if self.iso.is_dir() {
+            return Err(Error::ValidationError(format!(
+                "{} is an extracted directory; save it in place instead",
+                self.iso.path().display()
+            )));
+        }
+        std::fs::copy(self.iso.path(), path)?;
+        let iso = IsoFile::open(path)?;
+        if let Some(ref dol_data) = self.dol_data {
+            iso.write_file(Path::new("Start.dol"), dol_data)?;
+        }
+        if let Some(ref level_data) = self.level_data {
+            iso.write_file(Path::new(&self.offsets.level_file), level_data)?;
+        }
+        Ok(())
+    }
+
+    /// Randomize the game with `config` and save it as a new ISO at `dest`.
+    ///
+    /// Reads all data, runs [`randomizer::apply`](crate::randomizer::apply)
+    /// and writes the tables into a copy of the source ISO, the way
+    /// [`save_as`](Self::save_as) does. This `GameRoot` is left as it was:
+    /// it still refers to the source and its cached data isn't touched, so
+    /// the source may be opened read-only. An unset seed is picked at random
+    /// and reported in both results.
+    ///
+    /// Enemy drops live in ENP/EVP files, which `save_as` doesn't write, so
+    /// a config with `enemy_drops` set is rejected with
+    /// [`Error::FeatureUnavailable`] before anything is changed.
+    pub fn save_as_randomized(
+        &mut self,
+        config: &RandomizerConfig,
+        dest: &Path,
+    ) -> Result<(SpoilerLog, RandomizeSummary)> {
+        if config.enemy_drops.is_some() {
+            return Err(Error::FeatureUnavailable {
+                feature: "enemy_drops",
+                reason: "randomized enemy data can't be saved to a new ISO".into(),
+            });
+        }
+
+        let vanilla = self.read_game_data()?;
+        let (data, spoiler, reachability) = randomizer::randomize(&vanilla, config)?;
+
+        write_atomically(dest, |temp_path| -> Result<()> {
+            self.copy_with_cache(temp_path)?;
+            let mut copy = GameRoot::open(temp_path)?;
+            copy.set_offsets(self.offsets.clone());
+            copy.write_game_data(&data)?;
+            copy.save_dol()?;
+            copy.save_level()
+        })?;
+        log::info!("Saved randomized game to {}", dest.display());
+        let summary = RandomizeSummary::new(&spoiler, reachability);
+        log::info!("Randomized game: {}", summary);
+        Ok((spoiler, summary))
+    }
+
     // ========================================================================
     // Write methods for each entry type
     // ========================================================================
 
     /// Write accessories to the DOL.
     pub fn write_accessories(&mut self, accessories: &[Accessory]) -> Result<()> {
+        self.ensure_writable()?;
+        accessories
+            .iter()
+            .try_for_each(Accessory::validate_for_write)?;
         let data_range = self.offsets.accessory_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = Accessory::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::Accessory, accessories.len(), expected)?;
         Accessory::patch_all(accessories, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write armors to the DOL (patch approach).
     pub fn write_armors(&mut self, armors: &[Armor]) -> Result<()> {
+        self.ensure_writable()?;
+        armors.iter().try_for_each(Armor::validate_for_write)?;
         let data_range = self.offsets.armor_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = Armor::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::Armor, armors.len(), expected)?;
         Armor::patch_all(armors, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write weapons to the DOL (patch approach).
     pub fn write_weapons(&mut self, weapons: &[Weapon]) -> Result<()> {
+        self.ensure_writable()?;
+        weapons.iter().try_for_each(Weapon::validate_for_write)?;
         let data_range = self.offsets.weapon_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = Weapon::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::Weapon, weapons.len(), expected)?;
         Weapon::patch_all(weapons, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write usable items to the DOL (patch approach).
     pub fn write_usable_items(&mut self, items: &[UsableItem]) -> Result<()> {
+        self.ensure_writable()?;
+        items.iter().try_for_each(UsableItem::validate_for_write)?;
         let data_range = self.offsets.usable_item_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = UsableItem::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::UsableItem, items.len(), expected)?;
         UsableItem::patch_all(items, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write special items to the DOL (patch approach).
     pub fn write_special_items(&mut self, items: &[SpecialItem]) -> Result<()> {
+        self.ensure_writable()?;
+        items.iter().try_for_each(SpecialItem::validate_for_write)?;
         let data_range = self.offsets.special_item_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = SpecialItem::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::SpecialItem, items.len(), expected)?;
         SpecialItem::patch_all(items, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write characters to the DOL.
     pub fn write_characters(&mut self, characters: &[Character]) -> Result<()> {
+        self.ensure_writable()?;
+        characters
+            .iter()
+            .try_for_each(Character::validate_for_write)?;
         let data_range = self.offsets.character_data.clone();
         // Read original section, patch only numeric fields, write back
         let dol = self
@@ -302,186 +695,265 @@ impl GameRoot {
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = Character::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::Character, characters.len(), expected)?;
         Character::patch_all(characters, &mut buffer);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write character magic to the DOL (patch approach).
     pub fn write_character_magic(&mut self, magic: &[CharacterMagic]) -> Result<()> {
+        self.ensure_writable()?;
+        magic
+            .iter()
+            .try_for_each(CharacterMagic::validate_for_write)?;
         let data_range = self.offsets.character_magic_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = CharacterMagic::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::CharacterMagic, magic.len(), expected)?;
         CharacterMagic::patch_all(magic, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write character super moves to the DOL (patch approach).
     pub fn write_character_super_moves(&mut self, moves: &[CharacterSuperMove]) -> Result<()> {
+        self.ensure_writable()?;
+        moves
+            .iter()
+            .try_for_each(CharacterSuperMove::validate_for_write)?;
         let data_range = self.offsets.character_super_move_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = CharacterSuperMove::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::CharacterSuperMove, moves.len(), expected)?;
         CharacterSuperMove::patch_all(moves, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write shops to the DOL (patch approach).
+    ///
+    /// Every shop has [`Shop::MAX_ITEMS`] slots. A shop with more item IDs
+    /// is an error, one with fewer has the remaining slots emptied.
     pub fn write_shops(&mut self, shops: &[Shop]) -> Result<()> {
+        self.ensure_writable()?;
+        shops.iter().try_for_each(Shop::validate_for_write)?;
         let data_range = self.offsets.shop_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = Shop::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::Shop, shops.len(), expected)?;
         Shop::patch_all(shops, &mut buffer);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write treasure chests to the DOL (patch approach).
     pub fn write_treasure_chests(&mut self, chests: &[TreasureChest]) -> Result<()> {
+        self.ensure_writable()?;
+        chests
+            .iter()
+            .try_for_each(TreasureChest::validate_for_write)?;
         let data_range = self.offsets.treasure_chest_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = TreasureChest::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::TreasureChest, chests.len(), expected)?;
         TreasureChest::patch_all(chests, &mut buffer);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write crew members to the DOL (patch approach).
     pub fn write_crew_members(&mut self, members: &[CrewMember]) -> Result<()> {
+        self.ensure_writable()?;
+        members
+            .iter()
+            .try_for_each(CrewMember::validate_for_write)?;
         let data_range = self.offsets.crew_member_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = CrewMember::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::CrewMember, members.len(), expected)?;
         CrewMember::patch_all(members, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write playable ships to the DOL (patch approach).
     pub fn write_playable_ships(&mut self, ships: &[PlayableShip]) -> Result<()> {
+        self.ensure_writable()?;
+        ships
+            .iter()
+            .try_for_each(PlayableShip::validate_for_write)?;
         let data_range = self.offsets.playable_ship_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = PlayableShip::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::PlayableShip, ships.len(), expected)?;
         PlayableShip::patch_all(ships, &mut buffer);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write ship cannons to the DOL (patch approach).
     pub fn write_ship_cannons(&mut self, cannons: &[ShipCannon]) -> Result<()> {
+        self.ensure_writable()?;
+        cannons
+            .iter()
+            .try_for_each(ShipCannon::validate_for_write)?;
         let data_range = self.offsets.ship_cannon_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = ShipCannon::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::ShipCannon, cannons.len(), expected)?;
         ShipCannon::patch_all(cannons, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write ship accessories to the DOL (patch approach).
     pub fn write_ship_accessories(&mut self, accessories: &[ShipAccessory]) -> Result<()> {
+        self.ensure_writable()?;
+        accessories
+            .iter()
+            .try_for_each(ShipAccessory::validate_for_write)?;
         let data_range = self.offsets.ship_accessory_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = ShipAccessory::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::ShipAccessory, accessories.len(), expected)?;
         ShipAccessory::patch_all(accessories, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write ship items to the DOL (patch approach).
     pub fn write_ship_items(&mut self, items: &[ShipItem]) -> Result<()> {
+        self.ensure_writable()?;
+        items.iter().try_for_each(ShipItem::validate_for_write)?;
         let data_range = self.offsets.ship_item_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = ShipItem::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::ShipItem, items.len(), expected)?;
         ShipItem::patch_all(items, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write enemy ships to the DOL (patch approach).
     pub fn write_enemy_ships(&mut self, ships: &[EnemyShip]) -> Result<()> {
+        self.ensure_writable()?;
         let data_range = self.offsets.enemy_ship_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = EnemyShip::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::EnemyShip, ships.len(), expected)?;
         EnemyShip::patch_all(ships, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write enemy magic to the DOL (patch approach).
     pub fn write_enemy_magic(&mut self, magic: &[EnemyMagic]) -> Result<()> {
+        self.ensure_writable()?;
         let data_range = self.offsets.enemy_magic_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = EnemyMagic::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::EnemyMagic, magic.len(), expected)?;
         EnemyMagic::patch_all(magic, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write enemy super moves to the DOL (patch approach).
     pub fn write_enemy_super_moves(&mut self, moves: &[EnemySuperMove]) -> Result<()> {
+        self.ensure_writable()?;
         let data_range = self.offsets.enemy_super_move_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = EnemySuperMove::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::EnemySuperMove, moves.len(), expected)?;
         EnemySuperMove::patch_all(moves, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write swashbucklers to the DOL (patch approach).
     pub fn write_swashbucklers(&mut self, swashbucklers: &[Swashbuckler]) -> Result<()> {
+        self.ensure_writable()?;
+        swashbucklers
+            .iter()
+            .try_for_each(Swashbuckler::validate_for_write)?;
         let data_range = self.offsets.swashbuckler_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = Swashbuckler::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::Swashbuckler, swashbucklers.len(), expected)?;
         Swashbuckler::patch_all(swashbucklers, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write spirit curves to the DOL (patch approach).
     pub fn write_spirit_curves(&mut self, curves: &[SpiritCurve]) -> Result<()> {
+        self.ensure_writable()?;
         let data_range = self.offsets.spirit_curve_data.clone();
         let dol = self
             .dol_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
         let mut buffer = dol[data_range.clone()].to_vec();
+        let expected = SpiritCurve::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::SpiritCurve, curves.len(), expected)?;
         SpiritCurve::patch_all(curves, &mut buffer, &self.version);
         self.write_to_dol(data_range, &buffer)
     }
 
     /// Write exp boosts to the DOL (patch approach).
+    ///
+    /// Does nothing on builds without an exp boost table.
     pub fn write_exp_boosts(&mut self, boosts: &[ExpBoost]) -> Result<()> {
+        self.ensure_writable()?;
+        boosts.iter().try_for_each(ExpBoost::validate_for_write)?;
         if let Some(data_range) = self.offsets.exp_boost_data.clone() {
             let dol = self
                 .dol_data
                 .as_ref()
                 .ok_or_else(|| crate::error::Error::InvalidIso("DOL not loaded".into()))?;
             let mut buffer = dol[data_range.clone()].to_vec();
+            let expected = ExpBoost::read_all_data(&buffer, &self.version)?.len();
+            check_entry_count(EntryKind::ExpBoost, boosts.len(), expected)?;
             ExpBoost::patch_all(boosts, &mut buffer, &self.version);
             self.write_to_dol(data_range, &buffer)
         } else {
@@ -491,24 +963,36 @@ impl GameRoot {
 
     /// Write EXP curves to the level file (patch approach).
     pub fn write_exp_curves(&mut self, curves: &[ExpCurve]) -> Result<()> {
+        self.ensure_writable()?;
+        curves.iter().try_for_each(ExpCurve::validate_for_write)?;
+        self.require_level_file("exp_curves")?;
         let data_range = self.offsets.exp_curve_data.clone();
         let level = self
             .level_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("Level file not loaded".into()))?;
         let mut buffer = level[data_range.clone()].to_vec();
+        let expected = ExpCurve::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::ExpCurve, curves.len(), expected)?;
         ExpCurve::patch_all(curves, &mut buffer);
         self.write_to_level(data_range, &buffer)
     }
 
     /// Write Magic EXP curves to the level file (patch approach).
     pub fn write_magic_exp_curves(&mut self, curves: &[MagicExpCurve]) -> Result<()> {
+        self.ensure_writable()?;
+        curves
+            .iter()
+            .try_for_each(MagicExpCurve::validate_for_write)?;
+        self.require_level_file("magic_exp_curves")?;
         let data_range = self.offsets.magic_exp_curve_data.clone();
         let level = self
             .level_data
             .as_ref()
             .ok_or_else(|| crate::error::Error::InvalidIso("Level file not loaded".into()))?;
         let mut buffer = level[data_range.clone()].to_vec();
+        let expected = MagicExpCurve::read_all_data(&buffer, &self.version)?.len();
+        check_entry_count(EntryKind::MagicExpCurve, curves.len(), expected)?;
         MagicExpCurve::patch_all(curves, &mut buffer);
         self.write_to_level(data_range, &buffer)
     }
@@ -521,6 +1005,7 @@ impl GameRoot {
     pub fn read_accessories(&mut self) -> Result<Vec<Accessory>> {
         let data_range = self.offsets.accessory_data.clone();
         let dscr_range = self.offsets.accessory_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.accessory;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut accessories = Accessory::read_all_data(&data, &self.version)?;
@@ -532,7 +1017,7 @@ impl GameRoot {
                 &dscr_data,
                 dscr_range.start,
                 accessories.len(),
-                4, // 4-byte alignment for US/JP
+                dscr_align,
             )?;
 
             for (acc, (pos, size, text)) in accessories.iter_mut().zip(descriptions) {
@@ -549,6 +1034,7 @@ impl GameRoot {
     pub fn read_armors(&mut self) -> Result<Vec<Armor>> {
         let data_range = self.offsets.armor_data.clone();
         let dscr_range = self.offsets.armor_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.armor;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut armors = Armor::read_all_data(&data, &self.version)?;
@@ -557,7 +1043,7 @@ impl GameRoot {
         if dscr_range.start < dscr_range.end {
             let dscr_data = self.dol_slice(dscr_range.clone())?.to_vec();
             let descriptions =
-                read_description_strings(&dscr_data, dscr_range.start, armors.len(), 4)?;
+                read_description_strings(&dscr_data, dscr_range.start, armors.len(), dscr_align)?;
 
             for (armor, (pos, size, text)) in armors.iter_mut().zip(descriptions) {
                 armor.description_pos = pos;
@@ -573,6 +1059,7 @@ impl GameRoot {
     pub fn read_weapons(&mut self) -> Result<Vec<Weapon>> {
         let data_range = self.offsets.weapon_data.clone();
         let dscr_range = self.offsets.weapon_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.weapon;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut weapons = Weapon::read_all_data(&data, &self.version)?;
@@ -581,7 +1068,7 @@ impl GameRoot {
         if dscr_range.start < dscr_range.end {
             let dscr_data = self.dol_slice(dscr_range.clone())?.to_vec();
             let descriptions =
-                read_description_strings(&dscr_data, dscr_range.start, weapons.len(), 4)?;
+                read_description_strings(&dscr_data, dscr_range.start, weapons.len(), dscr_align)?;
 
             for (weapon, (pos, size, text)) in weapons.iter_mut().zip(descriptions) {
                 weapon.description_pos = pos;
@@ -604,6 +1091,7 @@ impl GameRoot {
     pub fn read_usable_items(&mut self) -> Result<Vec<UsableItem>> {
         let data_range = self.offsets.usable_item_data.clone();
         let dscr_range = self.offsets.usable_item_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.usable_item;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut items = UsableItem::read_all_data(&data, &self.version)?;
@@ -612,7 +1100,7 @@ impl GameRoot {
         if dscr_range.start < dscr_range.end {
             let dscr_data = self.dol_slice(dscr_range.clone())?.to_vec();
             let descriptions =
-                read_description_strings(&dscr_data, dscr_range.start, items.len(), 4)?;
+                read_description_strings(&dscr_data, dscr_range.start, items.len(), dscr_align)?;
 
             for (item, (pos, size, text)) in items.iter_mut().zip(descriptions) {
                 item.description_pos = pos;
@@ -628,6 +1116,7 @@ impl GameRoot {
     pub fn read_special_items(&mut self) -> Result<Vec<SpecialItem>> {
         let data_range = self.offsets.special_item_data.clone();
         let dscr_range = self.offsets.special_item_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.special_item;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut items = SpecialItem::read_all_data(&data, &self.version)?;
@@ -636,7 +1125,7 @@ impl GameRoot {
         if dscr_range.start < dscr_range.end {
             let dscr_data = self.dol_slice(dscr_range.clone())?.to_vec();
             let descriptions =
-                read_description_strings(&dscr_data, dscr_range.start, items.len(), 4)?;
+                read_description_strings(&dscr_data, dscr_range.start, items.len(), dscr_align)?;
 
             for (item, (pos, size, text)) in items.iter_mut().zip(descriptions) {
                 item.description_pos = pos;
@@ -659,6 +1148,7 @@ impl GameRoot {
     pub fn read_character_magic(&mut self) -> Result<Vec<CharacterMagic>> {
         let data_range = self.offsets.character_magic_data.clone();
         let dscr_range = self.offsets.character_magic_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.character_magic;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut magics = CharacterMagic::read_all_data(&data, &self.version)?;
@@ -667,7 +1157,7 @@ impl GameRoot {
         if dscr_range.start < dscr_range.end {
             let dscr_data = self.dol_slice(dscr_range.clone())?.to_vec();
             let descriptions =
-                read_description_strings(&dscr_data, dscr_range.start, magics.len(), 4)?;
+                read_description_strings(&dscr_data, dscr_range.start, magics.len(), dscr_align)?;
 
             for (magic, (pos, size, text)) in magics.iter_mut().zip(descriptions) {
                 magic.description_pos = pos;
@@ -683,6 +1173,7 @@ impl GameRoot {
     pub fn read_shops(&mut self) -> Result<Vec<Shop>> {
         let data_range = self.offsets.shop_data.clone();
         let dscr_range = self.offsets.shop_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.shop;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut shops = Shop::read_all_data(&data, &self.version)?;
@@ -691,7 +1182,7 @@ impl GameRoot {
         if dscr_range.start < dscr_range.end {
             let dscr_data = self.dol_slice(dscr_range.clone())?.to_vec();
             let descriptions =
-                read_description_strings(&dscr_data, dscr_range.start, shops.len(), 4)?;
+                read_description_strings(&dscr_data, dscr_range.start, shops.len(), dscr_align)?;
 
             for (shop, (pos, size, text)) in shops.iter_mut().zip(descriptions) {
                 shop.description_pos = pos;
@@ -734,10 +1225,27 @@ impl GameRoot {
         ))
     }
 
+    /// Get the item database, building it on first use.
+    ///
+    /// The database is kept until the DOL is written to, so repeated
+    /// lookups don't re-read every item table.
+    pub fn item_database(&mut self) -> Result<&ItemDatabase> {
+        if self.item_db.is_none() {
+            self.item_db = Some(self.build_item_database()?);
+        }
+        Ok(self.item_db.as_ref().unwrap())
+    }
+
+    /// Look up one item's name by ID (see [`ItemDatabase::name_or_default`]).
+    pub fn item_name(&mut self, id: i32) -> Result<String> {
+        Ok(self.item_database()?.name_or_default(id))
+    }
+
     /// Read all character super moves (S-Moves) from the game.
     pub fn read_character_super_moves(&mut self) -> Result<Vec<CharacterSuperMove>> {
         let data_range = self.offsets.character_super_move_data.clone();
         let dscr_range = self.offsets.character_super_move_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.character_super_move;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut entries = CharacterSuperMove::read_all_data(&data, &self.version)?;
@@ -746,7 +1254,7 @@ impl GameRoot {
         if !dscr_range.is_empty() {
             let dscr_data = self.dol_slice(dscr_range.clone())?.to_vec();
             let descriptions =
-                read_description_strings(&dscr_data, dscr_range.start, entries.len(), 4)?;
+                read_description_strings(&dscr_data, dscr_range.start, entries.len(), dscr_align)?;
 
             for (entry, (pos, size, text)) in entries.iter_mut().zip(descriptions.iter()) {
                 entry.description_pos = *pos;
@@ -762,6 +1270,7 @@ impl GameRoot {
     pub fn read_crew_members(&mut self) -> Result<Vec<CrewMember>> {
         let data_range = self.offsets.crew_member_data.clone();
         let dscr_range = self.offsets.crew_member_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.crew_member;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut entries = CrewMember::read_all_data(&data, &self.version)?;
@@ -770,7 +1279,7 @@ impl GameRoot {
         if !dscr_range.is_empty() {
             let dscr_data = self.dol_slice(dscr_range.clone())?.to_vec();
             let descriptions =
-                read_description_strings(&dscr_data, dscr_range.start, entries.len(), 4)?;
+                read_description_strings(&dscr_data, dscr_range.start, entries.len(), dscr_align)?;
 
             for (entry, (pos, size, text)) in entries.iter_mut().zip(descriptions.iter()) {
                 entry.description_pos = *pos;
@@ -793,6 +1302,7 @@ impl GameRoot {
     pub fn read_ship_cannons(&mut self) -> Result<Vec<ShipCannon>> {
         let data_range = self.offsets.ship_cannon_data.clone();
         let dscr_range = self.offsets.ship_cannon_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.ship_cannon;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut entries = ShipCannon::read_all_data(&data, &self.version)?;
@@ -801,7 +1311,7 @@ impl GameRoot {
         if !dscr_range.is_empty() {
             let dscr_data = self.dol_slice(dscr_range.clone())?.to_vec();
             let descriptions =
-                read_description_strings(&dscr_data, dscr_range.start, entries.len(), 4)?;
+                read_description_strings(&dscr_data, dscr_range.start, entries.len(), dscr_align)?;
 
             for (entry, (pos, size, text)) in entries.iter_mut().zip(descriptions.iter()) {
                 entry.description_pos = *pos;
@@ -817,6 +1327,7 @@ impl GameRoot {
     pub fn read_ship_accessories(&mut self) -> Result<Vec<ShipAccessory>> {
         let data_range = self.offsets.ship_accessory_data.clone();
         let dscr_range = self.offsets.ship_accessory_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.ship_accessory;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut entries = ShipAccessory::read_all_data(&data, &self.version)?;
@@ -825,7 +1336,7 @@ impl GameRoot {
         if !dscr_range.is_empty() {
             let dscr_data = self.dol_slice(dscr_range.clone())?.to_vec();
             let descriptions =
-                read_description_strings(&dscr_data, dscr_range.start, entries.len(), 4)?;
+                read_description_strings(&dscr_data, dscr_range.start, entries.len(), dscr_align)?;
 
             for (entry, (pos, size, text)) in entries.iter_mut().zip(descriptions.iter()) {
                 entry.description_pos = *pos;
@@ -841,6 +1352,7 @@ impl GameRoot {
     pub fn read_ship_items(&mut self) -> Result<Vec<ShipItem>> {
         let data_range = self.offsets.ship_item_data.clone();
         let dscr_range = self.offsets.ship_item_dscr.clone();
+        let dscr_align = self.offsets.dscr_alignment.ship_item;
 
         let data = self.dol_slice(data_range)?.to_vec();
         let mut entries = ShipItem::read_all_data(&data, &self.version)?;
@@ -849,7 +1361,7 @@ impl GameRoot {
         if !dscr_range.is_empty() {
             let dscr_data = self.dol_slice(dscr_range.clone())?.to_vec();
             let descriptions =
-                read_description_strings(&dscr_data, dscr_range.start, entries.len(), 4)?;
+                read_description_strings(&dscr_data, dscr_range.start, entries.len(), dscr_align)?;
 
             for (entry, (pos, size, text)) in entries.iter_mut().zip(descriptions.iter()) {
                 entry.description_pos = *pos;
@@ -882,6 +1394,14 @@ impl GameRoot {
         EnemySuperMove::read_all_data(&data, &self.version)
     }
 
+    /// Map enemy magic and super move IDs to their names, for naming the
+    /// actions of enemy tasks.
+    pub fn enemy_task_name_maps(&mut self) -> Result<(HashMap<u32, String>, HashMap<u32, String>)> {
+        let magic = self.read_enemy_magic()?;
+        let super_moves = self.read_enemy_super_moves()?;
+        Ok(super::data::enemy_task_name_maps(&magic, &super_moves))
+    }
+
     /// Read all swashbuckler ratings from the game.
     pub fn read_swashbucklers(&mut self) -> Result<Vec<Swashbuckler>> {
         let data_range = self.offsets.swashbuckler_data.clone();
@@ -908,6 +1428,7 @@ impl GameRoot {
 
     /// Read all EXP curves from the level file.
     pub fn read_exp_curves(&mut self) -> Result<Vec<ExpCurve>> {
+        self.require_level_file("exp_curves")?;
         let data_range = self.offsets.exp_curve_data.clone();
         let data = self.level_slice(data_range)?.to_vec();
         ExpCurve::read_all_data(&data, &self.version)
@@ -915,44 +1436,270 @@ impl GameRoot {
 
     /// Read all Magic EXP curves from the level file.
     pub fn read_magic_exp_curves(&mut self) -> Result<Vec<MagicExpCurve>> {
+        self.require_level_file("magic_exp_curves")?;
         let data_range = self.offsets.magic_exp_curve_data.clone();
         let data = self.level_slice(data_range)?.to_vec();
         MagicExpCurve::read_all_data(&data, &self.version)
     }
 
-    /// Read all enemies from ENP, EVP, and DAT files in the ISO.
-    /// Returns enemies and their tasks.
+    /// Restore one entry type to how it was when its file was first read
+    /// from the ISO, undoing every write to it since.
     ///
-    /// Enemy handling (matching Ruby ALX behavior):
-    /// - Collect all enemies from all files
-    /// - Post-process to handle duplicates:
-    ///   - Group by (ID, stats)
-    ///   - If group has 2+ entries from different files: one becomes `*`, keep one file-specific
-    ///   - Unique entries keep their original filter
-    pub fn read_enemies(&mut self) -> Result<(Vec<Enemy>, Vec<EnemyTask>)> {
+    /// Enemy data lives in ENP/EVP files, which aren't cached, so the enemy
+    /// kinds are [`Error::FeatureUnavailable`].
+    pub fn reset_to_vanilla(&mut self, kind: EntryKind) -> Result<()> {
+        self.ensure_writable()?;
+        let offsets = &self.offsets;
+        let dol_range = match kind {
+            EntryKind::Accessory => offsets.accessory_data.clone(),
+            EntryKind::Armor => offsets.armor_data.clone(),
+            EntryKind::Weapon => offsets.weapon_data.clone(),
+            EntryKind::UsableItem => offsets.usable_item_data.clone(),
+            EntryKind::SpecialItem => offsets.special_item_data.clone(),
+            EntryKind::Character => offsets.character_data.clone(),
+            EntryKind::CharacterMagic => offsets.character_magic_data.clone(),
+            EntryKind::CharacterSuperMove => offsets.character_super_move_data.clone(),
+            EntryKind::Shop => offsets.shop_data.clone(),
+            EntryKind::TreasureChest => offsets.treasure_chest_data.clone(),
+            EntryKind::CrewMember => offsets.crew_member_data.clone(),
+            EntryKind::PlayableShip => offsets.playable_ship_data.clone(),
+            EntryKind::ShipCannon => offsets.ship_cannon_data.clone(),
+            EntryKind::ShipAccessory => offsets.ship_accessory_data.clone(),
+            EntryKind::ShipItem => offsets.ship_item_data.clone(),
+            EntryKind::EnemyShip => offsets.enemy_ship_data.clone(),
+            EntryKind::EnemyMagic => offsets.enemy_magic_data.clone(),
+            EntryKind::EnemySuperMove => offsets.enemy_super_move_data.clone(),
+            EntryKind::Swashbuckler => offsets.swashbuckler_data.clone(),
+            EntryKind::SpiritCurve => offsets.spirit_curve_data.clone(),
+            EntryKind::ExpBoost => match offsets.exp_boost_data.clone() {
+                Some(range) => range,
+                // Not in this build, so there is nothing to restore
+                None => return Ok(()),
+            },
+            EntryKind::ExpCurve => {
+                let range = offsets.exp_curve_data.clone();
+                return self.reset_level_range(range);
+            }
+            EntryKind::MagicExpCurve => {
+                let range = offsets.magic_exp_curve_data.clone();
+                return self.reset_level_range(range);
+            }
+            EntryKind::Enemy
+            | EntryKind::EnemyTask
+            | EntryKind::EnemyEncounter
+            | EntryKind::EnemyEvent => {
+                return Err(Error::FeatureUnavailable {
+                    feature: "reset_to_vanilla",
+                    reason: format!("{} data is stored in ENP/EVP files", kind),
+                });
+            }
+        };
+        self.load_dol()?;
+        let vanilla = vanilla_slice(self.vanilla_dol.as_deref(), &dol_range, "DOL")?;
+        self.write_to_dol(dol_range, &vanilla)
+    }
+
+    /// Restore a range of the level file to how it was first read.
+    fn reset_level_range(&mut self, range: std::ops::Range<usize>) -> Result<()> {
+        self.load_level_file()?;
+        let vanilla = vanilla_slice(self.vanilla_level.as_deref(), &range, "level file")?;
+        self.write_to_level(range, &vanilla)
+    }
+
+    /// Read every entry type into a [`GameData`] snapshot.
+    pub fn read_game_data(&mut self) -> Result<GameData> {
+        let (enemies, enemy_tasks) = self.read_enemies()?;
+        Ok(GameData {
+            accessories: self.read_accessories()?,
+            armors: self.read_armors()?,
+            weapons: self.read_weapons()?,
+            weapon_effects: self.read_weapon_effects()?,
+            usable_items: self.read_usable_items()?,
+            special_items: self.read_special_items()?,
+            characters: self.read_characters()?,
+            character_magic: self.read_character_magic()?,
+            character_super_moves: self.read_character_super_moves()?,
+            shops: self.read_shops()?,
+            treasure_chests: self.read_treasure_chests()?,
+            crew_members: self.read_crew_members()?,
+            playable_ships: self.read_playable_ships()?,
+            ship_cannons: self.read_ship_cannons()?,
+            ship_accessories: self.read_ship_accessories()?,
+            ship_items: self.read_ship_items()?,
+            enemy_ships: self.read_enemy_ships()?,
+            enemy_magic: self.read_enemy_magic()?,
+            enemy_super_moves: self.read_enemy_super_moves()?,
+            swashbucklers: self.read_swashbucklers()?,
+            spirit_curves: self.read_spirit_curves()?,
+            exp_boosts: self.read_exp_boosts()?,
+            exp_curves: self.read_exp_curves()?,
+            magic_exp_curves: self.read_magic_exp_curves()?,
+            enemies,
+            enemy_tasks,
+            enemy_encounters: self.read_enemy_encounters()?,
+            enemy_events: self.read_enemy_events()?,
+        })
+    }
+
+    /// Write every DOL and level file table in `data`.
+    ///
+    /// This is the inverse of [`read_game_data`](Self::read_game_data) for
+    /// the cached DOL and level file; call [`save_dol`](Self::save_dol) and
+    /// [`save_level`](Self::save_level) (or [`save_as`](Self::save_as))
+    /// afterwards. Enemy data stored in ENP/EVP files is not written.
+    pub fn write_game_data(&mut self, data: &GameData) -> Result<()> {
+        self.write_accessories(&data.accessories)?;
+        self.write_armors(&data.armors)?;
+        self.write_weapons(&data.weapons)?;
+        self.write_usable_items(&data.usable_items)?;
+        self.write_special_items(&data.special_items)?;
+        self.write_characters(&data.characters)?;
+        self.write_character_magic(&data.character_magic)?;
+        self.write_character_super_moves(&data.character_super_moves)?;
+        self.write_shops(&data.shops)?;
+        self.write_treasure_chests(&data.treasure_chests)?;
+        self.write_crew_members(&data.crew_members)?;
+        self.write_playable_ships(&data.playable_ships)?;
+        self.write_ship_cannons(&data.ship_cannons)?;
+        self.write_ship_accessories(&data.ship_accessories)?;
+        self.write_ship_items(&data.ship_items)?;
+        self.write_enemy_ships(&data.enemy_ships)?;
+        self.write_enemy_magic(&data.enemy_magic)?;
+        self.write_enemy_super_moves(&data.enemy_super_moves)?;
+        self.write_swashbucklers(&data.swashbucklers)?;
+        self.write_spirit_curves(&data.spirit_curves)?;
+        self.write_exp_boosts(&data.exp_boosts)?;
+        self.write_exp_curves(&data.exp_curves)?;
+        self.write_magic_exp_curves(&data.magic_exp_curves)
+    }
+
+    /// Read one DOL or level file table into its field of `data`, leaving
+    /// the other fields as they are.
+    ///
+    /// Enemy data lives in ENP/EVP files, so the enemy kinds are
+    /// [`Error::FeatureUnavailable`].
+    pub fn read_kind(&mut self, kind: EntryKind, data: &mut GameData) -> Result<()> {
+        match kind {
+            EntryKind::Accessory => data.accessories = self.read_accessories()?,
+            EntryKind::Armor => data.armors = self.read_armors()?,
+            EntryKind::Weapon => data.weapons = self.read_weapons()?,
+            EntryKind::UsableItem => data.usable_items = self.read_usable_items()?,
+            EntryKind::SpecialItem => data.special_items = self.read_special_items()?,
+            EntryKind::Character => data.characters = self.read_characters()?,
+            EntryKind::CharacterMagic => data.character_magic = self.read_character_magic()?,
+            EntryKind::CharacterSuperMove => {
+                data.character_super_moves = self.read_character_super_moves()?
+            }
+            EntryKind::Shop => data.shops = self.read_shops()?,
+            EntryKind::TreasureChest => data.treasure_chests = self.read_treasure_chests()?,
+            EntryKind::CrewMember => data.crew_members = self.read_crew_members()?,
+            EntryKind::PlayableShip => data.playable_ships = self.read_playable_ships()?,
+            EntryKind::ShipCannon => data.ship_cannons = self.read_ship_cannons()?,
+            EntryKind::ShipAccessory => data.ship_accessories = self.read_ship_accessories()?,
+            EntryKind::ShipItem => data.ship_items = self.read_ship_items()?,
+            EntryKind::EnemyShip => data.enemy_ships = self.read_enemy_ships()?,
+            EntryKind::EnemyMagic => data.enemy_magic = self.read_enemy_magic()?,
+            EntryKind::EnemySuperMove => data.enemy_super_moves = self.read_enemy_super_moves()?,
+            EntryKind::Swashbuckler => data.swashbucklers = self.read_swashbucklers()?,
+            EntryKind::SpiritCurve => data.spirit_curves = self.read_spirit_curves()?,
+            EntryKind::ExpBoost => data.exp_boosts = self.read_exp_boosts()?,
+            EntryKind::ExpCurve => data.exp_curves = self.read_exp_curves()?,
+            EntryKind::MagicExpCurve => data.magic_exp_curves = self.read_magic_exp_curves()?,
+            EntryKind::Enemy
+            | EntryKind::EnemyTask
+            | EntryKind::EnemyEncounter
+            | EntryKind::EnemyEvent => {
+                return Err(Error::FeatureUnavailable {
+                    feature: "read_kind",
+                    reason: format!("{} data is stored in ENP/EVP files", kind),
+                })
+            }
+        }
+        Ok(())
+    }
+
+    /// Write one DOL or level file table from its field of `data`, like
+    /// [`write_game_data`](Self::write_game_data) does for every table.
+    ///
+    /// Enemy data lives in ENP/EVP files, so the enemy kinds are
+    /// [`Error::FeatureUnavailable`].
+    pub fn write_kind(&mut self, kind: EntryKind, data: &GameData) -> Result<()> {
+        match kind {
+            EntryKind::Accessory => self.write_accessories(&data.accessories),
+            EntryKind::Armor => self.write_armors(&data.armors),
+            EntryKind::Weapon => self.write_weapons(&data.weapons),
+            EntryKind::UsableItem => self.write_usable_items(&data.usable_items),
+            EntryKind::SpecialItem => self.write_special_items(&data.special_items),
+            EntryKind::Character => self.write_characters(&data.characters),
+            EntryKind::CharacterMagic => self.write_character_magic(&data.character_magic),
+            EntryKind::CharacterSuperMove => {
+                self.write_character_super_moves(&data.character_super_moves)
+            }
+            EntryKind::Shop => self.write_shops(&data.shops),
+            EntryKind::TreasureChest => self.write_treasure_chests(&data.treasure_chests),
+            EntryKind::CrewMember => self.write_crew_members(&data.crew_members),
+            EntryKind::PlayableShip => self.write_playable_ships(&data.playable_ships),
+            EntryKind::ShipCannon => self.write_ship_cannons(&data.ship_cannons),
+            EntryKind::ShipAccessory => self.write_ship_accessories(&data.ship_accessories),
+            EntryKind::ShipItem => self.write_ship_items(&data.ship_items),
+            EntryKind::EnemyShip => self.write_enemy_ships(&data.enemy_ships),
+            EntryKind::EnemyMagic => self.write_enemy_magic(&data.enemy_magic),
+            EntryKind::EnemySuperMove => self.write_enemy_super_moves(&data.enemy_super_moves),
+            EntryKind::Swashbuckler => self.write_swashbucklers(&data.swashbucklers),
+            EntryKind::SpiritCurve => self.write_spirit_curves(&data.spirit_curves),
+            EntryKind::ExpBoost => self.write_exp_boosts(&data.exp_boosts),
+            EntryKind::ExpCurve => self.write_exp_curves(&data.exp_curves),
+            EntryKind::MagicExpCurve => self.write_magic_exp_curves(&data.magic_exp_curves),
+            EntryKind::Enemy
+            | EntryKind::EnemyTask
+            | EntryKind::EnemyEncounter
+            | EntryKind::EnemyEvent => Err(Error::FeatureUnavailable {
+                feature: "write_kind",
+                reason: format!("{} data is stored in ENP/EVP files", kind),
+            }),
+        }
+    }
+
+    /// Read every entry type into a [`GameSnapshot`] that can be shared
+    /// between threads without holding on to this `GameRoot`.
+    pub fn snapshot(&mut self) -> Result<GameSnapshot> {
+        let data = self.read_game_data()?;
+        Ok(GameSnapshot::new(self.version.clone(), data))
+    }
+
+    /// Read the enemies and tasks of each ENP, EVP and DAT file as stored,
+    /// without merging duplicates across files.
+    ///
+    /// Every enemy's filter is the file it came from. [`read_enemies`](Self::read_enemies)
+    /// builds its deduplicated list from this.
+    pub fn read_enemies_per_file(&mut self) -> Result<(Vec<Enemy>, Vec<EnemyTask>)> {
         let mut raw_enemies: Vec<Enemy> = Vec::new();
         let mut all_tasks: Vec<EnemyTask> = Vec::new();
 
         // 1. Read EVP file (epevent.evp) - scripted battle events
-        if let Ok(evp_files) = self.iso.list_files_matching("epevent.evp") {
-            for entry in &evp_files {
-                let raw_data = self.iso.read_file_direct(entry)?;
-                let data = decompress_aklz(&raw_data)?;
+        match self.list_enemy_files(EnemyFileKind::Evp) {
+            Ok(evp_files) => {
+                for entry in &evp_files {
+                    let raw_data = self.iso.read_file_direct(entry)?;
+                    let data = decompress_aklz(&raw_data)?;
 
-                let filename = entry
-                    .path
-                    .file_name()
-                    .map(|s| s.to_string_lossy().to_string())
-                    .unwrap_or_else(|| "epevent.evp".to_string());
+                    let filename = entry
+                        .path
+                        .file_name()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "epevent.evp".to_string());
 
-                let parsed = parse_evp(&data, &filename, &self.version)?;
-                raw_enemies.extend(parsed.enemies);
-                all_tasks.extend(parsed.tasks);
+                    let parsed = parse_evp(&data, &filename, &self.version)?;
+                    log_parsed_enemies(&filename, &parsed);
+                    raw_enemies.extend(parsed.enemies);
+                    all_tasks.extend(parsed.tasks);
+                }
             }
+            Err(e) => log::warn!("Skipping EVP enemies: {}", e),
         }
 
         // 2. Read ENP files (*_ep.enp) - field encounters
-        let enp_files = self.iso.list_files_matching("_ep.enp")?;
+        let enp_files = self.list_enemy_files(EnemyFileKind::Enp)?;
 
         for entry in &enp_files {
             let raw_data = self.iso.read_file_direct(entry)?;
@@ -965,17 +1712,15 @@ impl GameRoot {
                 .unwrap_or_else(|| "*".to_string());
 
             let parsed = parse_enp(&data, &filename, &self.version)?;
+            log_parsed_enemies(&filename, &parsed);
             raw_enemies.extend(parsed.enemies);
             all_tasks.extend(parsed.tasks);
         }
 
         // 3. Read EC/EB DAT files - battle init enemies
-        let ec_files = self.iso.list_files_matching("ecinit");
-        let eb_files = self.iso.list_files_matching("ebinit");
-
-        for files in [ec_files, eb_files].into_iter().flatten() {
-            for entry in &files {
-                if entry.path.to_string_lossy().ends_with(".dat") {
+        match self.list_enemy_files(EnemyFileKind::Dat) {
+            Ok(dat_files) => {
+                for entry in &dat_files {
                     let raw_data = self.iso.read_file_direct(entry)?;
                     let data = decompress_aklz(&raw_data)?;
 
@@ -986,141 +1731,49 @@ impl GameRoot {
                         .unwrap_or_else(|| "*".to_string());
 
                     let parsed = parse_dat_file(&data, &filename, &self.version)?;
+                    log_parsed_enemies(&filename, &parsed);
                     raw_enemies.extend(parsed.enemies);
                     all_tasks.extend(parsed.tasks);
                 }
             }
+            Err(e) => log::warn!("Skipping DAT enemies: {}", e),
         }
 
-        // Post-process: deduplicate enemies (matching Ruby ALX behavior)
-        // 1. Enemies with IDENTICAL stats merge (files combined)
-        // 2. Enemies with DIFFERENT stats stay separate
-        // 3. After sorting, the entry with most files from ENP/EVP gets `*` filter
-        use std::collections::HashMap;
-
-        // Helper to compute a stats key for comparison
-        // Two enemies with same stats_key are considered identical
-        // Use a string key to avoid tuple size limits
-        fn stats_key(e: &Enemy) -> String {
-            format!(
-                "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
-                e.max_hp,
-                e.exp,
-                e.gold,
-                e.attack,
-                e.defense,
-                e.mag_def,
-                e.quick,
-                e.agile,
-                e.level,
-                e.counter,
-                e.danger,
-                e.element_id,
-                e.width,
-                e.depth,
-                e.will,
-                e.vigor,
-                e.hit,
-                e.name_jp
-            )
-        }
-
-        // Helper to get order (ENP=0, EVP=1, DAT=2)
-        fn file_order(filter: &str) -> u8 {
-            if filter == "*" || filter.ends_with(".enp") {
-                0
-            } else if filter.ends_with(".evp") {
-                1
-            } else {
-                2 // DAT files
-            }
-        }
-
-        // Group by (ID, stats_key) - enemies with same ID and stats merge
-        let mut merged: HashMap<(u32, String), Enemy> = HashMap::new();
-
-        // Track which (id, stats) combinations appeared in multiple files
-        use std::collections::HashSet;
-        let mut multi_file: HashSet<(u32, String)> = HashSet::new();
-
-        for enemy in raw_enemies {
-            let key = (enemy.id, stats_key(&enemy));
-
-            if let Some(existing) = merged.get_mut(&key) {
-                // Same ID and stats - mark as appearing in multiple files
-                multi_file.insert(key.clone());
-
-                // Keep the filter with lower order (ENP < EVP < DAT)
-                let existing_order = file_order(&existing.filter);
-                let new_order = file_order(&enemy.filter);
-
-                if new_order < existing_order {
-                    // New file has better priority - switch to it
-                    existing.filter = enemy.filter;
-                }
-            } else {
-                merged.insert(key, enemy);
-            }
-        }
-
-        // Now process merged enemies - apply filter summarization
-        let mut all_enemies: Vec<Enemy> = Vec::new();
-
-        // Group by ID to apply filter rules
-        let mut by_id: HashMap<u32, Vec<Enemy>> = HashMap::new();
-        for (_, enemy) in merged {
-            by_id.entry(enemy.id).or_default().push(enemy);
-        }
-
-        for (_id, mut enemies) in by_id {
-            if enemies.len() == 1 {
-                // Single stat variant for this ID
-                let mut enemy = enemies.remove(0);
-                let key = (enemy.id, stats_key(&enemy));
-
-                // If this enemy appeared in multiple files, mark as global
-                if multi_file.contains(&key) {
-                    let order = file_order(&enemy.filter);
-                    if order <= 2 {
-                        enemy.filter = "*".to_string();
-                    }
-                }
-                all_enemies.push(enemy);
-                continue;
-            }
-
-            // Multiple stat variants for this ID
-            // Sort by: order (asc - ENP/EVP first), then filter name
-            enemies.sort_by(|a, b| {
-                let a_order = file_order(&a.filter);
-                let b_order = file_order(&b.filter);
-                let cmp = a_order.cmp(&b_order);
-                if cmp != std::cmp::Ordering::Equal {
-                    return cmp;
-                }
-                a.filter.cmp(&b.filter)
-            });
-
-            // First enemy (lowest order = ENP/EVP) becomes global
-            let mut first = enemies.remove(0);
-            let first_order = file_order(&first.filter);
-            let first_key = (first.id, stats_key(&first));
-
-            // If first entry is from ENP/EVP (order <= 1) OR appeared in multiple files, mark as global
-            if first_order <= 1 || multi_file.contains(&first_key) {
-                first.filter = "*".to_string();
-            }
-            all_enemies.push(first);
-
-            // Remaining entries become file-specific
-            for enemy in enemies {
-                all_enemies.push(enemy);
-            }
-        }
+        Ok((raw_enemies, all_tasks))
+    }
 
+    /// Read all enemies from ENP, EVP, and DAT files in the ISO.
+    /// Returns enemies and their tasks.
+    ///
+    /// Enemy handling (matching Ruby ALX behavior):
+    /// - Collect all enemies from all files
+    /// - Post-process to handle duplicates:
+    ///   - Group by (ID, stats)
+    ///   - If group has 2+ entries from different files: one becomes `*`, keep one file-specific
+    ///   - Unique entries keep their original filter
+    pub fn read_enemies(&mut self) -> Result<(Vec<Enemy>, Vec<EnemyTask>)> {
+        let (raw_enemies, all_tasks) = self.read_enemies_per_file()?;
+        let raw_count = raw_enemies.len();
+        let all_enemies = merge_enemy_variants(raw_enemies);
+        log::debug!(
+            "Merged {} enemy entries into {} variants",
+            raw_count,
+            all_enemies.len()
+        );
         Ok((all_enemies, all_tasks))
     }
 
+    /// Read all enemies like [`read_enemies`](Self::read_enemies), sorted by
+    /// `(id, filter)`.
+    ///
+    /// Use this when the order matters and you don't sort yourself; the
+    /// global `*` variant of an ID comes before its file-specific ones.
+    pub fn read_enemies_sorted(&mut self) -> Result<(Vec<Enemy>, Vec<EnemyTask>)> {
+        let (mut enemies, tasks) = self.read_enemies()?;
+        sort_enemies(&mut enemies);
+        Ok((enemies, tasks))
+    }
+
     /// Read all enemy encounters from ENP files in the ISO.
     ///
     /// Enemy encounters define battle formations - which enemies appear together
@@ -1129,7 +1782,7 @@ impl GameRoot {
         let mut all_encounters: Vec<EnemyEncounter> = Vec::new();
 
         // Read ENP files (*_ep.enp) - field encounters
-        let enp_files = self.iso.list_files_matching("_ep.enp")?;
+        let enp_files = self.list_enemy_files(EnemyFileKind::Enp)?;
 
         for entry in &enp_files {
             let raw_data = self.iso.read_file_direct(entry)?;
@@ -1145,15 +1798,7 @@ impl GameRoot {
             all_encounters.extend(parsed.encounters);
         }
 
-        // Sort by filter (filename) then by ID
-        all_encounters.sort_by(|a, b| {
-            let filter_cmp = a.filter.cmp(&b.filter);
-            if filter_cmp != std::cmp::Ordering::Equal {
-                return filter_cmp;
-            }
-            a.id.cmp(&b.id)
-        });
-
+        sort_encounters(&mut all_encounters);
         Ok(all_encounters)
     }
 
@@ -1166,7 +1811,7 @@ impl GameRoot {
         let mut all_events: Vec<EnemyEvent> = Vec::new();
 
         // Read EVP file (epevent.evp) - scripted battle events
-        if let Ok(evp_files) = self.iso.list_files_matching("epevent.evp") {
+        if let Ok(evp_files) = self.list_enemy_files(EnemyFileKind::Evp) {
             for entry in &evp_files {
                 let raw_data = self.iso.read_file_direct(entry)?;
                 let data = decompress_aklz(&raw_data)?;
@@ -1188,6 +1833,225 @@ impl GameRoot {
         Ok(all_events)
     }
 
+    /// Count the entries of each type without reading them in full.
+    ///
+    /// Keys match the `read_*` method names (e.g. `"weapons"`, `"enemies"`).
+    /// DOL and level file tables are counted from their ID ranges and data
+    /// ranges without parsing, stopping at the end of a short data range
+    /// like `read_*` does. Enemy, task, encounter and event counts need the
+    /// ENP/EVP files to be parsed; those are cached until the files are
+    /// written or the offsets change.
+    pub fn entry_counts(&mut self) -> Result<HashMap<String, usize>> {
+        let offsets = &self.offsets;
+        let version = &self.version;
+        let mut counts: HashMap<String, usize> = [
+            (
+                "accessories",
+                table_count(
+                    &id_ranges::ACCESSORY,
+                    &offsets.accessory_data,
+                    Accessory::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "armors",
+                table_count(
+                    &id_ranges::ARMOR,
+                    &offsets.armor_data,
+                    Armor::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "weapons",
+                table_count(
+                    &id_ranges::WEAPON,
+                    &offsets.weapon_data,
+                    Weapon::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "weapon_effects",
+                offsets.weapon_effect_data.len() / WeaponEffect::ENTRY_SIZE,
+            ),
+            (
+                "usable_items",
+                table_count(
+                    &id_ranges::USABLE_ITEM,
+                    &offsets.usable_item_data,
+                    UsableItem::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "special_items",
+                table_count(
+                    &id_ranges::SPECIAL_ITEM,
+                    &offsets.special_item_data,
+                    SpecialItem::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "characters",
+                table_count(
+                    &id_ranges::CHARACTER,
+                    &offsets.character_data,
+                    Character::ENTRY_SIZE,
+                ),
+            ),
+            (
+                "character_magic",
+                table_count(
+                    &id_ranges::CHARACTER_MAGIC,
+                    &offsets.character_magic_data,
+                    CharacterMagic::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "character_super_moves",
+                table_count(
+                    &id_ranges::CHARACTER_SUPER_MOVE,
+                    &offsets.character_super_move_data,
+                    CharacterSuperMove::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "shops",
+                table_count(&id_ranges::SHOP, &offsets.shop_data, Shop::ENTRY_SIZE),
+            ),
+            (
+                "treasure_chests",
+                table_count(
+                    &id_ranges::TREASURE_CHEST,
+                    &offsets.treasure_chest_data,
+                    TreasureChest::ENTRY_SIZE,
+                ),
+            ),
+            (
+                "crew_members",
+                table_count(
+                    &id_ranges::CREW_MEMBER,
+                    &offsets.crew_member_data,
+                    CrewMember::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "playable_ships",
+                table_count(
+                    &id_ranges::PLAYABLE_SHIP,
+                    &offsets.playable_ship_data,
+                    PlayableShip::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "ship_cannons",
+                table_count(
+                    &id_ranges::SHIP_CANNON,
+                    &offsets.ship_cannon_data,
+                    ShipCannon::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "ship_accessories",
+                table_count(
+                    &id_ranges::SHIP_ACCESSORY,
+                    &offsets.ship_accessory_data,
+                    ShipAccessory::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "ship_items",
+                table_count(
+                    &id_ranges::SHIP_ITEM,
+                    &offsets.ship_item_data,
+                    ShipItem::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "enemy_ships",
+                table_count(
+                    &id_ranges::ENEMY_SHIP,
+                    &offsets.enemy_ship_data,
+                    EnemyShip::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "enemy_magic",
+                table_count(
+                    &id_ranges::ENEMY_MAGIC,
+                    &offsets.enemy_magic_data,
+                    EnemyMagic::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "enemy_super_moves",
+                table_count(
+                    &id_ranges::ENEMY_SUPER_MOVE_GC,
+                    &offsets.enemy_super_move_data,
+                    EnemySuperMove::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "swashbucklers",
+                table_count(
+                    &id_ranges::SWASHBUCKLER_GC,
+                    &offsets.swashbuckler_data,
+                    Swashbuckler::entry_size_for_version(version),
+                ),
+            ),
+            (
+                "spirit_curves",
+                table_count(
+                    &id_ranges::SPIRIT_CURVE,
+                    &offsets.spirit_curve_data,
+                    SpiritCurve::ENTRY_SIZE,
+                ),
+            ),
+            (
+                "exp_boosts",
+                offsets.exp_boost_data.as_ref().map_or(0, |data| {
+                    table_count(&id_ranges::EXP_BOOST, data, ExpBoost::ENTRY_SIZE)
+                }),
+            ),
+            (
+                "exp_curves",
+                table_count(
+                    &id_ranges::EXP_CURVE,
+                    &offsets.exp_curve_data,
+                    ExpCurve::ENTRY_SIZE,
+                ),
+            ),
+            (
+                "magic_exp_curves",
+                table_count(
+                    &id_ranges::MAGIC_EXP_CURVE,
+                    &offsets.magic_exp_curve_data,
+                    MagicExpCurve::ENTRY_SIZE,
+                ),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, count)| (name.to_string(), count))
+        .collect();
+
+        if self.enemy_counts.is_none() {
+            let (enemies, tasks) = self.read_enemies()?;
+            let encounters = self.read_enemy_encounters()?;
+            let events = self.read_enemy_events()?;
+
+            let mut enemy_counts = HashMap::new();
+            enemy_counts.insert("enemies".to_string(), enemies.len());
+            enemy_counts.insert("enemy_tasks".to_string(), tasks.len());
+            enemy_counts.insert("enemy_encounters".to_string(), encounters.len());
+            enemy_counts.insert("enemy_events".to_string(), events.len());
+            self.enemy_counts = Some(enemy_counts);
+        }
+
+        if let Some(enemy_counts) = &self.enemy_counts {
+            counts.extend(enemy_counts.iter().map(|(k, v)| (k.clone(), *v)));
+        }
+
+        Ok(counts)
+    }
+
     /// Write enemy encounters back to ENP files in the ISO.
     ///
     /// This groups encounters by their filter (filename) and writes them
@@ -1195,8 +2059,7 @@ impl GameRoot {
     ///
     /// If the original file was AKLZ compressed, the output will also be compressed.
     pub fn write_enemy_encounters(&mut self, encounters: &[EnemyEncounter]) -> Result<()> {
-        use std::collections::HashMap;
-
+        self.ensure_writable()?;
         // Group encounters by filter (filename)
         let mut by_file: HashMap<String, Vec<&EnemyEncounter>> = HashMap::new();
         for enc in encounters {
@@ -1233,6 +2096,8 @@ impl GameRoot {
 
                 // Patch encounters
                 let patched = patch_enp_encounters(&data, &sorted_encounters);
+                self.enp_cache.remove(filename);
+                self.enemy_counts = None;
 
                 // Re-compress if original was compressed
                 let output = if was_compressed {
@@ -1241,6 +2106,7 @@ impl GameRoot {
                     patched
                 };
 
+                self.iso_written = true;
                 self.iso.write_file(&entry.path, &output)?;
             }
         }
@@ -1252,77 +2118,25 @@ impl GameRoot {
     /// This stores ALL enemy variants (multiple entries per name with different stats).
     /// Use this as a fallback when an enemy isn't found in a file-specific database.
     pub fn build_global_enemy_database(&mut self) -> Result<crate::io::GlobalEnemyDatabase> {
-        use crate::io::{decompress_aklz, GlobalEnemyDatabase};
+        use crate::io::{decompress_aklz, split_enp_enemy_records, GlobalEnemyDatabase};
         use crate::lookups::enemy_names_map;
 
         let mut db = GlobalEnemyDatabase::new();
         let enemy_names = enemy_names_map();
 
         // Read ENP files (*_ep.enp) - field encounters
-        let enp_files = self.iso.list_files_matching("_ep.enp")?;
+        let enp_files = self.list_enemy_files(EnemyFileKind::Enp)?;
 
         for entry in &enp_files {
             let raw_data = self.iso.read_file_direct(entry)?;
             let data = decompress_aklz(&raw_data)?;
 
-            // Parse header to find enemy positions
-            if data.len() < 8 {
-                continue;
-            }
-
-            // Check for multi-segment signature
-            if data[0..4] == [0x00, 0x00, 0xff, 0xff] {
-                continue;
-            }
-
-            const MAX_ENEMIES: usize = 84;
-            let mut enemies: Vec<(u32, String, usize)> = Vec::new();
-
-            // Read header entries
-            for i in 0..MAX_ENEMIES {
-                let offset = i * 8;
-                if offset + 8 > data.len() {
-                    break;
-                }
-
-                let enemy_id = i32::from_be_bytes([
-                    data[offset],
-                    data[offset + 1],
-                    data[offset + 2],
-                    data[offset + 3],
-                ]);
-                let position = i32::from_be_bytes([
-                    data[offset + 4],
-                    data[offset + 5],
-                    data[offset + 6],
-                    data[offset + 7],
-                ]);
-
-                if enemy_id >= 0 && position > 0 && (position as usize) < data.len() {
-                    let name = enemy_names
-                        .get(&(enemy_id as u32))
-                        .cloned()
-                        .unwrap_or_else(|| format!("Enemy_{}", enemy_id));
-                    enemies.push((enemy_id as u32, name, position as usize));
-                }
-            }
-
-            // Sort by position to find boundaries
-            enemies.sort_by_key(|(_, _, pos)| *pos);
-
-            // Extract raw enemy data - add ALL variants to global database
-            for i in 0..enemies.len() {
-                let (id, name, pos) = &enemies[i];
-                let end = if i + 1 < enemies.len() {
-                    enemies[i + 1].2
-                } else {
-                    data.len()
-                };
-
-                if *pos < data.len() && end <= data.len() {
-                    let raw = data[*pos..end].to_vec();
-                    db.add(name.clone(), *id, raw);
-                }
+            for (id, raw) in split_enp_enemy_records(&data) {
+                let name = enemy_names
+                    .get(&id)
+                    .cloned()
+                    .unwrap_or_else(|| format!("Enemy_{}", id));
+                db.add(name, id, raw.to_vec());
             }
         }
 
@@ -1335,101 +2149,68 @@ impl GameRoot {
         &mut self,
         filename: &str,
     ) -> Result<crate::io::EnemyDatabase> {
-        use crate::io::{decompress_aklz, EnemyDatabase};
+        use crate::io::{decompress_aklz, split_enp_enemy_records, EnemyDatabase};
         use crate::lookups::enemy_names_map;
 
         let mut db = EnemyDatabase::new();
         let enemy_names = enemy_names_map();
 
-        // Find and read the specific ENP file
-        let matching = self.iso.list_files_matching(filename)?;
-
-        for entry in &matching {
-            let entry_name = entry
-                .path
-                .file_name()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_default();
-
-            if entry_name != filename {
-                continue;
-            }
-
-            let raw_data = self.iso.read_file_direct(entry)?;
-            let data = decompress_aklz(&raw_data)?;
-
-            // Parse header to find enemy positions
-            if data.len() < 8 {
-                continue;
-            }
-
-            // Check for multi-segment signature
-            if data[0..4] == [0x00, 0x00, 0xff, 0xff] {
-                continue;
-            }
-
-            const MAX_ENEMIES: usize = 84;
-            let mut enemies: Vec<(u32, String, usize)> = Vec::new();
-
-            // Read header entries
-            for i in 0..MAX_ENEMIES {
-                let offset = i * 8;
-                if offset + 8 > data.len() {
-                    break;
-                }
-
-                let enemy_id = i32::from_be_bytes([
-                    data[offset],
-                    data[offset + 1],
-                    data[offset + 2],
-                    data[offset + 3],
-                ]);
-                let position = i32::from_be_bytes([
-                    data[offset + 4],
-                    data[offset + 5],
-                    data[offset + 6],
-                    data[offset + 7],
-                ]);
-
-                if enemy_id >= 0 && position > 0 && (position as usize) < data.len() {
-                    let name = enemy_names
-                        .get(&(enemy_id as u32))
-                        .cloned()
-                        .unwrap_or_else(|| format!("Enemy_{}", enemy_id));
-                    enemies.push((enemy_id as u32, name, position as usize));
-                }
-            }
-
-            // Sort by position to find boundaries
-            enemies.sort_by_key(|(_, _, pos)| *pos);
-
-            // Extract raw enemy data
-            for i in 0..enemies.len() {
-                let (id, name, pos) = &enemies[i];
-                let end = if i + 1 < enemies.len() {
-                    enemies[i + 1].2
-                } else {
-                    data.len()
-                };
-
-                if *pos < data.len() && end <= data.len() {
-                    let raw = data[*pos..end].to_vec();
-                    db.add(name.clone(), *id, raw);
-                }
-            }
+        if !self.enp_cache.contains_key(filename) {
+            // Find and read the specific ENP file
+            let entry = self
+                .iso
+                .list_files_matching(filename)?
+                .into_iter()
+                .find(|entry| {
+                    entry
+                        .path
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy() == filename)
+                })
+                .ok_or_else(|| Error::FileNotFound {
+                    path: std::path::PathBuf::from(filename),
+                })?;
+            let data = decompress_aklz(&self.iso.read_file_direct(&entry)?)?;
+            self.enp_cache.insert(filename.to_string(), data);
+        }
 
-            return Ok(db);
+        for (id, raw) in split_enp_enemy_records(&self.enp_cache[filename]) {
+            let name = enemy_names
+                .get(&id)
+                .cloned()
+                .unwrap_or_else(|| format!("Enemy_{}", id));
+            db.add(name, id, raw.to_vec());
         }
+        Ok(db)
+    }
 
-        Err(Error::FileNotFound {
-            path: std::path::PathBuf::from(filename),
-        })
+    /// Check if the decompressed contents of an ENP file are cached.
+    ///
+    /// [`build_enemy_database_for_file`](Self::build_enemy_database_for_file)
+    /// reads each file from the ISO once; writing the file drops it again.
+    pub fn is_enp_cached(&self, filename: &str) -> bool {
+        self.enp_cache.contains_key(filename)
+    }
+
+    /// Forget all cached ENP contents, e.g. at the end of an import.
+    pub fn clear_enp_cache(&mut self) {
+        self.enp_cache.clear();
     }
 
     /// Write an ENP file back to the ISO.
-    /// Compresses with AKLZ if the original was compressed.
-    pub fn write_enp_file(&mut self, filename: &str, data: &[u8]) -> Result<()> {
-        use crate::io::{compress_aklz, is_aklz};
+    ///
+    /// `mode` picks whether to compress with AKLZ; `CompressionMode::Auto`
+    /// compresses if the original was compressed. Uncompressed data that
+    /// doesn't fit the file's slot on the disc is rejected.
+    pub fn write_enp_file(
+        &mut self,
+        filename: &str,
+        data: &[u8],
+        mode: crate::io::CompressionMode,
+    ) -> Result<()> {
+        use crate::io::{is_aklz, CompressionMode};
+
+        self.ensure_writable()?;
 
         // Find the file
         let matching = self.iso.list_files_matching(filename)?;
@@ -1444,16 +2225,24 @@ impl GameRoot {
             if entry_name == filename {
                 // Check if original was compressed
                 let raw_data = self.iso.read_file_direct(entry)?;
-                let was_compressed = is_aklz(&raw_data);
-
-                // Compress if original was compressed
-                let output = if was_compressed {
-                    compress_aklz(data)
-                } else {
-                    data.to_vec()
-                };
+                let output = mode.encode(data, is_aklz(&raw_data));
+
+                if mode == CompressionMode::None {
+                    let capacity = self.iso.file_capacity(entry)?;
+                    if output.len() as u64 > capacity {
+                        return Err(Error::ValidationError(format!(
+                            "{} is {} bytes uncompressed, but only {} bytes fit",
+                            filename,
+                            output.len(),
+                            capacity
+                        )));
+                    }
+                }
 
+                self.iso_written = true;
                 self.iso.write_file(&entry.path, &output)?;
+                self.enp_cache.remove(filename);
+                self.enemy_counts = None;
                 return Ok(());
             }
         }
@@ -1463,6 +2252,20 @@ impl GameRoot {
         })
     }
 
+    /// Rebuild `a099a_ep.enp` from its segment files in the ISO.
+    ///
+    /// All segments are gathered first; if any is missing or corrupt, an
+    /// error is returned and the baked file is left untouched. Returns the
+    /// uncompressed size of the baked file.
+    pub fn rebake_a099a(&mut self) -> Result<usize> {
+        use crate::io::{bake_a099a, CompressionMode, A099A_BAKED_FILENAME};
+
+        self.ensure_writable()?;
+        let baked = bake_a099a(|name| self.read_enp_file_raw(name))?;
+        self.write_enp_file(A099A_BAKED_FILENAME, &baked, CompressionMode::Auto)?;
+        Ok(baked.len())
+    }
+
     /// Read the raw (potentially compressed) bytes of an ENP file from the ISO.
     pub fn read_enp_file_raw(&mut self, filename: &str) -> Result<Vec<u8>> {
         // Find the file
@@ -1572,6 +2375,8 @@ impl GameRoot {
     pub fn write_evp_file(&mut self, data: &[u8]) -> Result<()> {
         use crate::io::{compress_aklz, is_aklz};
 
+        self.ensure_writable()?;
+
         const EVP_FILENAME: &str = "epevent.evp";
 
         // Find the file
@@ -1596,7 +2401,9 @@ impl GameRoot {
                     data.to_vec()
                 };
 
+                self.iso_written = true;
                 self.iso.write_file(&entry.path, &output)?;
+                self.enemy_counts = None;
                 return Ok(());
             }
         }
@@ -1607,7 +2414,368 @@ impl GameRoot {
     }
 }
 
+/// Count the entries of a table: its ID range, cut short if the data range
+/// holds fewer entries (as the `read_all_data` readers do).
+fn table_count(
+    ids: &std::ops::Range<u32>,
+    data: &std::ops::Range<usize>,
+    entry_size: usize,
+) -> usize {
+    ids.len().min(data.len() / entry_size)
+}
+
+/// Log what was parsed from one enemy file.
+fn log_parsed_enemies(filename: &str, parsed: &EnpData) {
+    log::debug!(
+        "Parsed {} enemies and {} tasks from {}",
+        parsed.enemies.len(),
+        parsed.tasks.len(),
+        filename
+    );
+}
+
+/// Key of the stats that decide if two enemy entries are the same variant.
+///
+/// Only used to order variants of one ID that can't be told apart by
+/// source file; [`same_enemy_stats`] does the comparisons.
+fn enemy_stats_key(e: &Enemy) -> String {
+    format!(
+        "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+        e.max_hp,
+        e.exp,
+        e.gold,
+        e.attack,
+        e.defense,
+        e.mag_def,
+        e.quick,
+        e.agile,
+        e.level,
+        e.counter,
+        e.danger,
+        e.element_id,
+        e.width,
+        e.depth,
+        e.will,
+        e.vigor,
+        e.hit,
+        e.name_jp
+    )
+}
+
+/// Check if two enemy entries have the stats in [`enemy_stats_key`].
+fn same_enemy_stats(a: &Enemy, b: &Enemy) -> bool {
+    a.max_hp == b.max_hp
+        && a.exp == b.exp
+        && a.gold == b.gold
+        && a.attack == b.attack
+        && a.defense == b.defense
+        && a.mag_def == b.mag_def
+        && a.quick == b.quick
+        && a.agile == b.agile
+        && a.level == b.level
+        && a.counter == b.counter
+        && a.danger == b.danger
+        && a.element_id == b.element_id
+        && a.width == b.width
+        && a.depth == b.depth
+        && a.will == b.will
+        && a.vigor == b.vigor
+        && a.hit == b.hit
+        && a.name_jp == b.name_jp
+}
+
+/// Merge the per-file enemy entries into stat variants (matching Ruby ALX
+/// behavior).
+///
+/// Entries are bucketed by ID first, so stats are only compared within the
+/// same ID:
+/// 1. Entries with identical stats merge, keeping the filter of the
+///    highest priority file (ENP < EVP < DAT)
+/// 2. Entries with different stats stay separate
+/// 3. For each ID, the first variant by file order gets the `*` filter if
+///    it comes from ENP/EVP or appeared in several files
+fn merge_enemy_variants(raw_enemies: Vec<Enemy>) -> Vec<Enemy> {
+    use std::collections::BTreeMap;
+
+    let file_order = EnemyFileKind::filter_order;
+
+    // Variants per ID, each with whether it appeared in several files.
+    // Ordered by ID so the output is identical from run to run.
+    let mut by_id: BTreeMap<u32, Vec<(Enemy, bool)>> = BTreeMap::new();
+    for enemy in raw_enemies {
+        let variants = by_id.entry(enemy.id).or_default();
+        match variants
+            .iter_mut()
+            .find(|(existing, _)| same_enemy_stats(existing, &enemy))
+        {
+            Some((existing, multi_file)) => {
+                *multi_file = true;
+                // Keep the filter with lower order (ENP < EVP < DAT)
+                if file_order(&enemy.filter) < file_order(&existing.filter) {
+                    existing.filter = enemy.filter;
+                }
+            }
+            None => variants.push((enemy, false)),
+        }
+    }
+
+    let mut all_enemies = Vec::new();
+    for (_id, mut variants) in by_id {
+        if variants.len() == 1 {
+            // Single stat variant for this ID; global if it was in several files
+            let (mut enemy, multi_file) = variants.remove(0);
+            if multi_file && file_order(&enemy.filter) <= 2 {
+                enemy.filter = "*".to_string();
+            }
+            all_enemies.push(enemy);
+            continue;
+        }
+
+        // Multiple stat variants: sort by order (ENP/EVP first), then filter
+        // name, then stats
+        variants.sort_by(|(a, _), (b, _)| {
+            file_order(&a.filter)
+                .cmp(&file_order(&b.filter))
+                .then_with(|| a.filter.cmp(&b.filter))
+                .then_with(|| enemy_stats_key(a).cmp(&enemy_stats_key(b)))
+        });
+
+        // First variant becomes global if it is from ENP/EVP or appeared in
+        // several files; the rest stay file-specific
+        let mut variants = variants.into_iter();
+        if let Some((mut first, multi_file)) = variants.next() {
+            if file_order(&first.filter) <= 1 || multi_file {
+                first.filter = "*".to_string();
+            }
+            all_enemies.push(first);
+        }
+        all_enemies.extend(variants.map(|(enemy, _)| enemy));
+    }
+    all_enemies
+}
+
+/// Sort enemies by ID, then filter.
+fn sort_enemies(enemies: &mut [Enemy]) {
+    enemies.sort_by(|a, b| (a.id, &a.filter).cmp(&(b.id, &b.filter)));
+}
+
+/// Sort encounters by filter (filename), then by ID.
+///
+/// The sort is stable, so encounters sharing a filter and ID (possible after
+/// edits) stay in the order they were parsed and exports are reproducible.
+fn sort_encounters(encounters: &mut [EnemyEncounter]) {
+    encounters.sort_by(|a, b| (&a.filter, a.id).cmp(&(&b.filter, b.id)));
+}
+
+/// Check that a table being written has exactly as many entries as the
+/// game's table.
+///
+/// Writes patch entries in place by ID, so a CSV with missing or extra rows
+/// would otherwise silently leave entries untouched or be truncated.
+fn check_entry_count(kind: EntryKind, actual: usize, expected: usize) -> Result<()> {
+    if actual != expected {
+        return Err(Error::ValidationError(format!(
+            "Expected {} {}, got {}",
+            expected,
+            kind.label(),
+            actual
+        )));
+    }
+    Ok(())
+}
+
+/// Copy `range` out of a file's vanilla bytes.
+fn vanilla_slice(
+    vanilla: Option<&[u8]>,
+    range: &std::ops::Range<usize>,
+    file: &str,
+) -> Result<Vec<u8>> {
+    vanilla
+        .and_then(|data| data.get(range.clone()))
+        .map(<[u8]>::to_vec)
+        .ok_or_else(|| Error::ParseError {
+            offset: range.start,
+            message: format!(
+                "Range {:x}..{:x} is outside the {}",
+                range.start, range.end, file
+            ),
+        })
+}
+
 #[cfg(test)]
 mod tests {
-    // Integration tests would go here
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_failed_save_keeps_unsaved_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("sys")).unwrap();
+        std::fs::create_dir_all(root.join("files")).unwrap();
+        let mut header = b"GEAE8P".to_vec();
+        header.resize(0x440, 0);
+        std::fs::write(root.join("sys/boot.bin"), header).unwrap();
+        std::fs::write(root.join("sys/main.dol"), [0u8; 16]).unwrap();
+
+        let mut game = GameRoot::open_fst_dir(root).unwrap();
+        game.load_dol().unwrap();
+        assert!(!game.has_unsaved_changes());
+        game.write_to_dol(0..4, &[1, 2, 3, 4]).unwrap();
+        assert!(game.has_unsaved_changes());
+
+        // The DOL's folder is gone, so writing it back fails
+        std::fs::remove_dir_all(root.join("sys")).unwrap();
+        assert!(game.save_dol().is_err());
+        assert!(game.has_unsaved_changes());
+
+        std::fs::create_dir_all(root.join("sys")).unwrap();
+        game.save_dol().unwrap();
+        assert!(!game.has_unsaved_changes());
+        assert_eq!(
+            std::fs::read(root.join("sys/main.dol")).unwrap()[..4],
+            [1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_revision_without_offsets_needs_a_table() {
+        let image = |revision: u8| {
+            let mut image = b"GEAE8P".to_vec();
+            image.resize(0x2440, 0);
+            image[7] = revision;
+            IsoFile::from_bytes(image).unwrap()
+        };
+        let rev0 = GameRoot::from_iso(image(0), None).unwrap();
+        assert!(matches!(
+            GameRoot::from_iso(image(1), None),
+            Err(Error::FeatureUnavailable { .. })
+        ));
+
+        let mut rev1_offsets = Offsets::gc_us();
+        rev1_offsets.weapon_data.start += 0x20;
+        rev1_offsets.weapon_data.end += 0x20;
+        let rev1 = GameRoot::from_iso(image(1), Some(rev1_offsets)).unwrap();
+        assert_eq!(rev1.version().revision, 1);
+        assert_ne!(rev1.offsets().regions(), rev0.offsets().regions());
+    }
+
+    #[test]
+    fn test_check_entry_count() {
+        assert!(check_entry_count(EntryKind::Weapon, 80, 80).is_ok());
+        match check_entry_count(EntryKind::Weapon, 79, 80) {
+            Err(Error::ValidationError(message)) => {
+                assert_eq!(message, "Expected 80 weapons, got 79")
+            }
+            other => panic!("Expected ValidationError, got {:?}", other),
+        }
+    }
+
+    /// The string-keyed merge `read_enemies` used before bucketing by ID.
+    fn reference_merge(raw_enemies: Vec<Enemy>) -> Vec<Enemy> {
+        use std::collections::{BTreeMap, HashSet};
+
+        let file_order = EnemyFileKind::filter_order;
+        let mut merged: BTreeMap<(u32, String), Enemy> = BTreeMap::new();
+        let mut multi_file: HashSet<(u32, String)> = HashSet::new();
+        for enemy in raw_enemies {
+            let key = (enemy.id, enemy_stats_key(&enemy));
+            if let Some(existing) = merged.get_mut(&key) {
+                multi_file.insert(key.clone());
+                if file_order(&enemy.filter) < file_order(&existing.filter) {
+                    existing.filter = enemy.filter;
+                }
+            } else {
+                merged.insert(key, enemy);
+            }
+        }
+
+        let mut by_id: BTreeMap<u32, Vec<Enemy>> = BTreeMap::new();
+        for (_, enemy) in merged {
+            by_id.entry(enemy.id).or_default().push(enemy);
+        }
+        let mut all_enemies = Vec::new();
+        for (_id, mut enemies) in by_id {
+            if enemies.len() == 1 {
+                let mut enemy = enemies.remove(0);
+                let key = (enemy.id, enemy_stats_key(&enemy));
+                if multi_file.contains(&key) && file_order(&enemy.filter) <= 2 {
+                    enemy.filter = "*".to_string();
+                }
+                all_enemies.push(enemy);
+                continue;
+            }
+            enemies.sort_by(|a, b| {
+                file_order(&a.filter)
+                    .cmp(&file_order(&b.filter))
+                    .then_with(|| a.filter.cmp(&b.filter))
+                    .then_with(|| enemy_stats_key(a).cmp(&enemy_stats_key(b)))
+            });
+            let mut first = enemies.remove(0);
+            let first_key = (first.id, enemy_stats_key(&first));
+            if file_order(&first.filter) <= 1 || multi_file.contains(&first_key) {
+                first.filter = "*".to_string();
+            }
+            all_enemies.push(first);
+            all_enemies.extend(enemies);
+        }
+        all_enemies
+    }
+
+    #[test]
+    fn test_merge_enemy_variants_matches_string_keyed_merge() {
+        use rand::Rng;
+
+        let version = GameVersion::from_game_id("GEAE8P").unwrap();
+        let blank = [0u8; Enemy::ENTRY_SIZE];
+        let filters = [
+            "a_ep.enp",
+            "b_ep.enp",
+            "epevent.evp",
+            "eb001.dat",
+            "ec002.dat",
+        ];
+
+        for seed in 0..20 {
+            let mut rng = crate::randomizer::rng_from_seed(seed);
+            let raw: Vec<Enemy> = (0..300)
+                .map(|_| {
+                    let filter = filters[rng.gen_range(0..filters.len())];
+                    let mut enemy = Enemy::read_one(
+                        &mut Cursor::new(&blank[..]),
+                        rng.gen_range(0..40),
+                        filter,
+                        &version,
+                    )
+                    .unwrap();
+                    // Few distinct values so many entries share stats
+                    enemy.max_hp = rng.gen_range(0..3);
+                    enemy.level = rng.gen_range(0..2);
+                    enemy
+                })
+                .collect();
+
+            let merged = merge_enemy_variants(raw.clone());
+            assert_eq!(merged, reference_merge(raw), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_sort_encounters_keeps_parse_order_for_ties() {
+        let encounter = |filter: &str, id: u32, initiative: u8| EnemyEncounter {
+            id,
+            filter: filter.to_string(),
+            initiative,
+            ..Default::default()
+        };
+        let mut encounters = vec![
+            encounter("b_ep.enp", 0, 1),
+            encounter("a_ep.enp", 1, 2),
+            encounter("a_ep.enp", 1, 3),
+            encounter("a_ep.enp", 0, 4),
+        ];
+        sort_encounters(&mut encounters);
+        let order: Vec<u8> = encounters.iter().map(|e| e.initiative).collect();
+        assert_eq!(order, vec![4, 2, 3, 1]);
+    }
 }
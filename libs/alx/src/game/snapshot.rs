@@ -0,0 +1,76 @@
+//! Immutable, shareable game data.
+
+use std::sync::Arc;
+
+use super::data::GameData;
+use super::region::GameVersion;
+
+/// A read-only [`GameData`] that can be shared across threads.
+///
+/// Take one with [`GameRoot::snapshot`](super::GameRoot::snapshot). Cloning
+/// only bumps a reference count, so callers such as the GUI can hand a clone
+/// to every reader instead of locking the ISO handle for each lookup. Edits
+/// still go through `GameRoot`; take a new snapshot afterwards.
+#[derive(Debug, Clone)]
+pub struct GameSnapshot {
+    version: GameVersion,
+    data: Arc<GameData>,
+}
+
+impl GameSnapshot {
+    /// Wrap already-read game data.
+    pub fn new(version: GameVersion, data: GameData) -> Self {
+        Self {
+            version,
+            data: Arc::new(data),
+        }
+    }
+
+    /// The version of the game the data was read from.
+    pub fn version(&self) -> &GameVersion {
+        &self.version
+    }
+
+    /// The game data.
+    pub fn data(&self) -> &GameData {
+        &self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entries::Shop;
+    use crate::game::Region;
+    use std::sync::Barrier;
+
+    #[test]
+    fn test_concurrent_reads() {
+        let data = GameData {
+            shops: vec![Shop::default(); 3],
+            ..Default::default()
+        };
+        let snapshot = GameSnapshot::new(GameVersion::from_game_id("GEAE8P").unwrap(), data);
+
+        // Both readers must be inside a read at the same time to pass the
+        // barrier, which would deadlock if reads were exclusive
+        let barrier = Barrier::new(2);
+        std::thread::scope(|s| {
+            let readers: Vec<_> = (0..2)
+                .map(|_| {
+                    let snapshot = snapshot.clone();
+                    let barrier = &barrier;
+                    s.spawn(move || {
+                        let shops = &snapshot.data().shops;
+                        barrier.wait();
+                        shops.len()
+                    })
+                })
+                .collect();
+            for reader in readers {
+                assert_eq!(reader.join().unwrap(), 3);
+            }
+        });
+        assert_eq!(snapshot.version().region, Region::Us);
+    }
+}
@@ -108,6 +108,30 @@ pub fn decompress(data: &[u8]) -> Result<Vec<u8>> {
     Ok(output)
 }
 
+/// How to compress a file when writing it back to the ISO.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompressionMode {
+    /// Compress only if the original file was AKLZ compressed.
+    #[default]
+    Auto,
+    /// Always compress.
+    Force,
+    /// Always store uncompressed.
+    None,
+}
+
+impl CompressionMode {
+    /// Encode `data` for writing over a file whose original bytes were
+    /// `was_compressed`.
+    pub fn encode(self, data: &[u8], was_compressed: bool) -> Vec<u8> {
+        match self {
+            CompressionMode::Auto if was_compressed => compress(data),
+            CompressionMode::Force => compress(data),
+            CompressionMode::Auto | CompressionMode::None => data.to_vec(),
+        }
+    }
+}
+
 /// Compress data using AKLZ format.
 ///
 /// This produces output compatible with the game's decompressor.
@@ -253,6 +277,22 @@ fn find_match(buffer: &[u8], buffer_ptr: usize, data_pos: usize, data: &[u8]) ->
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_compression_modes_roundtrip() {
+        let data: Vec<u8> = b"ENEMY DATA ".repeat(40);
+
+        for (mode, was_compressed, expect_compressed) in [
+            (CompressionMode::Auto, true, true),
+            (CompressionMode::Auto, false, false),
+            (CompressionMode::Force, false, true),
+            (CompressionMode::None, true, false),
+        ] {
+            let encoded = mode.encode(&data, was_compressed);
+            assert_eq!(is_aklz(&encoded), expect_compressed, "{:?}", mode);
+            assert_eq!(decompress(&encoded).unwrap(), data, "{:?}", mode);
+        }
+    }
+
     #[test]
     fn test_is_aklz() {
         let compressed = [
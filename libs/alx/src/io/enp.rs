@@ -31,6 +31,64 @@ struct EnemyNode {
     pos: usize,
 }
 
+/// The kinds of files enemy data is read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnemyFileKind {
+    /// Field encounters (`*_ep.enp`)
+    Enp,
+    /// Segments baked into a multi-segment ENP file (e.g. `a099a_01ep.enp`
+    /// in `a099a_ep.enp`). Their enemies are read from the baked file.
+    EnpSegment,
+    /// Scripted battle events (`epevent.evp`)
+    Evp,
+    /// Battle init enemies (`ecinit*.dat` / `ebinit*.dat`)
+    Dat,
+}
+
+impl EnemyFileKind {
+    /// Classify a file name, ignoring case. Returns `None` for files that
+    /// don't hold enemy data.
+    ///
+    /// This is the one place enemy file names are matched, so every reader
+    /// finds the same files regardless of how a dump cases them.
+    pub fn classify(file_name: &str) -> Option<Self> {
+        let lower = file_name.to_lowercase();
+        if lower.ends_with("_ep.enp") {
+            Some(EnemyFileKind::Enp)
+        } else if lower
+            .strip_suffix("ep.enp")
+            .is_some_and(|stem| stem.ends_with(|c: char| c.is_ascii_digit()))
+        {
+            Some(EnemyFileKind::EnpSegment)
+        } else if lower.ends_with("epevent.evp") {
+            Some(EnemyFileKind::Evp)
+        } else if lower.ends_with(".dat") && (lower.contains("ecinit") || lower.contains("ebinit"))
+        {
+            Some(EnemyFileKind::Dat)
+        } else {
+            None
+        }
+    }
+
+    /// Check if this is an ENP file or a segment of one.
+    pub fn is_enp(self) -> bool {
+        matches!(self, EnemyFileKind::Enp | EnemyFileKind::EnpSegment)
+    }
+
+    /// Sort order of an enemy filter by source (`*`/ENP=0, EVP=1, DAT=2),
+    /// ignoring case.
+    pub fn filter_order(filter: &str) -> u8 {
+        let lower = filter.to_lowercase();
+        if filter == "*" || lower.ends_with(".enp") {
+            0
+        } else if lower.ends_with(".evp") {
+            1
+        } else {
+            2
+        }
+    }
+}
+
 /// Parsed data from an ENP or EVP file
 #[derive(Debug, Clone, Default)]
 pub struct EnpData {
@@ -48,56 +106,66 @@ pub struct EnpData {
 pub fn parse_enp(data: &[u8], filename: &str, version: &GameVersion) -> Result<EnpData> {
     let mut result = EnpData::default();
 
-    if data.len() < 8 {
-        return Ok(result);
+    match split_enp_segments(data)? {
+        Some(segments) => {
+            for (seg_name, segment_data) in segments {
+                parse_enp_segment(segment_data, &seg_name, version, &mut result)?;
+            }
+        }
+        None if data.len() < 8 => {}
+        None => parse_enp_segment(data, filename, version, &mut result)?,
     }
 
-    let mut cursor = Cursor::new(data);
+    Ok(result)
+}
 
-    // Check for segment header
-    let mut sig = [0u8; 4];
-    for i in 0..4 {
-        sig[i] = cursor.read_u8()?;
+/// Split a multi-segment ENP (like `a099a_ep.enp`) into its named segments.
+///
+/// Returns `None` for single-segment files. Segment names have their `.bin`
+/// extension replaced with `.enp`, and segments that run past the end of
+/// the data are skipped.
+pub fn split_enp_segments(data: &[u8]) -> Result<Option<Vec<(String, &[u8])>>> {
+    if data.len() < 8 || data[0..4] != FILE_SIG {
+        return Ok(None);
     }
 
-    if sig == FILE_SIG {
-        // Multi-segment file
-        let num_segments = cursor.read_i16_be()? as usize;
-        let check = cursor.read_i16_be()?;
-        if check != -1 {
-            return Err(Error::ParseError {
-                offset: 4,
-                message: "ENP segments corrupted".to_string(),
-            });
-        }
+    let mut cursor = Cursor::new(data);
+    cursor.set_position(4);
+    let num_segments = cursor.read_i16_be()? as usize;
+    let check = cursor.read_i16_be()?;
+    if check != -1 {
+        return Err(Error::ParseError {
+            offset: 4,
+            message: "ENP segments corrupted".to_string(),
+        });
+    }
 
-        // Read segment info
-        let mut segments = Vec::new();
-        for _ in 0..num_segments {
-            let seg_name = cursor.read_string_fixed(20)?;
-            let seg_pos = cursor.read_i32_be()? as usize;
-            let seg_size = cursor.read_i32_be()? as usize;
-            let _check = cursor.read_i32_be()?;
-
-            // Convert .bin extension to .enp for GC
-            let seg_name = seg_name.replace(".bin", ".enp");
-            segments.push((seg_name, seg_pos, seg_size));
+    // Read segment info
+    let mut segments = Vec::new();
+    for _ in 0..num_segments {
+        let seg_name = cursor.read_string_fixed(20)?;
+        let seg_pos = cursor.read_i32_be()?;
+        let seg_size = cursor.read_i32_be()?;
+        let _check = cursor.read_i32_be()?;
+
+        // Negative bounds are skipped like out-of-range ones
+        let (Ok(seg_pos), Ok(seg_size)) = (usize::try_from(seg_pos), usize::try_from(seg_size))
+        else {
+            continue;
+        };
+        if seg_pos
+            .checked_add(seg_size)
+            .is_none_or(|end| end > data.len())
+        {
+            continue;
         }
 
-        // Parse each segment
-        for (seg_name, seg_pos, seg_size) in segments {
-            if seg_pos + seg_size > data.len() {
-                continue;
-            }
-            let segment_data = &data[seg_pos..seg_pos + seg_size];
-            parse_enp_segment(segment_data, &seg_name, version, &mut result)?;
-        }
-    } else {
-        // Single segment file - reset and parse
-        parse_enp_segment(data, filename, version, &mut result)?;
+        // Convert .bin extension to .enp for GC
+        let seg_name = seg_name.replace(".bin", ".enp");
+        segments.push((seg_name, &data[seg_pos..seg_pos + seg_size]));
     }
 
-    Ok(result)
+    Ok(Some(segments))
 }
 
 /// Parse a single ENP segment
@@ -206,8 +274,14 @@ fn parse_enp_segment(
                     task_id += 1;
                 }
             }
-            Err(_) => {
+            Err(e) => {
                 // Skip this enemy if we can't read it
+                log::warn!(
+                    "Skipping unreadable enemy {} in {}: {}",
+                    node.id,
+                    filename,
+                    e
+                );
                 continue;
             }
         }
@@ -445,6 +519,106 @@ pub fn patch_enp_encounters(data: &[u8], encounters: &[EnemyEncounter]) -> Vec<u
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::io::bake_enp_segments;
+
+    #[test]
+    fn test_split_enp_segments() {
+        let baked =
+            bake_enp_segments(&[("x_01ep.enp", &[1, 2, 3]), ("x_02ep.enp", &[4; 9])]).unwrap();
+        let segments = split_enp_segments(&baked).unwrap().unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                ("x_01ep.enp".to_string(), &[1u8, 2, 3][..]),
+                ("x_02ep.enp".to_string(), &[4u8; 9][..]),
+            ]
+        );
+
+        assert_eq!(split_enp_segments(&[0u8; 16]).unwrap(), None);
+    }
+
+    /// Feed random and mostly-valid buffers to every enemy file parser.
+    /// They may return errors but must never panic.
+    #[test]
+    fn test_parsers_never_panic_on_random_data() {
+        use rand::Rng;
+
+        let version = GameVersion::from_game_id("GEAE8P").unwrap();
+        let evp_size = EVP_MAX_ENEMIES * 8 + EVP_MAX_EVENTS * EnemyEvent::ENTRY_SIZE;
+        let mut rng = crate::randomizer::rng_from_seed(0x5eed);
+
+        for round in 0..300 {
+            let len = match round % 3 {
+                0 => rng.gen_range(0..64),
+                1 => rng.gen_range(0..4096),
+                _ => evp_size + rng.gen_range(0..2048),
+            };
+            let mut data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            // Make some buffers take the multi-segment path
+            if round % 2 == 0 && data.len() >= 8 {
+                data[0..4].copy_from_slice(&FILE_SIG);
+                data[6..8].copy_from_slice(&(-1i16).to_be_bytes());
+            }
+
+            let _ = parse_enp(&data, "fuzz_ep.enp", &version);
+            let _ = split_enp_segments(&data);
+            let _ = parse_evp(&data, "epevent.evp", &version);
+            let _ = parse_dat_file(&data, "ecinit001.dat", &version);
+            let _ = patch_enp_encounters(&data, &[EnemyEncounter::default()]);
+        }
+    }
+
+    #[test]
+    fn test_split_enp_segments_skips_negative_bounds() {
+        let mut data = vec![0u8; 40];
+        data[0..4].copy_from_slice(&FILE_SIG);
+        data[4..6].copy_from_slice(&1i16.to_be_bytes());
+        data[6..8].copy_from_slice(&(-1i16).to_be_bytes());
+        // Segment at offset 8 with a negative size
+        data[28..32].copy_from_slice(&8i32.to_be_bytes());
+        data[32..36].copy_from_slice(&(-1i32).to_be_bytes());
+        assert_eq!(split_enp_segments(&data).unwrap(), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_enemy_file_kind_ignores_case() {
+        assert_eq!(
+            EnemyFileKind::classify("a101b_ep.enp"),
+            Some(EnemyFileKind::Enp)
+        );
+        assert_eq!(
+            EnemyFileKind::classify("A101B_EP.ENP"),
+            Some(EnemyFileKind::Enp)
+        );
+        assert_eq!(
+            EnemyFileKind::classify("A099A_01EP.ENP"),
+            Some(EnemyFileKind::EnpSegment)
+        );
+        assert_eq!(
+            EnemyFileKind::classify("EpEvent.EVP"),
+            Some(EnemyFileKind::Evp)
+        );
+        assert_eq!(
+            EnemyFileKind::classify("ECINIT01.DAT"),
+            Some(EnemyFileKind::Dat)
+        );
+        assert_eq!(
+            EnemyFileKind::classify("ebinit.Dat"),
+            Some(EnemyFileKind::Dat)
+        );
+
+        assert_eq!(EnemyFileKind::classify("a101b_ep.enp.bak"), None);
+        assert_eq!(EnemyFileKind::classify("ecinit.txt"), None);
+        assert_eq!(EnemyFileKind::classify("other.dat"), None);
+    }
+
+    #[test]
+    fn test_filter_order_ignores_case() {
+        assert_eq!(EnemyFileKind::filter_order("*"), 0);
+        assert_eq!(EnemyFileKind::filter_order("A101B_EP.ENP"), 0);
+        assert_eq!(EnemyFileKind::filter_order("EPEVENT.EVP"), 1);
+        assert_eq!(EnemyFileKind::filter_order("ecinit01.dat"), 2);
+    }
 
     #[test]
     fn test_constants() {
@@ -3,7 +3,7 @@
 use crate::entries::{Enemy, EnemyEvent};
 use crate::error::{Error, Result};
 use crate::io::enp_dump::{EnemyDefinition, EnpDefinition, EvpDefinition};
-use crate::io::BinaryWriter;
+use crate::io::{decompress_aklz, BinaryWriter};
 use crate::items::ItemDatabase;
 use std::collections::HashMap;
 use std::io::Cursor;
@@ -46,6 +46,48 @@ impl RawEnemyData {
     }
 }
 
+/// Split a single-segment ENP into its raw enemy records.
+///
+/// Returns `(enemy_id, bytes)` for each header slot with a non-negative id
+/// and an in-bounds position, in file order. A record runs up to the next
+/// higher position (slots sharing a position share the record) or the end
+/// of the file. Short, multi-segment or malformed data yields an empty or
+/// partial list rather than panicking.
+pub fn split_enp_enemy_records(data: &[u8]) -> Vec<(u32, &[u8])> {
+    if data.len() < 8 || data.starts_with(&[0x00, 0x00, 0xff, 0xff]) {
+        return Vec::new();
+    }
+
+    let mut slots: Vec<(u32, usize)> = data
+        .chunks_exact(8)
+        .take(MAX_HEADER_ENTRIES)
+        .filter_map(|slot| {
+            let id = i32::from_be_bytes([slot[0], slot[1], slot[2], slot[3]]);
+            let position = i32::from_be_bytes([slot[4], slot[5], slot[6], slot[7]]);
+            let id = u32::try_from(id).ok()?;
+            let position = usize::try_from(position)
+                .ok()
+                .filter(|&pos| pos > 0 && pos < data.len())?;
+            Some((id, position))
+        })
+        .collect();
+
+    // Sort by position to find boundaries
+    slots.sort_by_key(|&(_, pos)| pos);
+
+    slots
+        .iter()
+        .filter_map(|&(id, start)| {
+            let end = slots
+                .iter()
+                .map(|&(_, pos)| pos)
+                .find(|&pos| pos > start)
+                .unwrap_or(data.len());
+            data.get(start..end).map(|raw| (id, raw))
+        })
+        .collect()
+}
+
 /// Enemy database for looking up raw enemy data by name.
 /// Stores a single entry per name (for file-specific lookups).
 #[derive(Debug, Clone, Default)]
@@ -160,6 +202,59 @@ impl GlobalEnemyDatabase {
     }
 }
 
+/// Enemies referenced by an ENP definition's encounters that can't be
+/// resolved from either enemy database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingEnemyReferences {
+    /// ENP filename the definition came from
+    pub filename: String,
+    /// Referenced enemy names that were found in neither database
+    pub enemies: Vec<String>,
+}
+
+impl std::fmt::Display for MissingEnemyReferences {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: encounters reference enemies not found in the file or global database: {}",
+            self.filename,
+            self.enemies.join(", ")
+        )
+    }
+}
+
+/// Check that every enemy referenced by the definition's encounters exists
+/// in the file-specific database or, failing that, the global database.
+///
+/// Returns `None` when all references resolve. Run this before `build_enp`
+/// to get a report naming the dangling enemies instead of a build error.
+pub fn find_missing_enemy_references(
+    def: &EnpDefinition,
+    db: &EnemyDatabase,
+    global_db: Option<&GlobalEnemyDatabase>,
+) -> Option<MissingEnemyReferences> {
+    let mut missing: Vec<String> = Vec::new();
+
+    for name in def.encounters.iter().flat_map(|e| e.enemies.iter()) {
+        let found = db.get(name).is_some()
+            || global_db
+                .map(|g| g.get_any(name).is_some())
+                .unwrap_or(false);
+        if !found && !missing.contains(name) {
+            missing.push(name.clone());
+        }
+    }
+
+    if missing.is_empty() {
+        None
+    } else {
+        Some(MissingEnemyReferences {
+            filename: def.filename.clone(),
+            enemies: missing,
+        })
+    }
+}
+
 /// Patch raw enemy data with values from an EnemyDefinition
 /// This updates stats and item drops while preserving AI tasks
 fn patch_enemy_data(raw: &[u8], def: &EnemyDefinition, item_db: &ItemDatabase) -> Vec<u8> {
@@ -526,6 +621,36 @@ pub const A099A_SEGMENTS: [&str; 13] = [
 /// The baked filename for a099a
 pub const A099A_BAKED_FILENAME: &str = "a099a_ep.enp";
 
+/// Gather all [`A099A_SEGMENTS`] and bake them into `a099a_ep.enp`.
+///
+/// `read_segment` returns a segment's raw (possibly compressed) bytes. Every
+/// segment is read and decompressed before anything is baked, so a missing
+/// or corrupt segment returns an error naming it and no partial file.
+pub fn bake_a099a<F>(mut read_segment: F) -> Result<Vec<u8>>
+where
+    F: FnMut(&str) -> Result<Vec<u8>>,
+{
+    let segment_error = |seg_name: &str, e: Error| {
+        Error::ValidationError(format!(
+            "Can't rebake {}: segment {} unavailable ({})",
+            A099A_BAKED_FILENAME, seg_name, e
+        ))
+    };
+
+    let mut segments: Vec<(&str, Vec<u8>)> = Vec::with_capacity(A099A_SEGMENTS.len());
+    for seg_name in A099A_SEGMENTS {
+        let raw = read_segment(seg_name).map_err(|e| segment_error(seg_name, e))?;
+        let data = decompress_aklz(&raw).map_err(|e| segment_error(seg_name, e))?;
+        segments.push((seg_name, data));
+    }
+
+    let segment_refs: Vec<(&str, &[u8])> = segments
+        .iter()
+        .map(|(name, data)| (*name, data.as_slice()))
+        .collect();
+    bake_enp_segments(&segment_refs)
+}
+
 // ============================================================================
 // EVP Builder
 // ============================================================================
@@ -779,6 +904,26 @@ fn escape_condition_to_id(name: &str) -> i8 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_bake_a099a_missing_segment_aborts() {
+        let mut reads = 0;
+        let result = bake_a099a(|name| {
+            reads += 1;
+            if name == "a099a_07ep.enp" {
+                return Err(Error::FileNotFound { path: name.into() });
+            }
+            Ok(vec![0xAB; 16])
+        });
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("a099a_07ep.enp"), "{}", err);
+        // Stops at the first missing segment
+        assert_eq!(reads, 7);
+
+        let baked = bake_a099a(|_| Ok(vec![0xAB; 16])).unwrap();
+        let segments = crate::io::split_enp_segments(&baked).unwrap().unwrap();
+        assert_eq!(segments.len(), A099A_SEGMENTS.len());
+    }
+
     #[test]
     fn test_bake_enp_segments() {
         // Create dummy segment data with sizes that test alignment
@@ -862,4 +1007,68 @@ mod tests {
         assert!(db.get("Soldier").is_some());
         assert!(db.get("Unknown").is_none());
     }
+
+    #[test]
+    fn test_find_missing_enemy_references() {
+        use crate::io::enp_dump::EncounterDefinition;
+
+        let mut db = EnemyDatabase::new();
+        db.add("Soldier".to_string(), 0, vec![0u8; 200]);
+        let mut global_db = GlobalEnemyDatabase::new();
+        global_db.add("Guard".to_string(), 1, vec![0u8; 200]);
+
+        let mut def = EnpDefinition {
+            filename: "a101b_ep.enp".to_string(),
+            enemies: Vec::new(),
+            encounters: vec![EncounterDefinition {
+                initiative: 0,
+                magic_exp: 0,
+                enemies: vec!["Soldier".to_string(), "Guard".to_string()],
+            }],
+            meta: Default::default(),
+        };
+        assert!(find_missing_enemy_references(&def, &db, Some(&global_db)).is_none());
+
+        def.encounters[0].enemies.push("Phantom".to_string());
+        let missing = find_missing_enemy_references(&def, &db, Some(&global_db)).unwrap();
+        assert_eq!(missing.filename, "a101b_ep.enp");
+        assert_eq!(missing.enemies, vec!["Phantom".to_string()]);
+
+        let warning = missing.to_string();
+        assert!(warning.contains("a101b_ep.enp"));
+        assert!(warning.contains("Phantom"));
+
+        // Without the global DB, the stolen enemy is dangling too
+        let missing = find_missing_enemy_references(&def, &db, None).unwrap();
+        assert_eq!(
+            missing.enemies,
+            vec!["Guard".to_string(), "Phantom".to_string()]
+        );
+    }
+
+    fn enp_header(slots: &[(i32, i32)]) -> Vec<u8> {
+        slots
+            .iter()
+            .flat_map(|(id, pos)| id.to_be_bytes().into_iter().chain(pos.to_be_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_enp_enemy_records_corrupt_header() {
+        // Too short, or multi-segment: nothing to split
+        assert!(split_enp_enemy_records(&[0u8; 5]).is_empty());
+        assert!(split_enp_enemy_records(&enp_header(&[(0x0000_ffff, 8)])).is_empty());
+
+        // Out-of-range and negative slots are dropped, and slots sharing
+        // a position both get the whole record
+        let data = enp_header(&[(1, i32::MAX), (-1, 16), (2, 40), (3, 24), (4, 24)]);
+        assert_eq!(data.len(), 40);
+        let records = split_enp_enemy_records(&data);
+        assert_eq!(records, vec![(3, &data[24..40]), (4, &data[24..40])]);
+
+        // A record truncated by the end of the file is partial, not a panic
+        let mut data = enp_header(&[(7, 8)]);
+        data.extend([0xAA; 4]);
+        assert_eq!(split_enp_enemy_records(&data), vec![(7, &data[8..12])]);
+    }
 }
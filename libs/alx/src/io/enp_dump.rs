@@ -1,9 +1,9 @@
 //! ENP and EVP file structure dumper for debugging and analysis.
 
 use crate::entries::{Enemy, EnemyEvent};
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::game::region::GameVersion;
-use crate::io::BinaryReader;
+use crate::io::{split_enp_segments, BinaryReader};
 use crate::items::ItemDatabase;
 use crate::lookups::enemy_names_map;
 use serde::{Deserialize, Serialize};
@@ -23,6 +23,22 @@ pub struct EnpDefinition {
     pub enemies: Vec<EnemyDefinition>,
     /// Battle encounters
     pub encounters: Vec<EncounterDefinition>,
+    /// Counts and sizes at dump time (ignored on import)
+    #[serde(default)]
+    pub meta: EnpMeta,
+}
+
+/// Summary of a dumped ENP file, so editors don't have to count.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnpMeta {
+    pub enemy_count: usize,
+    pub encounter_count: usize,
+    /// Segments in the source file (1 unless it is multi-segment)
+    pub segment_count: usize,
+    /// Size of the file in the ISO, if known
+    pub compressed_size: Option<usize>,
+    /// Size of this definition's (segment) data after decompression
+    pub decompressed_size: usize,
 }
 
 /// Simplified enemy definition
@@ -75,6 +91,42 @@ pub struct EncounterDefinition {
     pub enemies: Vec<String>,
 }
 
+/// Encounter-only ENP definition, for editing battles without the enemy
+/// stats and drops.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterListDefinition {
+    /// Source filename
+    pub filename: String,
+    /// Battle encounters
+    pub encounters: Vec<EncounterDefinition>,
+}
+
+impl EnpDefinition {
+    /// Get just the encounters of this file.
+    pub fn encounters_only(&self) -> EncounterListDefinition {
+        EncounterListDefinition {
+            filename: self.filename.clone(),
+            encounters: self.encounters.clone(),
+        }
+    }
+
+    /// Replace this file's encounters with the ones in `list`.
+    ///
+    /// The enemies are kept, so the result can be passed to
+    /// [`build_enp`](crate::io::build_enp) like a full definition.
+    pub fn apply_encounters(&mut self, list: EncounterListDefinition) -> Result<()> {
+        if list.filename != self.filename {
+            return Err(Error::ValidationError(format!(
+                "Encounters are for {}, not {}",
+                list.filename, self.filename
+            )));
+        }
+        self.encounters = list.encounters;
+        self.meta.encounter_count = self.encounters.len();
+        Ok(())
+    }
+}
+
 // ============================================================================
 // Full Debug Schema (for analysis)
 // ============================================================================
@@ -121,6 +173,81 @@ pub struct EnemyDump {
 const MAX_ENEMIES: usize = 84;
 const ELEMENTS: [&str; 6] = ["Green", "Red", "Purple", "Blue", "Yellow", "Silver"];
 
+// ============================================================================
+// Dump Statistics
+// ============================================================================
+
+/// Compression statistics for a single dumped ENP file
+#[derive(Debug, Clone, Serialize)]
+pub struct EnpFileStats {
+    pub filename: String,
+    pub compressed_size: usize,
+    pub decompressed_size: usize,
+}
+
+impl EnpFileStats {
+    /// Compressed size as a fraction of the decompressed size.
+    pub fn ratio(&self) -> f64 {
+        if self.decompressed_size == 0 {
+            0.0
+        } else {
+            self.compressed_size as f64 / self.decompressed_size as f64
+        }
+    }
+}
+
+/// Compression statistics collected while dumping ENP files
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EnpDumpStats {
+    pub files: Vec<EnpFileStats>,
+}
+
+impl EnpDumpStats {
+    /// Create empty stats.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the sizes of one dumped file.
+    pub fn record(&mut self, filename: &str, compressed_size: usize, decompressed_size: usize) {
+        self.files.push(EnpFileStats {
+            filename: filename.to_string(),
+            compressed_size,
+            decompressed_size,
+        });
+    }
+
+    /// Total compressed bytes across all recorded files.
+    pub fn total_compressed(&self) -> usize {
+        self.files.iter().map(|f| f.compressed_size).sum()
+    }
+
+    /// Total decompressed bytes across all recorded files.
+    pub fn total_decompressed(&self) -> usize {
+        self.files.iter().map(|f| f.decompressed_size).sum()
+    }
+
+    /// Overall compressed/decompressed ratio.
+    pub fn ratio(&self) -> f64 {
+        let total = self.total_decompressed();
+        if total == 0 {
+            0.0
+        } else {
+            self.total_compressed() as f64 / total as f64
+        }
+    }
+
+    /// Get the number of recorded files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Check if no files were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
 // ============================================================================
 // Export Functions
 // ============================================================================
@@ -133,6 +260,13 @@ pub fn dump_enp_editable(
     item_db: &ItemDatabase,
 ) -> Result<EnpDefinition> {
     let dump = dump_enp(data, filename, version, item_db)?;
+    let meta = EnpMeta {
+        enemy_count: dump.enemies.len(),
+        encounter_count: dump.encounters.len(),
+        segment_count: 1,
+        compressed_size: None,
+        decompressed_size: data.len(),
+    };
 
     Ok(EnpDefinition {
         filename: dump.filename,
@@ -155,9 +289,37 @@ pub fn dump_enp_editable(
                 enemies: e.enemies,
             })
             .collect(),
+        meta,
     })
 }
 
+/// Dump an ENP file to one editable definition per segment.
+///
+/// Multi-segment files (like `a099a_ep.enp`) give one definition per
+/// segment, named after the segment. Single-segment files give one
+/// definition named `filename`.
+pub fn dump_enp_segments_editable(
+    data: &[u8],
+    filename: &str,
+    version: &GameVersion,
+    item_db: &ItemDatabase,
+) -> Result<Vec<EnpDefinition>> {
+    match split_enp_segments(data)? {
+        Some(segments) => {
+            let segment_count = segments.len();
+            segments
+                .into_iter()
+                .map(|(seg_name, segment_data)| {
+                    let mut def = dump_enp_editable(segment_data, &seg_name, version, item_db)?;
+                    def.meta.segment_count = segment_count;
+                    Ok(def)
+                })
+                .collect()
+        }
+        None => Ok(vec![dump_enp_editable(data, filename, version, item_db)?]),
+    }
+}
+
 /// Dump an ENP file to a full debug format
 pub fn dump_enp(
     data: &[u8],
@@ -734,3 +896,49 @@ pub fn dump_evp(
 
     Ok(dump)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_encounters_checks_filename() {
+        let mut def = EnpDefinition {
+            filename: "a101b_ep.enp".to_string(),
+            enemies: Vec::new(),
+            encounters: Vec::new(),
+            meta: EnpMeta::default(),
+        };
+        let mut list = def.encounters_only();
+        list.encounters.push(EncounterDefinition {
+            initiative: 1,
+            magic_exp: 2,
+            enemies: vec!["Looper".to_string()],
+        });
+
+        def.apply_encounters(list.clone()).unwrap();
+        assert_eq!(def.encounters.len(), 1);
+
+        list.filename = "a102a_ep.enp".to_string();
+        assert!(matches!(
+            def.apply_encounters(list),
+            Err(Error::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_enp_dump_stats_totals() {
+        let mut stats = EnpDumpStats::new();
+        assert!(stats.is_empty());
+        assert_eq!(stats.ratio(), 0.0);
+
+        stats.record("a.enp", 100, 400);
+        stats.record("b.enp", 300, 400);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats.total_compressed(), 400);
+        assert_eq!(stats.total_decompressed(), 800);
+        assert_eq!(stats.files[0].ratio(), 0.25);
+        assert_eq!(stats.ratio(), 0.5);
+    }
+}
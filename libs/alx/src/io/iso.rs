@@ -4,27 +4,271 @@ use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 
 /// Header offsets in the ISO.
 const HEADER_INFO_OFFSET: u64 = 0x420;
 
-/// Represents an opened GameCube ISO file.
+/// Minimum size of a disc image: boot header, BI2 and the header info block.
+const MIN_ISO_SIZE: u64 = 0x2440;
+
+/// Represents an opened GameCube ISO file, or a disc extracted to a directory.
 pub struct IsoFile {
     path: PathBuf,
-    file: File,
+    source: Source,
+    read_only: bool,
+}
+
+/// Where an [`IsoFile`] reads its data from.
+enum Source {
+    /// A disc image
+    Image(File),
+    /// An extracted disc
+    Dir(FstDir),
+    /// A disc image held in memory
+    Memory(Vec<u8>),
+}
+
+/// Locations of the pieces of a disc extracted to a directory.
+#[derive(Debug, Clone)]
+struct FstDir {
+    /// Root of the game's file system
+    files: PathBuf,
+    /// Boot header (starts with the game ID)
+    header: PathBuf,
+    /// Main executable
+    dol: PathBuf,
+}
+
+impl FstDir {
+    /// Detect a gc_fst/GCRebuilder (`&&systemdata/`) or Dolphin
+    /// (`sys/` + `files/`) extraction under `root`.
+    fn detect(root: &Path) -> Result<Self> {
+        let systemdata = root.join("&&systemdata");
+        let sys = root.join("sys");
+        let layouts = [
+            (
+                root.to_path_buf(),
+                systemdata.join("ISO.hdr"),
+                systemdata.join("Start.dol"),
+            ),
+            (
+                root.join("files"),
+                sys.join("boot.bin"),
+                sys.join("main.dol"),
+            ),
+        ];
+
+        layouts
+            .into_iter()
+            .find(|(_, header, dol)| header.is_file() && dol.is_file())
+            .map(|(files, header, dol)| Self { files, header, dol })
+            .ok_or_else(|| {
+                Error::InvalidIso(format!(
+                    "{} is not an extracted disc (no &&systemdata/ or sys/ with a boot header and DOL)",
+                    root.display()
+                ))
+            })
+    }
+
+    /// Map a path inside the disc to the file on disk.
+    fn resolve(&self, iso_path: &Path) -> PathBuf {
+        if iso_path == Path::new("Start.dol") {
+            self.dol.clone()
+        } else {
+            self.files.join(iso_path)
+        }
+    }
+
+    /// List the game's files, skipping the system files.
+    fn list_files(&self) -> Result<Vec<IsoFileEntry>> {
+        let mut files = Vec::new();
+        let mut dirs = vec![PathBuf::new()];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(self.files.join(&dir))? {
+                let entry = entry?;
+                let path = dir.join(entry.file_name());
+                let file_type = entry.file_type()?;
+                if file_type.is_dir() {
+                    if path != Path::new("&&systemdata") {
+                        dirs.push(path);
+                    }
+                } else if file_type.is_file() {
+                    files.push(IsoFileEntry {
+                        path,
+                        offset: 0,
+                        size: u32::try_from(entry.metadata()?.len()).unwrap_or(u32::MAX),
+                    });
+                }
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
 }
 
 impl IsoFile {
     /// Open an ISO file for reading.
+    ///
+    /// The image is checked up front so that a partially-downloaded or
+    /// truncated file fails here with [`Error::TruncatedIso`] instead of
+    /// part-way through a later read.
     pub fn open(path: &Path) -> Result<Self> {
+        Self::open_with_mode(path, false)
+    }
+
+    /// Open an ISO file that refuses all writes with [`Error::ReadOnly`].
+    pub fn open_readonly(path: &Path) -> Result<Self> {
+        Self::open_with_mode(path, true)
+    }
+
+    fn open_with_mode(path: &Path, read_only: bool) -> Result<Self> {
         let file = File::open(path)?;
+        let mut iso = Self {
+            path: path.canonicalize().unwrap_or_else(|_| path.to_path_buf()),
+            source: Source::Image(file),
+            read_only,
+        };
+        iso.check_size()?;
+        Ok(iso)
+    }
+
+    /// Open a disc that has been extracted to a directory.
+    ///
+    /// Both the gc_fst/GCRebuilder layout (`&&systemdata/ISO.hdr` and
+    /// `&&systemdata/Start.dol` next to the game files) and the Dolphin
+    /// layout (`sys/boot.bin`, `sys/main.dol` and `files/`) are recognised.
+    /// Writes go straight to the files on disk.
+    pub fn open_dir(path: &Path) -> Result<Self> {
+        let dir = FstDir::detect(path)?;
         Ok(Self {
             path: path.canonicalize().unwrap_or_else(|_| path.to_path_buf()),
-            file,
+            source: Source::Dir(dir),
+            read_only: false,
         })
     }
 
+    /// Open a disc image that is already in memory.
+    ///
+    /// The image is checked the same way as [`open`](Self::open). Reads work
+    /// as for a file on disk, without touching the file system; the image is
+    /// read-only, so writes fail with [`Error::ReadOnly`].
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        let mut iso = Self {
+            path: PathBuf::from("<memory>"),
+            source: Source::Memory(data),
+            read_only: true,
+        };
+        iso.check_size()?;
+        Ok(iso)
+    }
+
+    /// Check if this ISO was opened read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Check if this is an extracted directory rather than a disc image.
+    pub fn is_dir(&self) -> bool {
+        matches!(self.source, Source::Dir(_))
+    }
+
+    /// Get the disc image file, failing for an extracted directory.
+    fn image(&mut self) -> Result<&mut File> {
+        match &mut self.source {
+            Source::Image(file) => Ok(file),
+            Source::Dir(_) | Source::Memory(_) => Err(Error::ValidationError(format!(
+                "{} is not a disc image file",
+                self.path.display()
+            ))),
+        }
+    }
+
+    /// Get the size of the disc image in bytes.
+    fn image_len(&mut self) -> Result<u64> {
+        match &self.source {
+            Source::Memory(data) => Ok(data.len() as u64),
+            _ => Ok(self.image()?.metadata()?.len()),
+        }
+    }
+
+    /// Read `len` bytes of the boot header starting at `offset`.
+    fn read_header(&mut self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        match &self.source {
+            Source::Image(_) | Source::Memory(_) => self.read_bytes_at(offset, len),
+            Source::Dir(dir) => {
+                let header = std::fs::read(&dir.header)?;
+                let start = offset as usize;
+                header
+                    .get(start..start.saturating_add(len))
+                    .map(<[u8]>::to_vec)
+                    .ok_or_else(|| {
+                        Error::InvalidIso(format!(
+                            "Boot header {} is too short",
+                            dir.header.display()
+                        ))
+                    })
+            }
+        }
+    }
+
+    /// Verify that the file is large enough to hold the header, the FST and
+    /// every file the FST points to.
+    fn check_size(&mut self) -> Result<()> {
+        let actual = self.image_len()?;
+        if actual < MIN_ISO_SIZE {
+            return Err(Error::TruncatedIso {
+                expected_min: MIN_ISO_SIZE,
+                actual,
+            });
+        }
+
+        let buf = self.read_bytes_at(HEADER_INFO_OFFSET, 12)?;
+        let fst_offset = u32::from_be_bytes(buf[4..8].try_into().unwrap()) as u64;
+        let fs_size = u32::from_be_bytes(buf[8..12].try_into().unwrap()) as u64;
+
+        let fst_end = fst_offset + fs_size;
+        if fst_end > actual {
+            return Err(Error::TruncatedIso {
+                expected_min: fst_end,
+                actual,
+            });
+        }
+
+        // Walk the FST entry table (names are not needed) to find the end of
+        // the last file on the disc.
+        let fst = self.read_bytes_at(fst_offset, fs_size as usize)?;
+        if fst.len() < 0xC {
+            return Err(Error::InvalidIso("FST is too small".into()));
+        }
+        let entry_count = u32::from_be_bytes(fst[8..12].try_into().unwrap()) as usize;
+        if entry_count.saturating_mul(0xC) > fst.len() {
+            return Err(Error::InvalidIso(format!(
+                "FST declares {} entries but is only {} bytes",
+                entry_count,
+                fst.len()
+            )));
+        }
+
+        let mut data_end = fst_end;
+        for entry in fst[..entry_count * 0xC].chunks_exact(0xC).skip(1) {
+            if entry[0] == 0 {
+                let offset = u32::from_be_bytes(entry[4..8].try_into().unwrap()) as u64;
+                let size = u32::from_be_bytes(entry[8..12].try_into().unwrap()) as u64;
+                data_end = data_end.max(offset + size);
+            }
+        }
+
+        if data_end > actual {
+            return Err(Error::TruncatedIso {
+                expected_min: data_end,
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Get the path to the ISO file.
     pub fn path(&self) -> &Path {
         &self.path
@@ -32,41 +276,42 @@ impl IsoFile {
 
     /// Read the DOL (executable) offset from the ISO header.
     pub fn read_dol_offset(&mut self) -> Result<u32> {
-        self.file.seek(SeekFrom::Start(HEADER_INFO_OFFSET))?;
-        let mut buf = [0u8; 4];
-        self.file.read_exact(&mut buf)?;
-        Ok(u32::from_be_bytes(buf))
+        let buf = self.read_bytes_at(HEADER_INFO_OFFSET, 4)?;
+        Ok(u32::from_be_bytes(buf.try_into().unwrap()))
     }
 
     /// Read the FST (file system table) offset from the ISO header.
     pub fn read_fst_offset(&mut self) -> Result<u32> {
-        self.file.seek(SeekFrom::Start(HEADER_INFO_OFFSET + 4))?;
-        let mut buf = [0u8; 4];
-        self.file.read_exact(&mut buf)?;
-        Ok(u32::from_be_bytes(buf))
+        let buf = self.read_bytes_at(HEADER_INFO_OFFSET + 4, 4)?;
+        Ok(u32::from_be_bytes(buf.try_into().unwrap()))
     }
 
     /// Read raw bytes from a specific offset in the ISO.
     pub fn read_bytes_at(&mut self, offset: u64, len: usize) -> Result<Vec<u8>> {
-        self.file.seek(SeekFrom::Start(offset))?;
+        if let Source::Memory(data) = &self.source {
+            return memory_slice(data, offset, len).map(<[u8]>::to_vec);
+        }
+        let file = self.image()?;
+        file.seek(SeekFrom::Start(offset))?;
         let mut buf = vec![0u8; len];
-        self.file.read_exact(&mut buf)?;
+        file.read_exact(&mut buf)?;
         Ok(buf)
     }
 
     /// Read the game ID from the ISO header (first 6 bytes).
     pub fn read_game_id(&mut self) -> Result<String> {
-        self.file.seek(SeekFrom::Start(0))?;
-        let mut buf = [0u8; 6];
-        self.file.read_exact(&mut buf)?;
+        let buf = self.read_header(0, 6)?;
         Ok(String::from_utf8_lossy(&buf).to_string())
     }
 
+    /// Read the disc revision from the ISO header (byte 7).
+    pub fn read_disc_revision(&mut self) -> Result<u8> {
+        Ok(self.read_header(7, 1)?[0])
+    }
+
     /// Read the game title from the ISO header.
     pub fn read_game_title(&mut self) -> Result<String> {
-        self.file.seek(SeekFrom::Start(0x20))?;
-        let mut buf = [0u8; 0x3E0];
-        self.file.read_exact(&mut buf)?;
+        let buf = self.read_header(0x20, 0x3E0)?;
 
         // Find null terminator
         let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
@@ -80,6 +325,18 @@ impl IsoFile {
             std::fs::create_dir_all(parent)?;
         }
 
+        match &self.source {
+            Source::Dir(dir) => {
+                std::fs::copy(dir.resolve(iso_path), dest_path)?;
+                return Ok(());
+            }
+            Source::Memory(data) => {
+                std::fs::write(dest_path, memory_file(data, iso_path)?)?;
+                return Ok(());
+            }
+            Source::Image(_) => {}
+        }
+
         let files = [(iso_path, dest_path)];
         gc_fst::read_iso_files(self.path(), &files)?;
         Ok(())
@@ -87,6 +344,13 @@ impl IsoFile {
 
     /// Replace a file in the ISO with new content.
     pub fn replace_file(&self, iso_path: &Path, source_path: &Path) -> Result<()> {
+        if self.read_only {
+            return Err(Error::ReadOnly);
+        }
+        if let Source::Dir(dir) = &self.source {
+            std::fs::copy(source_path, dir.resolve(iso_path))?;
+            return Ok(());
+        }
         let ops = [gc_fst::IsoOp::Insert {
             iso_path,
             input_path: source_path,
@@ -98,6 +362,9 @@ impl IsoFile {
     /// Write file data to a path in the ISO.
     /// This writes to a temp file and replaces the ISO file.
     pub fn write_file(&self, iso_path: &Path, data: &[u8]) -> Result<()> {
+        if self.read_only {
+            return Err(Error::ReadOnly);
+        }
         // Create a unique temp file
         let temp_dir = std::env::temp_dir();
         let unique_id = std::time::SystemTime::now()
@@ -120,6 +387,10 @@ impl IsoFile {
     /// Read a file directly from the ISO into memory.
     /// This extracts to a temp file and reads it.
     pub fn read_file(&self, iso_path: &Path) -> Result<Vec<u8>> {
+        if let Source::Memory(data) = &self.source {
+            return memory_file(data, iso_path);
+        }
+
         // Create a unique temp file path
         let temp_dir = std::env::temp_dir();
         let unique_id = std::time::SystemTime::now()
@@ -148,12 +419,48 @@ pub fn read_start_dol(iso: &IsoFile) -> Result<Vec<u8>> {
     iso.read_file(dol_path)
 }
 
+/// Get the temporary sibling path used while writing `dest` (`<name>.tmp`).
+pub fn temp_sibling_path(dest: &Path) -> Result<PathBuf> {
+    let file_name = dest
+        .file_name()
+        .ok_or_else(|| Error::ValidationError(format!("Invalid path: {}", dest.display())))?;
+    let mut temp_name = file_name.to_os_string();
+    temp_name.push(".tmp");
+    Ok(dest.with_file_name(temp_name))
+}
+
+/// Produce `dest` atomically.
+///
+/// `build` is given a temporary sibling path to write to. Only if it succeeds
+/// is the temporary file renamed over `dest`; on failure it is removed and
+/// `dest` is left exactly as it was. `build` may use any error type that
+/// crate errors convert into.
+pub fn write_atomically<F, E>(dest: &Path, build: F) -> std::result::Result<(), E>
+where
+    F: FnOnce(&Path) -> std::result::Result<(), E>,
+    E: From<Error>,
+{
+    let temp_path = temp_sibling_path(dest)?;
+
+    if let Err(e) = build(&temp_path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    if let Err(e) = std::fs::rename(&temp_path, dest) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(Error::from(e).into());
+    }
+
+    Ok(())
+}
+
 /// A file entry in the ISO filesystem.
 #[derive(Debug, Clone)]
 pub struct IsoFileEntry {
     /// Path within the ISO
     pub path: PathBuf,
-    /// Offset in the ISO
+    /// Offset in the ISO (0 for an extracted directory)
     pub offset: u32,
     /// Size in bytes
     pub size: u32,
@@ -162,95 +469,11 @@ pub struct IsoFileEntry {
 impl IsoFile {
     /// List all files in the ISO filesystem.
     pub fn list_files(&mut self) -> Result<Vec<IsoFileEntry>> {
-        // Read header info
-        self.file.seek(SeekFrom::Start(HEADER_INFO_OFFSET))?;
-        let mut buf = [0u8; 12];
-        self.file.read_exact(&mut buf)?;
-        let fst_offset = u32::from_be_bytes(buf[4..8].try_into().unwrap());
-        let fs_size = u32::from_be_bytes(buf[8..12].try_into().unwrap());
-
-        // Read entry count
-        self.file.seek(SeekFrom::Start((fst_offset + 8) as u64))?;
-        let mut u32_buf = [0u8; 4];
-        self.file.read_exact(&mut u32_buf)?;
-        let entry_count = u32::from_be_bytes(u32_buf);
-
-        let string_table_offset = fst_offset + entry_count * 0xC;
-        let entry_start_offset = fst_offset + 0xC;
-
-        // Read FST data
-        let string_table_offset_in_buf = string_table_offset - entry_start_offset;
-        self.file.seek(SeekFrom::Start(entry_start_offset as u64))?;
-        let mut fst_buf = vec![0u8; fs_size as usize];
-        self.file.read_exact(&mut fst_buf)?;
-
-        let mut files = Vec::new();
-        let mut dir_end_indices: Vec<u32> = Vec::with_capacity(8);
-        let mut offset = 0u32;
-        let mut entry_index = 1u32;
-        let mut path = PathBuf::with_capacity(64);
-
-        while offset < string_table_offset_in_buf {
-            while Some(entry_index) == dir_end_indices.last().copied() {
-                dir_end_indices.pop();
-                path.pop();
-            }
-
-            let is_file = fst_buf[offset as usize] == 0;
-
-            let name_offset = u32::from_be_bytes([
-                0,
-                fst_buf[offset as usize + 1],
-                fst_buf[offset as usize + 2],
-                fst_buf[offset as usize + 3],
-            ]);
-
-            // Read filename from string table
-            let name_start = (string_table_offset_in_buf + name_offset) as usize;
-            let mut name_end = name_start;
-            while name_end < fst_buf.len() && fst_buf[name_end] != 0 {
-                name_end += 1;
-            }
-            let name = String::from_utf8_lossy(&fst_buf[name_start..name_end]).to_string();
-
-            if is_file {
-                let file_offset = u32::from_be_bytes([
-                    fst_buf[offset as usize + 4],
-                    fst_buf[offset as usize + 5],
-                    fst_buf[offset as usize + 6],
-                    fst_buf[offset as usize + 7],
-                ]);
-                let file_size = u32::from_be_bytes([
-                    fst_buf[offset as usize + 8],
-                    fst_buf[offset as usize + 9],
-                    fst_buf[offset as usize + 10],
-                    fst_buf[offset as usize + 11],
-                ]);
-
-                let mut file_path = path.clone();
-                file_path.push(&name);
-
-                files.push(IsoFileEntry {
-                    path: file_path,
-                    offset: file_offset,
-                    size: file_size,
-                });
-            } else {
-                path.push(&name);
-                let next_idx = u32::from_be_bytes([
-                    fst_buf[offset as usize + 8],
-                    fst_buf[offset as usize + 9],
-                    fst_buf[offset as usize + 10],
-                    fst_buf[offset as usize + 11],
-                ]);
-                dir_end_indices.push(next_idx);
-            }
-
-            offset += 0xC;
-            entry_index += 1;
+        if let Source::Dir(dir) = &self.source {
+            return dir.list_files();
         }
 
-        Ok(files)
+        list_image_files(|offset, len| self.read_bytes_at(offset, len))
     }
 
     /// List files matching a pattern (substring matching).
@@ -268,8 +491,260 @@ impl IsoFile {
             .collect())
     }
 
+    /// Get the most bytes that fit in `entry`'s slot without moving other
+    /// files: the space up to the next file on the disc, or to the end of
+    /// the image for the last file. Unlimited for an extracted directory.
+    pub fn file_capacity(&mut self, entry: &IsoFileEntry) -> Result<u64> {
+        if self.is_dir() {
+            return Ok(u64::MAX);
+        }
+        let image_len = self.image_len()?;
+        let next_offset = self
+            .list_files()?
+            .iter()
+            .map(|f| f.offset as u64)
+            .filter(|&offset| offset > entry.offset as u64)
+            .min()
+            .unwrap_or(image_len);
+        Ok(next_offset - entry.offset as u64)
+    }
+
     /// Read a file directly by offset and size (faster than by path).
     pub fn read_file_direct(&mut self, entry: &IsoFileEntry) -> Result<Vec<u8>> {
+        if let Source::Dir(dir) = &self.source {
+            return Ok(std::fs::read(dir.resolve(&entry.path))?);
+        }
         self.read_bytes_at(entry.offset as u64, entry.size as usize)
     }
 }
+
+/// Get `len` bytes at `offset` of an in-memory image.
+fn memory_slice(data: &[u8], offset: u64, len: usize) -> Result<&[u8]> {
+    usize::try_from(offset)
+        .ok()
+        .and_then(|start| data.get(start..start.checked_add(len)?))
+        .ok_or_else(|| {
+            Error::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "Read of {} bytes at {:#x} is past the end of the image",
+                    len, offset
+                ),
+            ))
+        })
+}
+
+/// Read a file out of an in-memory image by its path in the disc.
+///
+/// `Start.dol` is the main executable, located through the boot header
+/// like gc_fst does.
+fn memory_file(data: &[u8], iso_path: &Path) -> Result<Vec<u8>> {
+    let mut read_at = |offset: u64, len: usize| memory_slice(data, offset, len).map(<[u8]>::to_vec);
+
+    if iso_path == Path::new("Start.dol") {
+        let header = read_at(HEADER_INFO_OFFSET, 4)?;
+        let dol_offset = u32::from_be_bytes(header.try_into().unwrap()) as u64;
+        let dol_header = read_at(dol_offset, DOL_HEADER_SIZE)?;
+        return read_at(dol_offset, dol_size(&dol_header));
+    }
+
+    let entry = list_image_files(&mut read_at)?
+        .into_iter()
+        .find(|entry| entry.path == iso_path)
+        .ok_or_else(|| Error::FileNotFound {
+            path: iso_path.to_path_buf(),
+        })?;
+    read_at(entry.offset as u64, entry.size as usize)
+}
+
+/// Size of a DOL header: 18 section offsets, addresses and sizes, then the
+/// BSS and entry point.
+const DOL_HEADER_SIZE: usize = 0x100;
+
+/// Get the size of a DOL from its header: the end of its last section.
+fn dol_size(header: &[u8]) -> usize {
+    let word = |at: usize| u32::from_be_bytes(header[at..at + 4].try_into().unwrap()) as usize;
+    (0..18)
+        .map(|section| word(section * 4) + word(0x90 + section * 4))
+        .fold(DOL_HEADER_SIZE, usize::max)
+}
+
+/// Walk the FST of a disc image, reading the image through `read_at`.
+fn list_image_files<F>(mut read_at: F) -> Result<Vec<IsoFileEntry>>
+where
+    F: FnMut(u64, usize) -> Result<Vec<u8>>,
+{
+    // Read header info
+    let buf = read_at(HEADER_INFO_OFFSET, 12)?;
+    let fst_offset = u32::from_be_bytes(buf[4..8].try_into().unwrap());
+    let fs_size = u32::from_be_bytes(buf[8..12].try_into().unwrap());
+
+    // Read entry count
+    let u32_buf = read_at((fst_offset + 8) as u64, 4)?;
+    let entry_count = u32::from_be_bytes(u32_buf.try_into().unwrap());
+
+    let string_table_offset = fst_offset + entry_count * 0xC;
+    let entry_start_offset = fst_offset + 0xC;
+
+    // Read FST data
+    let string_table_offset_in_buf = string_table_offset - entry_start_offset;
+    let fst_buf = read_at(entry_start_offset as u64, fs_size as usize)?;
+
+    let mut files = Vec::new();
+    let mut dir_end_indices: Vec<u32> = Vec::with_capacity(8);
+    let mut offset = 0u32;
+    let mut entry_index = 1u32;
+    let mut path = PathBuf::with_capacity(64);
+
+    while offset < string_table_offset_in_buf {
+        while Some(entry_index) == dir_end_indices.last().copied() {
+            dir_end_indices.pop();
+            path.pop();
+        }
+
+        let is_file = fst_buf[offset as usize] == 0;
+
+        let name_offset = u32::from_be_bytes([
+            0,
+            fst_buf[offset as usize + 1],
+            fst_buf[offset as usize + 2],
+            fst_buf[offset as usize + 3],
+        ]);
+
+        // Read filename from string table
+        let name_start = (string_table_offset_in_buf + name_offset) as usize;
+        let mut name_end = name_start;
+        while name_end < fst_buf.len() && fst_buf[name_end] != 0 {
+            name_end += 1;
+        }
+        let name = String::from_utf8_lossy(&fst_buf[name_start..name_end]).to_string();
+
+        if is_file {
+            let file_offset = u32::from_be_bytes([
+                fst_buf[offset as usize + 4],
+                fst_buf[offset as usize + 5],
+                fst_buf[offset as usize + 6],
+                fst_buf[offset as usize + 7],
+            ]);
+            let file_size = u32::from_be_bytes([
+                fst_buf[offset as usize + 8],
+                fst_buf[offset as usize + 9],
+                fst_buf[offset as usize + 10],
+                fst_buf[offset as usize + 11],
+            ]);
+
+            let mut file_path = path.clone();
+            file_path.push(&name);
+
+            files.push(IsoFileEntry {
+                path: file_path,
+                offset: file_offset,
+                size: file_size,
+            });
+        } else {
+            path.push(&name);
+            let next_idx = u32::from_be_bytes([
+                fst_buf[offset as usize + 8],
+                fst_buf[offset as usize + 9],
+                fst_buf[offset as usize + 10],
+                fst_buf[offset as usize + 11],
+            ]);
+            dir_end_indices.push(next_idx);
+        }
+
+        offset += 0xC;
+        entry_index += 1;
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dol_size_is_end_of_last_section() {
+        let mut header = vec![0u8; DOL_HEADER_SIZE];
+        // Text section 0 at 0x100 (0x20 bytes), data section 0 at 0x200 (0x10 bytes)
+        header[0x00..0x04].copy_from_slice(&0x100u32.to_be_bytes());
+        header[0x90..0x94].copy_from_slice(&0x20u32.to_be_bytes());
+        header[0x1C..0x20].copy_from_slice(&0x200u32.to_be_bytes());
+        header[0xAC..0xB0].copy_from_slice(&0x10u32.to_be_bytes());
+        assert_eq!(dol_size(&header), 0x210);
+        assert_eq!(dol_size(&[0u8; DOL_HEADER_SIZE]), DOL_HEADER_SIZE);
+    }
+
+    #[test]
+    fn test_memory_image_too_small() {
+        assert!(matches!(
+            IsoFile::from_bytes(vec![0u8; 16]),
+            Err(Error::TruncatedIso { .. })
+        ));
+    }
+
+    #[test]
+    fn test_temp_sibling_path() {
+        let temp = temp_sibling_path(Path::new("out/game.iso")).unwrap();
+        assert_eq!(temp, Path::new("out/game.iso.tmp"));
+    }
+
+    #[test]
+    fn test_write_atomically_failure_leaves_dest_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("game.iso");
+        std::fs::write(&dest, b"original").unwrap();
+
+        let result = write_atomically(&dest, |temp| {
+            // Simulate a crash part-way through writing the output
+            std::fs::write(temp, b"half-writ")?;
+            Err(Error::IsoOperationError("simulated failure".into()))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(std::fs::read(&dest).unwrap(), b"original");
+        assert!(!dir.path().join("game.iso.tmp").exists());
+    }
+
+    #[test]
+    fn test_write_atomically_success_replaces_dest() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("game.iso");
+        std::fs::write(&dest, b"original").unwrap();
+
+        write_atomically(&dest, |temp| -> Result<()> {
+            std::fs::write(temp, b"modified")?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(std::fs::read(&dest).unwrap(), b"modified");
+        assert!(!dir.path().join("game.iso.tmp").exists());
+    }
+
+    #[test]
+    fn test_open_dir_dolphin_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("sys")).unwrap();
+        std::fs::create_dir_all(root.join("files/field")).unwrap();
+        let mut header = b"GEAE8P".to_vec();
+        header.resize(0x440, 0);
+        std::fs::write(root.join("sys/boot.bin"), header).unwrap();
+        std::fs::write(root.join("sys/main.dol"), b"dol").unwrap();
+        std::fs::write(root.join("files/field/a000a_ep.enp"), b"enp").unwrap();
+
+        let mut iso = IsoFile::open_dir(root).unwrap();
+        assert!(iso.is_dir());
+        assert_eq!(iso.read_game_id().unwrap(), "GEAE8P");
+        assert_eq!(iso.read_file(Path::new("Start.dol")).unwrap(), b"dol");
+
+        let files = iso.list_files_matching(".enp").unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, Path::new("field/a000a_ep.enp"));
+        assert_eq!(iso.read_file_direct(&files[0]).unwrap(), b"enp");
+
+        // Not an extracted disc
+        assert!(IsoFile::open_dir(&root.join("files")).is_err());
+    }
+}
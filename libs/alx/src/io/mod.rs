@@ -8,17 +8,24 @@ mod enp_dump;
 mod iso;
 mod strings;
 
-pub use aklz::{compress as compress_aklz, decompress as decompress_aklz, is_aklz};
+pub use aklz::{
+    compress as compress_aklz, decompress as decompress_aklz, is_aklz, CompressionMode,
+};
 pub use binary::{BinaryReader, BinaryWriter};
-pub use enp::{parse_dat_file, parse_enp, parse_evp, patch_enp_encounters, EnpData};
+pub use enp::{
+    parse_dat_file, parse_enp, parse_evp, patch_enp_encounters, split_enp_segments, EnemyFileKind,
+    EnpData,
+};
 pub use enp_builder::{
-    bake_enp_segments, build_enp, build_evp, EnemyDatabase, GlobalEnemyDatabase, RawEnemyData,
-    A099A_BAKED_FILENAME, A099A_SEGMENTS,
+    bake_a099a, bake_enp_segments, build_enp, build_evp, find_missing_enemy_references,
+    split_enp_enemy_records, EnemyDatabase, GlobalEnemyDatabase, MissingEnemyReferences,
+    RawEnemyData, A099A_BAKED_FILENAME, A099A_SEGMENTS,
 };
 pub use enp_dump::{
-    dump_enp, dump_enp_editable, dump_evp, dump_evp_editable, EncounterDefinition, EncounterDump,
-    EnemyDefinition, EnemyDump, EnemyStatsDef, EnpDefinition, EnpDump, EventCharacterDef,
+    dump_enp, dump_enp_editable, dump_enp_segments_editable, dump_evp, dump_evp_editable,
+    EncounterDefinition, EncounterDump, EncounterListDefinition, EnemyDefinition, EnemyDump,
+    EnemyStatsDef, EnpDefinition, EnpDump, EnpDumpStats, EnpFileStats, EnpMeta, EventCharacterDef,
     EventDefinition, EventEnemyDef, EvpDefinition, EvpDump, HeaderEntry, ItemDropDef,
 };
-pub use iso::{IsoFile, IsoFileEntry};
-pub use strings::{decode_windows1252, read_description_strings};
+pub use iso::{temp_sibling_path, write_atomically, IsoFile, IsoFileEntry};
+pub use strings::{decode_windows1252, normalize_description, read_description_strings};
@@ -133,8 +133,23 @@ pub fn decode_windows1252(bytes: &[u8]) -> String {
     decode_game_string(bytes)
 }
 
+/// Canonicalize a description's whitespace.
+///
+/// CRLF and lone CR line endings become LF, and trailing spaces and tabs
+/// are removed from every line, so the same text reads and exports the same
+/// on every platform. Leading whitespace and blank lines are kept.
+pub fn normalize_description(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .split('\n')
+        .map(|line| line.trim_end_matches([' ', '\t']))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Read all description strings from a description data range.
-/// Returns a vector of (position, size, text) tuples.
+/// Returns a vector of (position, size, text) tuples, with the text passed
+/// through [`normalize_description`]. Sizes are those of the raw strings.
 pub fn read_description_strings(
     data: &[u8],
     base_offset: usize,
@@ -152,7 +167,7 @@ pub fn read_description_strings(
         let pos = base_offset + cursor.position() as usize;
         let (text, size) = read_aligned_string(&mut cursor, block_size)?;
 
-        descriptions.push((pos as u32, size as u32, text));
+        descriptions.push((pos as u32, size as u32, normalize_description(&text)));
     }
 
     Ok(descriptions)
@@ -186,4 +201,21 @@ mod tests {
         assert_eq!(descriptions[0], (0x1000, 4, "Hi".to_string()));
         assert_eq!(descriptions[1], (0x1004, 8, "Test".to_string()));
     }
+
+    #[test]
+    fn test_read_description_normalizes_line_endings() {
+        let data = b"Line one  \r\nLine two\t\rEnd \x00\x00\x00\x00";
+        let descriptions = read_description_strings(data, 0, 1, 4).unwrap();
+        assert_eq!(
+            descriptions[0],
+            (0, 28, "Line one\nLine two\nEnd".to_string())
+        );
+
+        let text = &descriptions[0].2;
+        assert_eq!(normalize_description(text), *text);
+        assert_eq!(
+            normalize_description("  Indented\n\nGap"),
+            "  Indented\n\nGap"
+        );
+    }
 }
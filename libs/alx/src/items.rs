@@ -51,13 +51,31 @@ impl ItemCategory {
     }
 }
 
+/// Placeholder used for item IDs that don't resolve to a name.
+pub const DEFAULT_UNKNOWN_PLACEHOLDER: &str = "???";
+
 /// Database of all items in the game, providing name lookups.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct ItemDatabase {
     /// Map from item ID to item name
     id_to_name: HashMap<i32, String>,
     /// Map from item name (lowercase) to item ID
     name_to_id: HashMap<String, i32>,
+    /// Map from item ID to buy price
+    id_to_price: HashMap<i32, u16>,
+    /// Name returned for IDs that aren't in the database
+    placeholder: String,
+}
+
+impl Default for ItemDatabase {
+    fn default() -> Self {
+        Self {
+            id_to_name: HashMap::new(),
+            name_to_id: HashMap::new(),
+            id_to_price: HashMap::new(),
+            placeholder: DEFAULT_UNKNOWN_PLACEHOLDER.to_string(),
+        }
+    }
 }
 
 impl ItemDatabase {
@@ -81,27 +99,35 @@ impl ItemDatabase {
 
         for item in weapons {
             db.insert(item.id as i32, &item.name);
+            db.set_buy_price(item.id as i32, item.buy_price);
         }
         for item in armors {
             db.insert(item.id as i32, &item.name);
+            db.set_buy_price(item.id as i32, item.buy_price);
         }
         for item in accessories {
             db.insert(item.id as i32, &item.name);
+            db.set_buy_price(item.id as i32, item.buy_price);
         }
         for item in usable_items {
             db.insert(item.id as i32, &item.name);
+            db.set_buy_price(item.id as i32, item.buy_price);
         }
         for item in special_items {
             db.insert(item.id as i32, &item.name);
+            db.set_buy_price(item.id as i32, item.buy_price);
         }
         for item in ship_cannons {
             db.insert(item.id as i32, &item.name);
+            db.set_buy_price(item.id as i32, item.buy_price);
         }
         for item in ship_accessories {
             db.insert(item.id as i32, &item.name);
+            db.set_buy_price(item.id as i32, item.buy_price);
         }
         for item in ship_items {
             db.insert(item.id as i32, &item.name);
+            db.set_buy_price(item.id as i32, item.buy_price);
         }
 
         // Add Gold entry
@@ -110,12 +136,42 @@ impl ItemDatabase {
         db
     }
 
+    /// Set the name used for unresolved item IDs (default `"???"`).
+    ///
+    /// Exporters go through [`name_or_default`](Self::name_or_default), so a
+    /// distinctive placeholder makes dangling IDs easy to tell apart from
+    /// real empty slots ("None").
+    pub fn with_placeholder(mut self, placeholder: &str) -> Self {
+        self.set_placeholder(placeholder);
+        self
+    }
+
+    /// Set the name used for unresolved item IDs.
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = placeholder.to_string();
+    }
+
+    /// Get the name used for unresolved item IDs.
+    pub fn placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
     /// Insert an item into the database.
     pub fn insert(&mut self, id: i32, name: &str) {
         self.id_to_name.insert(id, name.to_string());
         self.name_to_id.insert(name.to_lowercase(), id);
     }
 
+    /// Record the buy price for an item ID.
+    pub fn set_buy_price(&mut self, id: i32, price: u16) {
+        self.id_to_price.insert(id, price);
+    }
+
+    /// Get the buy price for an item ID, if known.
+    pub fn buy_price(&self, id: i32) -> Option<u16> {
+        self.id_to_price.get(&id).copied()
+    }
+
     /// Get the name for an item ID.
     pub fn get_name(&self, id: i32) -> Option<&str> {
         // Handle Gold specially
@@ -130,7 +186,8 @@ impl ItemDatabase {
         self.get_name(id).unwrap_or(default).to_string()
     }
 
-    /// Get the name for an item ID, with "None" for -1 and "???" for unknown.
+    /// Get the name for an item ID, with "None" for -1 and the
+    /// [placeholder](Self::placeholder) for unknown.
     pub fn name_or_default(&self, id: i32) -> String {
         if id == -1 {
             "None".to_string()
@@ -140,7 +197,7 @@ impl ItemDatabase {
             self.id_to_name
                 .get(&id)
                 .cloned()
-                .unwrap_or_else(|| "???".to_string())
+                .unwrap_or_else(|| self.placeholder.clone())
         }
     }
 
@@ -241,6 +298,18 @@ mod tests {
         assert_eq!(db.name_or_default(512), "Gold"); // Gold IDs are 0x200+
     }
 
+    #[test]
+    fn test_custom_placeholder() {
+        let mut db = ItemDatabase::new().with_placeholder("<unresolved>");
+        db.insert(240, "Sacri Crystal");
+
+        assert_eq!(db.placeholder(), "<unresolved>");
+        assert_eq!(db.name_or_default(241), "<unresolved>");
+        assert_eq!(db.name_or_default(240), "Sacri Crystal");
+        assert_eq!(db.name_or_default(-1), "None");
+        assert_eq!(format_item_with_amount(241, 2, &db), "<unresolved> x2");
+    }
+
     #[test]
     fn test_format_item() {
         let mut db = ItemDatabase::new();
@@ -9,6 +9,7 @@
 //! - Parse all game data types (items, enemies, characters, etc.)
 //! - Export to CSV format (compatible with original ALX)
 //! - Import from CSV and write back to ISO
+//! - Seeded randomization passes over the parsed data
 //!
 //! ## Example
 //!
@@ -33,6 +34,8 @@ pub mod game;
 pub mod io;
 pub mod items;
 pub mod lookups;
+pub mod patch;
+pub mod randomizer;
 
 pub use items::{ItemCategory, ItemDatabase};
 
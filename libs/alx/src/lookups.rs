@@ -3,6 +3,9 @@
 //! These tables map IDs to human-readable names, matching the Ruby ALX vocabulary.
 
 /// Get character name by ID.
+///
+/// The playable characters keep the same romanized names in every release,
+/// so all regions share one table. Unknown IDs return `"???"`.
 pub fn character_name(id: i8) -> &'static str {
     match id {
         0 => "Vyse",
@@ -85,6 +88,37 @@ pub fn element_name(id: i8) -> &'static str {
     }
 }
 
+/// IDs of the six elements (Green, Red, Purple, Blue, Yellow, Silver).
+pub const ELEMENT_IDS: std::ops::RangeInclusive<i8> = 0..=5;
+
+/// Get the RGB color used to draw an element's icon.
+///
+/// The six elements use their in-game moon colors; anything else is grey.
+pub fn element_color(id: i8) -> (u8, u8, u8) {
+    match id {
+        0 => (0x3c, 0xb0, 0x4a),
+        1 => (0xd8, 0x33, 0x2b),
+        2 => (0x8e, 0x44, 0xad),
+        3 => (0x2f, 0x6f, 0xd6),
+        4 => (0xe8, 0xc5, 0x1c),
+        5 => (0xc0, 0xc6, 0xcc),
+        _ => (0x80, 0x80, 0x80),
+    }
+}
+
+/// Get the one-letter symbol shown on an element's icon.
+pub fn element_symbol(id: i8) -> &'static str {
+    match id {
+        0 => "G",
+        1 => "R",
+        2 => "P",
+        3 => "B",
+        4 => "Y",
+        5 => "S",
+        _ => "-",
+    }
+}
+
 /// Get state name by ID.
 pub fn state_name(id: i8) -> &'static str {
     match id {
@@ -375,9 +409,8 @@ pub fn character_flag_marker(flags: u16, character_id: u8) -> &'static str {
     }
 }
 
-/// Get enemy name by ID.
-/// Returns the US/English name for an enemy, or "???" if unknown.
-pub fn enemy_name(id: u32) -> &'static str {
+/// Resolve an enemy's US name, or "???" if the ID is unknown.
+pub fn enemy_us_name(id: u32) -> &'static str {
     match id {
         0 => "Soldier",
         1 => "Guard",
@@ -521,12 +554,18 @@ pub fn enemy_name(id: u32) -> &'static str {
     }
 }
 
+/// Resolve an enemy's US name, or "???" if the ID is unknown.
+#[deprecated(note = "use enemy_us_name")]
+pub fn enemy_name(id: u32) -> &'static str {
+    enemy_us_name(id)
+}
+
 /// Build a HashMap of enemy IDs to names.
 pub fn enemy_names_map() -> std::collections::HashMap<u32, String> {
     let mut map = std::collections::HashMap::new();
     // Add all known enemies
     for id in 0..=200 {
-        let name = enemy_name(id);
+        let name = enemy_us_name(id);
         if name != "???" {
             map.insert(id, name.to_string());
         }
@@ -674,6 +713,30 @@ pub fn action_param_name(id: i16) -> &'static str {
     }
 }
 
+/// Crew position names, indexed by position ID.
+const POSITION_NAMES: [&str; 11] = [
+    "Helmsman", "Engineer", "Gunner", "Artisan", "Sailor", "Cook", "Merchant", "Builder",
+    "Lookout", "Jester", "Delegate",
+];
+
+/// Get crew position name by ID.
+pub fn position_name(id: i8) -> &'static str {
+    usize::try_from(id)
+        .ok()
+        .and_then(|i| POSITION_NAMES.get(i))
+        .copied()
+        .unwrap_or("???")
+}
+
+/// Get crew position ID by name, ignoring case. Reverse of [`position_name`].
+pub fn position_id_by_name(name: &str) -> Option<i8> {
+    let name = name.trim();
+    POSITION_NAMES
+        .iter()
+        .position(|n| n.eq_ignore_ascii_case(name))
+        .map(|i| i as i8)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -681,10 +744,44 @@ mod tests {
     #[test]
     fn test_character_names() {
         assert_eq!(character_name(0), "Vyse");
+        assert_eq!(character_name(1), "Aika");
+        assert_eq!(character_name(2), "Fina");
+        assert_eq!(character_name(3), "Drachma");
+        assert_eq!(character_name(4), "Enrique");
         assert_eq!(character_name(5), "Gilder");
         assert_eq!(character_name(99), "???");
     }
 
+    #[test]
+    fn test_element_colors_distinct() {
+        let colors: std::collections::HashSet<_> = ELEMENT_IDS.map(element_color).collect();
+        assert_eq!(colors.len(), 6);
+        let symbols: std::collections::HashSet<_> = ELEMENT_IDS.map(element_symbol).collect();
+        assert_eq!(symbols.len(), 6);
+        assert_eq!(element_color(-1), element_color(6));
+    }
+
+    #[test]
+    fn test_enemy_us_names() {
+        assert_eq!(enemy_us_name(0), "Soldier");
+        assert_eq!(enemy_us_name(107), "Jynnus");
+        assert_eq!(enemy_us_name(132), "Galcian");
+        assert_eq!(enemy_us_name(151), "Galcian");
+        assert_eq!(enemy_us_name(159), "Piastol");
+        assert_eq!(enemy_us_name(9999), "???");
+        assert_eq!(
+            enemy_names_map().get(&107).map(String::as_str),
+            Some(enemy_us_name(107))
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_enemy_name_forwards_to_us_name() {
+        assert_eq!(enemy_name(107), enemy_us_name(107));
+        assert_eq!(enemy_name(9999), "???");
+    }
+
     #[test]
     fn test_trait_names() {
         assert_eq!(trait_name(-1), "None");
@@ -692,6 +789,16 @@ mod tests {
         assert_eq!(trait_name(36), "Yellow");
     }
 
+    #[test]
+    fn test_position_names_round_trip() {
+        for id in 0..POSITION_NAMES.len() as i8 {
+            assert_eq!(position_id_by_name(position_name(id)), Some(id));
+        }
+        assert_eq!(position_id_by_name("gunner"), Some(2));
+        assert_eq!(position_name(11), "???");
+        assert_eq!(position_id_by_name("???"), None);
+    }
+
     #[test]
     fn test_character_flags() {
         // 0b00111010 = V,A,F,E can equip
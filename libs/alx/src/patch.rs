@@ -0,0 +1,413 @@
+//! Raw byte patches for Start.dol.
+//!
+//! For mods that need to change bytes outside the structured data regions
+//! (e.g. ASM hacks). A [`PatchList`] is a set of `(offset, bytes)` entries
+//! that is guaranteed not to overlap, so the order patches are applied in
+//! doesn't matter.
+//!
+//! [`make_ips`] and [`apply_ips`] create and apply IPS patches, so mods can
+//! be shared without distributing game files.
+
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// A single patch: bytes to write at an offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch {
+    /// Offset into the file
+    pub offset: usize,
+    /// Bytes to write
+    pub bytes: Vec<u8>,
+}
+
+impl Patch {
+    /// Get the byte range this patch covers.
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.bytes.len()
+    }
+}
+
+/// A set of non-overlapping patches, sorted by offset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatchList {
+    patches: Vec<Patch>,
+}
+
+impl PatchList {
+    /// Create an empty patch list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a patch, failing if it overlaps one already in the list.
+    /// Empty patches are ignored.
+    pub fn add(&mut self, offset: usize, bytes: Vec<u8>) -> Result<()> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+
+        let patch = Patch { offset, bytes };
+        let range = patch.range();
+        if let Some(other) = self
+            .patches
+            .iter()
+            .find(|p| p.offset < range.end && range.start < p.range().end)
+        {
+            return Err(Error::ValidationError(format!(
+                "Patch at {:#x}..{:#x} overlaps patch at {:#x}..{:#x}",
+                range.start,
+                range.end,
+                other.offset,
+                other.range().end
+            )));
+        }
+
+        let pos = self.patches.partition_point(|p| p.offset < offset);
+        self.patches.insert(pos, patch);
+        Ok(())
+    }
+
+    /// Get the patches, sorted by offset.
+    pub fn patches(&self) -> &[Patch] {
+        &self.patches
+    }
+
+    /// Get the number of patches.
+    pub fn len(&self) -> usize {
+        self.patches.len()
+    }
+
+    /// Check if the list has no patches.
+    pub fn is_empty(&self) -> bool {
+        self.patches.is_empty()
+    }
+
+    /// Parse a patch list from text.
+    ///
+    /// Each line is a hex offset followed by hex bytes, Gecko-code style:
+    ///
+    /// ```text
+    /// # Comments start with '#'
+    /// 002c2790 0000FFFF
+    /// 0x002c27a0 38 60 00 01
+    /// ```
+    ///
+    /// Bytes may be split by whitespace or written as one run.
+    pub fn from_text(text: &str) -> Result<Self> {
+        let mut list = Self::new();
+
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let parse_err = |message: String| Error::ParseError {
+                offset: line_no + 1,
+                message,
+            };
+
+            let mut parts = line.split_whitespace();
+            let offset_str = parts.next().unwrap_or("");
+            let offset_hex = offset_str
+                .strip_prefix("0x")
+                .or_else(|| offset_str.strip_prefix("0X"))
+                .unwrap_or(offset_str);
+            let offset = usize::from_str_radix(offset_hex, 16)
+                .map_err(|_| parse_err(format!("Invalid patch offset: {}", offset_str)))?;
+
+            let hex: String = parts.collect();
+            if hex.is_empty() || hex.len() % 2 != 0 {
+                return Err(parse_err(format!(
+                    "Patch bytes must be an even number of hex digits: {}",
+                    line
+                )));
+            }
+            let bytes = (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+                .collect::<std::result::Result<Vec<u8>, _>>()
+                .map_err(|_| parse_err(format!("Invalid patch bytes: {}", hex)))?;
+
+            list.add(offset, bytes)?;
+        }
+
+        Ok(list)
+    }
+}
+
+impl FromStr for PatchList {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_text(s)
+    }
+}
+
+impl fmt::Display for PatchList {
+    /// Write the list in the text format read by [`PatchList::from_text`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for patch in &self.patches {
+            write!(f, "{:08x} ", patch.offset)?;
+            for b in &patch.bytes {
+                write!(f, "{:02X}", b)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// IPS Patches
+// ============================================================================
+
+/// IPS file header
+const IPS_HEADER: &[u8] = b"PATCH";
+
+/// IPS end-of-file marker
+const IPS_FOOTER: &[u8] = b"EOF";
+
+/// A record at this offset would be read as the footer
+const IPS_EOF_OFFSET: usize = 0x454f46;
+
+/// Largest offset an IPS record can start at (24-bit)
+const IPS_MAX_OFFSET: usize = 0xffffff;
+
+/// Largest number of bytes in one IPS record (16-bit)
+const IPS_MAX_RECORD: usize = 0xffff;
+
+/// Create an IPS patch that turns `original` into `modified`.
+///
+/// If `modified` is shorter than `original`, the truncation extension (a
+/// 3-byte size after the footer) is written.
+///
+/// Fails with [`Error::ValidationError`] if a change (or the truncated size)
+/// lies beyond the 16 MiB that IPS offsets can address.
+pub fn make_ips(original: &[u8], modified: &[u8]) -> Result<Vec<u8>> {
+    let differs = |i: usize| i >= original.len() || original[i] != modified[i];
+
+    let mut out = IPS_HEADER.to_vec();
+    let mut i = 0;
+    while i < modified.len() {
+        if !differs(i) {
+            i += 1;
+            continue;
+        }
+
+        // Start one byte early rather than at the offset that spells "EOF"
+        let start = if i == IPS_EOF_OFFSET { i - 1 } else { i };
+        if start > IPS_MAX_OFFSET {
+            return Err(Error::ValidationError(format!(
+                "IPS patches can't address offset {:#x}",
+                start
+            )));
+        }
+
+        let mut end = i;
+        while end < modified.len() && end - start < IPS_MAX_RECORD && differs(end) {
+            end += 1;
+        }
+
+        out.extend_from_slice(&(start as u32).to_be_bytes()[1..]);
+        out.extend_from_slice(&((end - start) as u16).to_be_bytes());
+        out.extend_from_slice(&modified[start..end]);
+        i = end;
+    }
+    out.extend_from_slice(IPS_FOOTER);
+
+    if modified.len() < original.len() {
+        if modified.len() > IPS_MAX_OFFSET {
+            return Err(Error::ValidationError(format!(
+                "IPS patches can't truncate to {:#x} bytes",
+                modified.len()
+            )));
+        }
+        out.extend_from_slice(&(modified.len() as u32).to_be_bytes()[1..]);
+    }
+
+    Ok(out)
+}
+
+/// Apply an IPS patch to `original`, returning the patched data.
+///
+/// Supports RLE records and the truncation extension.
+pub fn apply_ips(original: &[u8], patch: &[u8]) -> Result<Vec<u8>> {
+    let err = |offset: usize, message: &str| Error::ParseError {
+        offset,
+        message: message.to_string(),
+    };
+
+    if !patch.starts_with(IPS_HEADER) {
+        return Err(err(0, "Not an IPS patch (missing PATCH header)"));
+    }
+
+    let mut out = original.to_vec();
+    let mut pos = IPS_HEADER.len();
+    loop {
+        let record = patch
+            .get(pos..pos + 3)
+            .ok_or_else(|| err(pos, "IPS patch ended without EOF marker"))?;
+        if record == IPS_FOOTER {
+            pos += 3;
+            break;
+        }
+        let offset = u32::from_be_bytes([0, record[0], record[1], record[2]]) as usize;
+
+        let size = patch
+            .get(pos + 3..pos + 5)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]) as usize)
+            .ok_or_else(|| err(pos, "Truncated IPS record header"))?;
+        pos += 5;
+
+        let (data, consumed) = if size == 0 {
+            // RLE record: 2-byte run length, then the byte to repeat
+            let rle = patch
+                .get(pos..pos + 3)
+                .ok_or_else(|| err(pos, "Truncated IPS RLE record"))?;
+            let run = u16::from_be_bytes([rle[0], rle[1]]) as usize;
+            (vec![rle[2]; run], 3)
+        } else {
+            let data = patch
+                .get(pos..pos + size)
+                .ok_or_else(|| err(pos, "Truncated IPS record data"))?;
+            (data.to_vec(), size)
+        };
+        pos += consumed;
+
+        let end = offset + data.len();
+        if end > out.len() {
+            out.resize(end, 0);
+        }
+        out[offset..end].copy_from_slice(&data);
+    }
+
+    // Optional truncation extension
+    if let Some(size) = patch.get(pos..pos + 3) {
+        out.truncate(u32::from_be_bytes([0, size[0], size[1], size[2]]) as usize);
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_sorted() {
+        let mut list = PatchList::new();
+        list.add(0x20, vec![1, 2]).unwrap();
+        list.add(0x10, vec![3]).unwrap();
+        list.add(0x30, Vec::new()).unwrap();
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.patches()[0].offset, 0x10);
+        assert_eq!(list.patches()[1].range(), 0x20..0x22);
+    }
+
+    #[test]
+    fn test_add_rejects_overlap() {
+        let mut list = PatchList::new();
+        list.add(0x10, vec![0; 4]).unwrap();
+        assert!(list.add(0x12, vec![0; 4]).is_err());
+        assert!(list.add(0x0e, vec![0; 3]).is_err());
+        // Adjacent patches are fine
+        list.add(0x14, vec![0; 4]).unwrap();
+        list.add(0x0c, vec![0; 4]).unwrap();
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn test_from_text() {
+        let text = "# test patch\n002c2790 0000FFFF\n\n0x10 38 60 00 01 # li r3, 1\n";
+        let list: PatchList = text.parse().unwrap();
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.patches()[0].offset, 0x10);
+        assert_eq!(list.patches()[0].bytes, vec![0x38, 0x60, 0x00, 0x01]);
+        assert_eq!(list.patches()[1].offset, 0x2c2790);
+        assert_eq!(list.patches()[1].bytes, vec![0x00, 0x00, 0xff, 0xff]);
+
+        // Display writes the same format back
+        assert_eq!(PatchList::from_text(&list.to_string()).unwrap(), list);
+    }
+
+    #[test]
+    fn test_from_text_errors() {
+        assert!(PatchList::from_text("zz 00").is_err());
+        assert!(PatchList::from_text("10 0").is_err());
+        assert!(PatchList::from_text("10").is_err());
+        assert!(PatchList::from_text("10 0000\n11 00").is_err());
+    }
+
+    #[test]
+    fn test_ips_roundtrip() {
+        let original: Vec<u8> = (0..=255u8).cycle().take(0x1000).collect();
+        let mut modified = original.clone();
+        modified[0x10] = 0xaa;
+        modified[0x11] = 0xbb;
+        modified[0x800..0x900].fill(0);
+
+        let patch = make_ips(&original, &modified).unwrap();
+        assert!(patch.starts_with(b"PATCH"));
+        assert!(patch.ends_with(b"EOF"));
+        assert_eq!(apply_ips(&original, &patch).unwrap(), modified);
+    }
+
+    #[test]
+    fn test_ips_identical_is_empty() {
+        let data = vec![1u8; 64];
+        assert_eq!(make_ips(&data, &data).unwrap(), b"PATCHEOF");
+        assert_eq!(apply_ips(&data, b"PATCHEOF").unwrap(), data);
+    }
+
+    #[test]
+    fn test_ips_grow_and_truncate() {
+        let original = vec![0u8; 32];
+
+        let mut longer = original.clone();
+        longer.extend_from_slice(&[1, 2, 3]);
+        let patch = make_ips(&original, &longer).unwrap();
+        assert_eq!(apply_ips(&original, &patch).unwrap(), longer);
+
+        let shorter = original[..20].to_vec();
+        let patch = make_ips(&original, &shorter).unwrap();
+        assert_eq!(apply_ips(&original, &patch).unwrap(), shorter);
+    }
+
+    #[test]
+    fn test_ips_eof_offset() {
+        let original = vec![0u8; IPS_EOF_OFFSET + 8];
+        let mut modified = original.clone();
+        modified[IPS_EOF_OFFSET] = 1;
+
+        let patch = make_ips(&original, &modified).unwrap();
+        assert_eq!(apply_ips(&original, &patch).unwrap(), modified);
+    }
+
+    #[test]
+    fn test_make_ips_rejects_far_offsets() {
+        let original = vec![0u8; IPS_MAX_OFFSET + 8];
+        let mut modified = original.clone();
+        modified[IPS_MAX_OFFSET + 2] = 1;
+        let err = make_ips(&original, &modified).unwrap_err().to_string();
+        assert!(err.contains("can't address"), "{}", err);
+    }
+
+    #[test]
+    fn test_apply_ips_rle_and_errors() {
+        let original = vec![0u8; 16];
+        // RLE record: offset 4, size 0, run 3, value 0x7f
+        let patch = b"PATCH\x00\x00\x04\x00\x00\x00\x03\x7fEOF";
+        let patched = apply_ips(&original, patch).unwrap();
+        assert_eq!(&patched[4..7], &[0x7f; 3]);
+        assert_eq!(patched[7], 0);
+
+        assert!(apply_ips(&original, b"NOTIPS").is_err());
+        assert!(apply_ips(&original, b"PATCH\x00\x00\x04\x00\x05\x01").is_err());
+    }
+}
@@ -0,0 +1,117 @@
+//! Treasure chest contents randomization.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::entries::TreasureChest;
+use crate::items::{ItemCategory, ItemDatabase};
+
+/// Options for [`randomize_chests`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", default)]
+pub struct ChestOptions {
+    /// Only swap contents between chests holding the same [`ItemCategory`],
+    /// so weapon chests still hold weapons and gold chests still hold gold.
+    pub keep_category: bool,
+}
+
+/// Get the category of the item in a chest.
+pub fn chest_category(chest: &TreasureChest, item_db: &ItemDatabase) -> ItemCategory {
+    item_db.category(chest.item_id)
+}
+
+/// Shuffle contents (item and amount) between treasure chests.
+///
+/// Empty chests are left alone. With `keep_category` set, contents only
+/// move between chests of the same category.
+pub fn randomize_chests<R: Rng + ?Sized>(
+    chests: &mut [TreasureChest],
+    item_db: &ItemDatabase,
+    options: &ChestOptions,
+    rng: &mut R,
+) {
+    // Chest indices grouped by the category they shuffle within, in chest
+    // order so the result only depends on the seed
+    let mut groups: Vec<(ItemCategory, Vec<usize>)> = Vec::new();
+    for (i, chest) in chests.iter().enumerate() {
+        let category = chest_category(chest, item_db);
+        if category == ItemCategory::Unknown {
+            continue;
+        }
+        let key = if options.keep_category {
+            category
+        } else {
+            ItemCategory::Unknown
+        };
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, indices)) => indices.push(i),
+            None => groups.push((key, vec![i])),
+        }
+    }
+
+    for (_, indices) in groups {
+        let mut contents: Vec<(i32, i32)> = indices
+            .iter()
+            .map(|&i| (chests[i].item_id, chests[i].item_amount))
+            .collect();
+        contents.shuffle(rng);
+        for (i, (item_id, item_amount)) in indices.into_iter().zip(contents) {
+            chests[i].item_id = item_id;
+            chests[i].item_amount = item_amount;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::{chest, rng_from_seed};
+
+    #[test]
+    fn test_chest_category() {
+        let db = ItemDatabase::new();
+        assert_eq!(chest_category(&chest(0, 5), &db), ItemCategory::Weapon);
+        assert_eq!(chest_category(&chest(0, 0x200), &db), ItemCategory::Gold);
+        assert_eq!(chest_category(&chest(0, -1), &db), ItemCategory::Unknown);
+    }
+
+    #[test]
+    fn test_keep_category_shuffle() {
+        let db = ItemDatabase::new();
+        let original: Vec<TreasureChest> = [1, 0x50, 2, 0x150, -1, 3, 0x200, 0x151, 0x51]
+            .iter()
+            .enumerate()
+            // Distinct amounts, so amounts are seen to move with their items
+            .map(|(id, &item_id)| TreasureChest {
+                item_amount: id as i32,
+                ..chest(id as u32, item_id)
+            })
+            .collect();
+
+        let mut chests = original.clone();
+        let options = ChestOptions {
+            keep_category: true,
+        };
+        randomize_chests(&mut chests, &db, &options, &mut rng_from_seed(3));
+
+        for (before, after) in original.iter().zip(&chests) {
+            assert_eq!(
+                chest_category(before, &db),
+                chest_category(after, &db),
+                "chest {} changed category",
+                before.id
+            );
+        }
+        // Empty chests stay empty and nothing is lost
+        assert_eq!(chests[4], original[4]);
+        let mut before: Vec<_> = original
+            .iter()
+            .map(|c| (c.item_id, c.item_amount))
+            .collect();
+        let mut after: Vec<_> = chests.iter().map(|c| (c.item_id, c.item_amount)).collect();
+        before.sort();
+        after.sort();
+        assert_eq!(before, after);
+    }
+}
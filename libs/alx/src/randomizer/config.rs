@@ -0,0 +1,78 @@
+//! Randomizer run configuration.
+
+use serde::{Deserialize, Serialize};
+
+use super::{ChestOptions, DropOptions, MagicExpOptions, ShopOptions, WeaponEffectOptions};
+
+/// Settings for one randomizer run.
+///
+/// Each pass runs only when its options are set.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", default)]
+pub struct RandomizerConfig {
+    /// Run seed; each pass derives its own sub-seed from it. Unset means
+    /// pick one at random, see [`resolve_seed`](Self::resolve_seed)
+    pub seed: Option<u64>,
+    /// Shuffle enemy item drops
+    pub enemy_drops: Option<DropOptions>,
+    /// Randomize shop stock
+    pub shops: Option<ShopOptions>,
+    /// Shuffle treasure chest contents
+    pub treasure_chests: Option<ChestOptions>,
+    /// Perturb magic EXP thresholds
+    pub magic_exp_curves: Option<MagicExpOptions>,
+    /// Shuffle weapon on-hit effects
+    pub weapon_effects: Option<WeaponEffectOptions>,
+    /// Scale the late-joiner EXP boosts by this factor (EXP curves are
+    /// not changed)
+    pub exp_scale: Option<f32>,
+}
+
+impl RandomizerConfig {
+    /// Preset with every random pass that can be saved enabled at its most
+    /// extreme setting.
+    ///
+    /// Enemy drops are left off, as they live in the ENP files and can't be
+    /// written back yet. Shops keep the key items they sell, so those stay
+    /// obtainable.
+    pub fn chaos(seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            enemy_drops: None,
+            shops: Some(ShopOptions::default()),
+            treasure_chests: Some(ChestOptions {
+                keep_category: false,
+            }),
+            magic_exp_curves: Some(MagicExpOptions { variance: 1.0 }),
+            weapon_effects: Some(WeaponEffectOptions {
+                keep_none_fraction: 0.0,
+            }),
+            exp_scale: None,
+        }
+    }
+
+    /// Get the run seed, first picking a random one if it is unset.
+    ///
+    /// The picked seed is stored in the config, so it is the same for the
+    /// rest of the run and can be reported to reproduce it.
+    pub fn resolve_seed(&mut self) -> u64 {
+        *self.seed.get_or_insert_with(rand::random)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_seed() {
+        let mut config = RandomizerConfig::default();
+        assert_eq!(config.seed, None);
+        let seed = config.resolve_seed();
+        assert_eq!(config.seed, Some(seed));
+        assert_eq!(config.resolve_seed(), seed);
+
+        let mut config = RandomizerConfig::chaos(7);
+        assert_eq!(config.resolve_seed(), 7);
+    }
+}
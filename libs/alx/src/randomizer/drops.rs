@@ -0,0 +1,132 @@
+//! Enemy item drop randomization.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::entries::Enemy;
+use crate::items::{ItemCategory, ItemDatabase};
+
+/// Options for [`randomize_enemy_drops`].
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", default)]
+pub struct DropOptions {
+    /// How strongly valuable items go to tough enemies, from 0.0 (plain
+    /// shuffle) to 1.0 (strictly by value). Clamped to that range.
+    pub bias: f32,
+}
+
+/// Shuffle item drops between enemies.
+///
+/// Only the item and its amount move; each slot keeps its drop
+/// probability. Empty slots and gold drops are left in place.
+///
+/// With a non-zero bias, drops are ranked by buy price and slots by enemy
+/// toughness (level, then max HP), and the two rankings are paired up
+/// with `1.0 - bias` worth of noise mixed in.
+pub fn randomize_enemy_drops<R: Rng + ?Sized>(
+    enemies: &mut [Enemy],
+    item_db: &ItemDatabase,
+    options: &DropOptions,
+    rng: &mut R,
+) {
+    let bias = options.bias.clamp(0.0, 1.0);
+
+    // (enemy index, drop index) of every drop that takes part
+    let slots: Vec<(usize, usize)> = enemies
+        .iter()
+        .enumerate()
+        .flat_map(|(e, enemy)| {
+            enemy
+                .item_drops
+                .iter()
+                .enumerate()
+                .filter(|(_, drop)| is_shuffleable(drop.item_id))
+                .map(move |(d, _)| (e, d))
+        })
+        .collect();
+    if slots.len() < 2 {
+        return;
+    }
+
+    let items: Vec<(i16, i16)> = slots
+        .iter()
+        .map(|&(e, d)| {
+            let drop = &enemies[e].item_drops[d];
+            (drop.item_id, drop.amount)
+        })
+        .collect();
+
+    let mut slot_ranking: Vec<usize> = (0..slots.len()).collect();
+    slot_ranking.sort_by_key(|&i| {
+        let enemy = &enemies[slots[i].0];
+        (enemy.level, enemy.max_hp)
+    });
+    let mut item_ranking: Vec<usize> = (0..items.len()).collect();
+    item_ranking.sort_by_key(|&i| item_db.buy_price(items[i].0 as i32).unwrap_or(0));
+
+    let slot_ranking = blur_ranking(slot_ranking, bias, rng);
+    let item_ranking = blur_ranking(item_ranking, bias, rng);
+
+    for (slot, item) in slot_ranking.into_iter().zip(item_ranking) {
+        let (e, d) = slots[slot];
+        let (item_id, amount) = items[item];
+        let drop = &mut enemies[e].item_drops[d];
+        drop.item_id = item_id;
+        drop.amount = amount;
+    }
+}
+
+/// Check if a drop's item should be moved around (not empty, not gold).
+fn is_shuffleable(item_id: i16) -> bool {
+    !matches!(
+        ItemCategory::from_id(item_id as i32),
+        ItemCategory::Gold | ItemCategory::Unknown
+    )
+}
+
+/// Reorder a ranking by `bias * rank + (1 - bias) * noise`.
+///
+/// A bias of 1.0 keeps the ranking as is; 0.0 is a uniform shuffle.
+fn blur_ranking<R: Rng + ?Sized>(ranking: Vec<usize>, bias: f32, rng: &mut R) -> Vec<usize> {
+    let len = ranking.len() as f32;
+    let mut keyed: Vec<(f32, usize)> = ranking
+        .into_iter()
+        .enumerate()
+        .map(|(rank, i)| {
+            let noise: f32 = rng.gen();
+            (bias * rank as f32 / len + (1.0 - bias) * noise, i)
+        })
+        .collect();
+    keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+    keyed.into_iter().map(|(_, i)| i).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::rng_from_seed;
+
+    #[test]
+    fn test_blur_ranking_full_bias_keeps_order() {
+        let mut rng = rng_from_seed(7);
+        let ranking = vec![3, 1, 4, 0, 2];
+        assert_eq!(blur_ranking(ranking.clone(), 1.0, &mut rng), ranking);
+    }
+
+    #[test]
+    fn test_blur_ranking_is_a_permutation() {
+        let mut rng = rng_from_seed(7);
+        let mut shuffled = blur_ranking((0..50).collect(), 0.0, &mut rng);
+        assert_ne!(shuffled, (0..50).collect::<Vec<_>>());
+        shuffled.sort();
+        assert_eq!(shuffled, (0..50).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_gold_and_empty_drops_stay_put() {
+        assert!(is_shuffleable(0));
+        assert!(is_shuffleable(0x1FF));
+        assert!(!is_shuffleable(-1));
+        assert!(!is_shuffleable(0x200));
+    }
+}
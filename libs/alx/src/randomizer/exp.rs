@@ -0,0 +1,166 @@
+//! EXP adjustments.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::entries::{ExpBoost, MagicExpCurve};
+
+/// Options for [`randomize_magic_exp_curves`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", default)]
+pub struct MagicExpOptions {
+    /// Largest relative change to each threshold, e.g. 0.25 for +/-25%.
+    /// Clamped to 0.0..=1.0.
+    pub variance: f32,
+}
+
+impl Default for MagicExpOptions {
+    fn default() -> Self {
+        Self { variance: 0.25 }
+    }
+}
+
+/// Scale every EXP value of the late-joiner boosts by `factor`.
+///
+/// Only the boosts change; the EXP curves are left alone, so a factor
+/// above 1.0 has late joiners start ahead of the party and one below 1.0
+/// behind it. Values are rounded and clamped to the `u32` range. Builds
+/// without an exp boost table read no boosts, so there is nothing to
+/// scale.
+pub fn scale_exp_boosts(boosts: &mut [ExpBoost], factor: f32) {
+    let scale = |value: &mut u32| {
+        *value = (*value as f64 * factor as f64)
+            .round()
+            .clamp(0.0, u32::MAX as f64) as u32;
+    };
+    for boost in boosts {
+        scale(&mut boost.exp);
+        scale(&mut boost.green_exp);
+        scale(&mut boost.red_exp);
+        scale(&mut boost.purple_exp);
+        scale(&mut boost.blue_exp);
+        scale(&mut boost.yellow_exp);
+        scale(&mut boost.silver_exp);
+    }
+}
+
+/// Perturb each character's magic EXP thresholds.
+///
+/// Every threshold is scaled by a random factor within `variance`, then
+/// each element's six levels are made monotonic again: a level never needs
+/// less EXP than the one before it, and levels that needed more in the
+/// original still need more.
+pub fn randomize_magic_exp_curves<R: Rng + ?Sized>(
+    curves: &mut [MagicExpCurve],
+    options: &MagicExpOptions,
+    rng: &mut R,
+) {
+    let variance = options.variance.clamp(0.0, 1.0);
+    for curve in curves {
+        for levels in [
+            &mut curve.green_exp,
+            &mut curve.red_exp,
+            &mut curve.purple_exp,
+            &mut curve.blue_exp,
+            &mut curve.yellow_exp,
+            &mut curve.silver_exp,
+        ] {
+            let original = *levels;
+            for (i, level) in levels.iter_mut().enumerate() {
+                let factor = 1.0 + rng.gen_range(-variance..=variance);
+                *level = (original[i] as f32 * factor)
+                    .round()
+                    .clamp(0.0, u16::MAX as f32) as u16;
+            }
+            make_monotonic(levels, &original);
+        }
+    }
+}
+
+/// Raise each level to at least the one before it, and strictly above it
+/// where `original` was strictly increasing.
+fn make_monotonic(levels: &mut [u16; 6], original: &[u16; 6]) {
+    for i in 1..levels.len() {
+        let floor = if original[i] > original[i - 1] {
+            levels[i - 1].saturating_add(1)
+        } else {
+            levels[i - 1]
+        };
+        levels[i] = levels[i].max(floor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::rng_from_seed;
+
+    fn boost(exp: u32) -> ExpBoost {
+        ExpBoost {
+            id: 3,
+            character_name: "Drachma".to_string(),
+            exp,
+            green_exp: exp,
+            red_exp: 0,
+            purple_exp: 3,
+            blue_exp: exp,
+            yellow_exp: exp,
+            silver_exp: u32::MAX,
+        }
+    }
+
+    #[test]
+    fn test_scale_exp_boosts() {
+        let mut boosts = vec![boost(1000)];
+        scale_exp_boosts(&mut boosts, 1.5);
+        assert_eq!(boosts[0].exp, 1500);
+        assert_eq!(boosts[0].green_exp, 1500);
+        assert_eq!(boosts[0].red_exp, 0);
+        assert_eq!(boosts[0].purple_exp, 5);
+        assert_eq!(boosts[0].silver_exp, u32::MAX);
+
+        scale_exp_boosts(&mut boosts, 0.0);
+        assert_eq!(boosts[0].exp, 0);
+    }
+
+    #[test]
+    fn test_magic_exp_curves_stay_monotonic() {
+        let curve = MagicExpCurve {
+            id: 0,
+            character_name: "Vyse".to_string(),
+            green_exp: [0, 10, 11, 12, 13, 14],
+            red_exp: [100, 200, 400, 800, 1600, 3200],
+            purple_exp: [5, 5, 5, 5, 5, 5],
+            blue_exp: [1, 2, 3, 4, 5, 65535],
+            yellow_exp: [0; 6],
+            silver_exp: [50, 100, 150, 200, 250, 300],
+        };
+        let options = MagicExpOptions { variance: 1.0 };
+
+        for seed in 0..20 {
+            let mut curves = vec![curve.clone()];
+            randomize_magic_exp_curves(&mut curves, &options, &mut rng_from_seed(seed));
+            let randomized = &curves[0];
+            for (levels, original) in [
+                (randomized.green_exp, curve.green_exp),
+                (randomized.red_exp, curve.red_exp),
+                (randomized.purple_exp, curve.purple_exp),
+                (randomized.blue_exp, curve.blue_exp),
+                (randomized.yellow_exp, curve.yellow_exp),
+                (randomized.silver_exp, curve.silver_exp),
+            ] {
+                for i in 1..6 {
+                    assert!(levels[i] >= levels[i - 1], "{:?}", levels);
+                    if original[i] > original[i - 1] && levels[i - 1] < u16::MAX {
+                        assert!(levels[i] > levels[i - 1], "{:?}", levels);
+                    }
+                }
+            }
+        }
+
+        let mut curves = vec![curve.clone()];
+        let options = MagicExpOptions { variance: 0.0 };
+        randomize_magic_exp_curves(&mut curves, &options, &mut rng_from_seed(1));
+        assert_eq!(curves[0], curve);
+    }
+}
@@ -0,0 +1,120 @@
+//! Post-randomization logic checks.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::game::GameData;
+use crate::items::ItemCategory;
+
+/// Result of [`reachability_check`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct ReachabilityReport {
+    /// Key item IDs no longer found in any chest, shop or drop (sorted)
+    pub missing: Vec<i32>,
+}
+
+impl ReachabilityReport {
+    /// Check if every key item is still obtainable.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+impl fmt::Display for ReachabilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return write!(f, "All key items are obtainable");
+        }
+        let ids: Vec<String> = self.missing.iter().map(|id| id.to_string()).collect();
+        write!(f, "Key items no longer obtainable: {}", ids.join(", "))
+    }
+}
+
+/// Get the key items placed in the world: every special item found in a
+/// chest, shop or enemy drop.
+///
+/// Call this on the vanilla data before running any passes and check the
+/// result against the randomized data with [`reachability_check`].
+pub fn key_item_ids(game_data: &GameData) -> Vec<i32> {
+    placed_item_ids(game_data)
+        .into_iter()
+        .filter(|&id| ItemCategory::from_id(id) == ItemCategory::SpecialItem)
+        .collect()
+}
+
+/// Check that each key item is still in at least one chest, shop or drop.
+///
+/// If any are missing, a pass removed them and the run should be reseeded.
+pub fn reachability_check(game_data: &GameData, key_item_ids: &[i32]) -> ReachabilityReport {
+    let placed = placed_item_ids(game_data);
+    let mut missing: Vec<i32> = key_item_ids
+        .iter()
+        .copied()
+        .filter(|id| !placed.contains(id))
+        .collect();
+    missing.sort_unstable();
+    missing.dedup();
+    ReachabilityReport { missing }
+}
+
+/// Every item ID placed in a chest, shop or enemy drop.
+fn placed_item_ids(game_data: &GameData) -> BTreeSet<i32> {
+    let chests = game_data.treasure_chests.iter().map(|c| c.item_id);
+    let shops = game_data
+        .shops
+        .iter()
+        .flat_map(|s| s.items())
+        .map(|id| id as i32);
+    let drops = game_data
+        .enemies
+        .iter()
+        .flat_map(|e| e.item_drops.iter())
+        .map(|d| d.item_id as i32);
+
+    chests
+        .chain(shops)
+        .chain(drops)
+        .filter(|&id| id >= 0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entries::Shop;
+    use crate::randomizer::chest;
+
+    #[test]
+    fn test_key_items_come_from_placements() {
+        let game_data = GameData {
+            treasure_chests: vec![chest(0, 0x150), chest(1, 240), chest(2, 0x200)],
+            shops: vec![Shop {
+                item_ids: vec![0x160, -1],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(key_item_ids(&game_data), vec![0x150, 0x160]);
+        assert!(reachability_check(&game_data, &[0x150, 0x160]).is_ok());
+    }
+
+    #[test]
+    fn test_removed_key_item_is_reported() {
+        let mut game_data = GameData {
+            treasure_chests: vec![chest(0, 0x150), chest(1, 0x151)],
+            ..Default::default()
+        };
+        let key_items = key_item_ids(&game_data);
+
+        // A pass that swaps a key item out for a potion
+        game_data.treasure_chests[1].item_id = 240;
+
+        let report = reachability_check(&game_data, &key_items);
+        assert!(!report.is_ok());
+        assert_eq!(report.missing, vec![0x151]);
+        assert_eq!(report.to_string(), "Key items no longer obtainable: 337");
+    }
+}
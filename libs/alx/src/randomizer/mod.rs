@@ -0,0 +1,147 @@
+//! Randomization passes over parsed game data.
+//!
+//! Each pass takes the entries it modifies plus an RNG; [`apply`] runs the
+//! passes enabled in a [`RandomizerConfig`] over a [`GameData`] snapshot.
+//! Nothing here touches the ISO; write the modified entries back through
+//! [`GameRoot`](crate::GameRoot).
+
+pub mod chests;
+pub mod config;
+pub mod drops;
+pub mod exp;
+pub mod logic;
+pub mod repair;
+pub mod seed;
+pub mod shops;
+pub mod spoiler;
+pub mod weapons;
+
+pub use chests::{chest_category, randomize_chests, ChestOptions};
+pub use config::RandomizerConfig;
+pub use drops::{randomize_enemy_drops, DropOptions};
+pub use exp::{randomize_magic_exp_curves, scale_exp_boosts, MagicExpOptions};
+pub use logic::{key_item_ids, reachability_check, ReachabilityReport};
+pub use repair::{fix_invalid_chests, validate_references, ReferenceReport};
+pub use seed::SeedSource;
+pub use shops::{randomize_shops, ShopOptions};
+pub use spoiler::{RandomizeSummary, SpoilerLog};
+pub use weapons::{shuffle_weapon_effects, WeaponEffectOptions};
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::error::Result;
+use crate::game::GameData;
+use crate::items::ItemDatabase;
+
+/// Pass label for [`randomize_enemy_drops`].
+pub const PASS_ENEMY_DROPS: &str = "enemy_drops";
+/// Pass label for [`randomize_shops`].
+pub const PASS_SHOPS: &str = "shops";
+/// Pass label for [`randomize_chests`].
+pub const PASS_TREASURE_CHESTS: &str = "treasure_chests";
+/// Pass label for [`randomize_magic_exp_curves`].
+pub const PASS_MAGIC_EXP_CURVES: &str = "magic_exp_curves";
+/// Pass label for [`shuffle_weapon_effects`].
+pub const PASS_WEAPON_EFFECTS: &str = "weapon_effects";
+
+/// RNG used by the randomization passes (portable, so a seed gives the
+/// same result on every platform).
+pub type RandomizerRng = ChaCha8Rng;
+
+/// Create a randomizer RNG from a seed.
+pub fn rng_from_seed(seed: u64) -> RandomizerRng {
+    RandomizerRng::seed_from_u64(seed)
+}
+
+/// Run every enabled pass over `game_data`, then check key items.
+///
+/// Each pass gets its own RNG from a [`SeedSource`], so enabling or
+/// disabling one pass doesn't change what the others do. An unset seed is
+/// picked at random and stored in `config`. The returned report lists key
+/// items the passes made unobtainable; reseed if it isn't ok.
+pub fn apply(game_data: &mut GameData, config: &mut RandomizerConfig) -> ReachabilityReport {
+    let seeds = SeedSource::new(config.resolve_seed());
+    let key_items = key_item_ids(game_data);
+    let item_db = ItemDatabase::from_game_data(
+        &game_data.weapons,
+        &game_data.armors,
+        &game_data.accessories,
+        &game_data.usable_items,
+        &game_data.special_items,
+        &game_data.ship_cannons,
+        &game_data.ship_accessories,
+        &game_data.ship_items,
+    );
+
+    if let Some(options) = &config.enemy_drops {
+        let mut rng = seeds.rng(PASS_ENEMY_DROPS);
+        randomize_enemy_drops(&mut game_data.enemies, &item_db, options, &mut rng);
+    }
+    if let Some(options) = &config.shops {
+        let mut rng = seeds.rng(PASS_SHOPS);
+        randomize_shops(&mut game_data.shops, &item_db, options, &mut rng);
+    }
+    if let Some(options) = &config.treasure_chests {
+        let mut rng = seeds.rng(PASS_TREASURE_CHESTS);
+        randomize_chests(&mut game_data.treasure_chests, &item_db, options, &mut rng);
+    }
+    if let Some(options) = &config.magic_exp_curves {
+        let mut rng = seeds.rng(PASS_MAGIC_EXP_CURVES);
+        randomize_magic_exp_curves(&mut game_data.magic_exp_curves, options, &mut rng);
+    }
+    if let Some(options) = &config.weapon_effects {
+        let mut rng = seeds.rng(PASS_WEAPON_EFFECTS);
+        shuffle_weapon_effects(
+            &mut game_data.weapons,
+            &game_data.weapon_effects,
+            options,
+            &mut rng,
+        );
+    }
+    if let Some(factor) = config.exp_scale {
+        scale_exp_boosts(&mut game_data.exp_boosts, factor);
+    }
+
+    reachability_check(game_data, &key_items)
+}
+
+/// Preview a run: randomize a copy of `vanilla` and report what would
+/// change, without touching `vanilla` or the ISO.
+///
+/// With the same seed this gives the same spoiler as saving the run with
+/// [`GameRoot::save_as_randomized`](crate::GameRoot::save_as_randomized).
+/// An unset seed is picked at random and reported in the spoiler.
+pub fn plan(
+    vanilla: &GameData,
+    config: &RandomizerConfig,
+) -> Result<(SpoilerLog, ReachabilityReport)> {
+    let (_, spoiler, reachability) = randomize(vanilla, config)?;
+    Ok((spoiler, reachability))
+}
+
+/// Run [`apply`] on a copy of `vanilla`, returning the randomized data and
+/// what changed.
+pub(crate) fn randomize(
+    vanilla: &GameData,
+    config: &RandomizerConfig,
+) -> Result<(GameData, SpoilerLog, ReachabilityReport)> {
+    let mut data = vanilla.clone();
+    let mut config = config.clone();
+    let reachability = apply(&mut data, &mut config);
+    let spoiler = SpoilerLog {
+        seed: config.resolve_seed(),
+        changes: vanilla.diff(&data)?,
+    };
+    Ok((data, spoiler, reachability))
+}
+
+/// A chest holding one of `item_id`, for the pass tests.
+#[cfg(test)]
+pub(crate) fn chest(id: u32, item_id: i32) -> crate::entries::TreasureChest {
+    crate::entries::TreasureChest {
+        id,
+        item_id,
+        item_amount: 1,
+    }
+}
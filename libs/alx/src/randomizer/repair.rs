@@ -0,0 +1,113 @@
+//! Checks and repairs for entries that reference missing data.
+
+use std::fmt;
+
+use crate::entries::TreasureChest;
+use crate::game::GameData;
+use crate::items::ItemDatabase;
+
+/// Result of [`validate_references`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ReferenceReport {
+    /// (chest ID, item ID) of chests holding an item that doesn't exist
+    pub invalid_chests: Vec<(u32, i32)>,
+}
+
+impl ReferenceReport {
+    /// Check if every reference resolves.
+    pub fn is_ok(&self) -> bool {
+        self.invalid_chests.is_empty()
+    }
+}
+
+impl fmt::Display for ReferenceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return write!(f, "All references are valid");
+        }
+        let chests: Vec<String> = self
+            .invalid_chests
+            .iter()
+            .map(|(chest, item)| format!("chest {} (item {})", chest, item))
+            .collect();
+        write!(f, "Invalid item references: {}", chests.join(", "))
+    }
+}
+
+/// Find entries that reference items missing from `item_db`.
+pub fn validate_references(game_data: &GameData, item_db: &ItemDatabase) -> ReferenceReport {
+    ReferenceReport {
+        invalid_chests: game_data
+            .treasure_chests
+            .iter()
+            .filter(|chest| !is_valid_chest_item(chest.item_id, item_db))
+            .map(|chest| (chest.id, chest.item_id))
+            .collect(),
+    }
+}
+
+/// Replace items that don't exist in `item_db` with `fallback`.
+///
+/// Empty and gold chests are left alone. A repaired chest's amount is
+/// clamped to the fallback's range, so it can be written. Returns the IDs of
+/// the repaired chests.
+pub fn fix_invalid_chests(
+    chests: &mut [TreasureChest],
+    item_db: &ItemDatabase,
+    fallback: i32,
+) -> Vec<u32> {
+    let mut fixed = Vec::new();
+    for chest in chests.iter_mut() {
+        if !is_valid_chest_item(chest.item_id, item_db) {
+            chest.item_id = fallback;
+            chest.clamp_amount();
+            fixed.push(chest.id);
+        }
+    }
+    fixed
+}
+
+/// Check if a chest item is empty, gold, or a known item.
+fn is_valid_chest_item(item_id: i32, item_db: &ItemDatabase) -> bool {
+    item_id == -1 || item_db.is_gold(item_id) || item_db.get_name(item_id).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::chest;
+
+    #[test]
+    fn test_fix_invalid_chests() {
+        let mut db = ItemDatabase::new();
+        db.insert(240, "Sacri Crystal");
+        let game_data = GameData {
+            treasure_chests: vec![
+                chest(0, 240),
+                chest(1, 0x1A0),
+                chest(2, -1),
+                chest(3, 0x205),
+                chest(4, -7),
+            ],
+            ..Default::default()
+        };
+
+        let report = validate_references(&game_data, &db);
+        assert_eq!(report.invalid_chests, vec![(1, 0x1A0), (4, -7)]);
+        assert!(!report.is_ok());
+
+        let mut chests = game_data.treasure_chests.clone();
+        chests[4].item_amount = 5000;
+        assert_eq!(fix_invalid_chests(&mut chests, &db, 240), vec![1, 4]);
+        assert_eq!(chests[1].item_id, 240);
+        assert_eq!(chests[4].item_id, 240);
+        assert_eq!(chests[4].item_amount, 99);
+        assert_eq!(chests[3], game_data.treasure_chests[3]);
+
+        let repaired = GameData {
+            treasure_chests: chests,
+            ..Default::default()
+        };
+        assert!(validate_references(&repaired, &db).is_ok());
+    }
+}
@@ -0,0 +1,75 @@
+//! Per-pass seed derivation.
+
+use rand::SeedableRng;
+
+use super::RandomizerRng;
+
+/// Derives an independent RNG for each randomization pass from one seed.
+///
+/// Each pass gets its own sub-seed from the run seed and the pass label,
+/// so reordering passes or adding new ones leaves the others' output
+/// unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeedSource {
+    seed: u64,
+}
+
+impl SeedSource {
+    /// Create a seed source for a run.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Get the run seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Derive the sub-seed for a pass.
+    ///
+    /// Uses FNV-1a rather than `std`'s hasher, whose output may change
+    /// between Rust releases and would silently change every seed.
+    pub fn sub_seed(&self, pass: &str) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        self.seed
+            .to_le_bytes()
+            .iter()
+            .chain(pass.as_bytes())
+            .fold(FNV_OFFSET, |hash, &byte| {
+                (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// Create the RNG for a pass.
+    pub fn rng(&self, pass: &str) -> RandomizerRng {
+        RandomizerRng::seed_from_u64(self.sub_seed(pass))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sub_seeds_differ_per_pass() {
+        let source = SeedSource::new(42);
+        assert_ne!(source.sub_seed("shops"), source.sub_seed("enemy_drops"));
+        assert_ne!(
+            source.sub_seed("shops"),
+            SeedSource::new(43).sub_seed("shops")
+        );
+    }
+
+    #[test]
+    fn test_sub_seeds_are_stable() {
+        let source = SeedSource::new(42);
+        assert_eq!(
+            source.sub_seed("shops"),
+            SeedSource::new(42).sub_seed("shops")
+        );
+        // Pinned so an accidental change to the derivation shows up here
+        assert_eq!(SeedSource::new(42).sub_seed("shops"), 0x516f_97b9_f6af_6d70);
+    }
+}
@@ -0,0 +1,146 @@
+//! Shop inventory randomization.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::entries::Shop;
+use crate::items::{ItemCategory, ItemDatabase};
+
+/// Options for [`randomize_shops`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", default)]
+pub struct ShopOptions {
+    /// Allow items with a buy price of 0 into shops. These can't actually
+    /// be bought, so a needed item placed there would be unobtainable.
+    pub allow_unpriced: bool,
+}
+
+/// Replace each shop's stock with random items of the same kind.
+///
+/// Every stocked slot gets a random item from the same [`ItemCategory`]
+/// (so weapon shops still sell weapons), without repeats within a shop.
+/// Shops keep their number of items. Unless `allow_unpriced` is set,
+/// items with a buy price of 0 are never picked; if a slot has no priced
+/// candidate left it is dropped.
+///
+/// Special items are key items (see [`key_item_ids`](super::key_item_ids)),
+/// so shops keep stocking the ones they sell.
+pub fn randomize_shops<R: Rng + ?Sized>(
+    shops: &mut [Shop],
+    item_db: &ItemDatabase,
+    options: &ShopOptions,
+    rng: &mut R,
+) {
+    // Sorted so the result only depends on the seed
+    let mut candidates: Vec<i32> = item_db
+        .iter()
+        .map(|(&id, _)| id)
+        .filter(|&id| (0..0x200).contains(&id))
+        .filter(|&id| options.allow_unpriced || item_db.buy_price(id).unwrap_or(0) > 0)
+        .collect();
+    candidates.sort_unstable();
+
+    for shop in shops.iter_mut() {
+        let slot_count = shop.item_ids.len();
+        let mut stock: Vec<i16> = Vec::with_capacity(slot_count);
+
+        for original in shop.items() {
+            let category = ItemCategory::from_id(original as i32);
+            if category == ItemCategory::SpecialItem {
+                stock.push(original);
+                continue;
+            }
+            let choices: Vec<i32> = candidates
+                .iter()
+                .copied()
+                .filter(|&id| ItemCategory::from_id(id) == category)
+                .filter(|&id| !stock.contains(&(id as i16)))
+                .collect();
+            if let Some(&id) = choices.choose(rng) {
+                stock.push(id as i16);
+            }
+        }
+
+        stock.sort_unstable();
+        stock.resize(slot_count, -1);
+        shop.item_ids = stock;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::rng_from_seed;
+
+    fn test_db() -> ItemDatabase {
+        let mut db = ItemDatabase::new();
+        for id in 0..10 {
+            db.insert(id, &format!("Weapon {}", id));
+            db.set_buy_price(id, if id % 2 == 0 { 0 } else { 100 });
+        }
+        db.insert(240, "Sacri Crystal");
+        db.set_buy_price(240, 20);
+        db
+    }
+
+    fn test_shop(items: &[i16]) -> Shop {
+        let mut item_ids = items.to_vec();
+        item_ids.resize(Shop::MAX_ITEMS, -1);
+        Shop {
+            item_ids,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_protected_shops_have_no_unpriced_items() {
+        let db = test_db();
+        let mut shops = vec![test_shop(&[0, 1, 2, 240]), test_shop(&[4, 6, 8])];
+        let mut rng = rng_from_seed(99);
+        randomize_shops(&mut shops, &db, &ShopOptions::default(), &mut rng);
+
+        for shop in &shops {
+            assert_eq!(shop.item_ids.len(), Shop::MAX_ITEMS);
+            for id in shop.items() {
+                assert!(
+                    db.buy_price(id as i32).unwrap() > 0,
+                    "item {} is unpriced",
+                    id
+                );
+            }
+        }
+        // The sacri crystal slot can only be refilled with itself
+        assert!(shops[0].items().contains(&240));
+    }
+
+    #[test]
+    fn test_unpriced_items_allowed_when_asked() {
+        let db = test_db();
+        let mut shops = vec![test_shop(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9])];
+        let mut rng = rng_from_seed(99);
+        let options = ShopOptions {
+            allow_unpriced: true,
+        };
+        randomize_shops(&mut shops, &db, &options, &mut rng);
+
+        // Ten weapon slots and ten weapons: every one must be stocked
+        assert_eq!(shops[0].items(), (0..10).collect::<Vec<i16>>());
+    }
+
+    #[test]
+    fn test_key_items_stay_in_shops() {
+        let mut db = test_db();
+        db.insert(0x150, "Key");
+        db.insert(0x151, "Other key");
+        db.set_buy_price(0x151, 50);
+        let mut shops = vec![test_shop(&[1, 0x150, 240])];
+        let mut rng = rng_from_seed(99);
+        randomize_shops(&mut shops, &db, &ShopOptions::default(), &mut rng);
+
+        // Kept even though it is unpriced, and not swapped for the other key
+        let items = shops[0].items();
+        assert!(items.contains(&0x150));
+        assert!(!items.contains(&0x151));
+    }
+}
@@ -0,0 +1,138 @@
+//! Spoiler logs and summaries of randomizer runs.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::Serialize;
+
+use super::ReachabilityReport;
+use crate::game::FieldDiff;
+
+/// Every field a randomizer run changed, for players who want to look.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpoilerLog {
+    /// Run seed
+    pub seed: u64,
+    /// Changed fields, in entry kind order
+    pub changes: Vec<FieldDiff>,
+}
+
+impl SpoilerLog {
+    /// Check if the run changed nothing.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Get a short code that is the same for every run with the same
+    /// result, so players can check they randomized identically.
+    pub fn verification_string(&self) -> String {
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(&self.seed.to_le_bytes());
+        for change in &self.changes {
+            hasher.update(
+                format!(
+                    "{} {} {} {}",
+                    change.kind, change.index, change.field, change.after
+                )
+                .as_bytes(),
+            );
+        }
+        format!("{:08X}", hasher.finalize())
+    }
+}
+
+impl fmt::Display for SpoilerLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Seed: {}", self.seed)?;
+        for change in &self.changes {
+            writeln!(
+                f,
+                "{} {}: {} {} -> {}",
+                change.kind, change.id, change.field, change.before, change.after
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Short, spoiler-free result of a randomizer run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RandomizeSummary {
+    /// Run seed, to reproduce the run
+    pub seed: u64,
+    /// Number of entries with at least one changed field
+    pub changed_entries: usize,
+    /// Number of changed fields
+    pub changed_fields: usize,
+    /// Key item check on the randomized data
+    pub reachability: ReachabilityReport,
+}
+
+impl RandomizeSummary {
+    /// Summarize a run from its spoiler log and key item check.
+    pub fn new(spoiler: &SpoilerLog, reachability: ReachabilityReport) -> Self {
+        let entries: HashSet<_> = spoiler
+            .changes
+            .iter()
+            .map(|change| (change.kind, change.index))
+            .collect();
+        Self {
+            seed: spoiler.seed,
+            changed_entries: entries.len(),
+            changed_fields: spoiler.changes.len(),
+            reachability,
+        }
+    }
+}
+
+impl fmt::Display for RandomizeSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Seed {}: changed {} fields in {} entries. {}",
+            self.seed, self.changed_fields, self.changed_entries, self.reachability
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entries::EntryKind;
+    use serde_json::json;
+
+    fn change(index: usize, field: &str) -> FieldDiff {
+        FieldDiff {
+            kind: EntryKind::Shop,
+            id: index as u32,
+            enemy_id: None,
+            filter: None,
+            index,
+            field: field.to_string(),
+            before: json!(1),
+            after: json!(2),
+        }
+    }
+
+    #[test]
+    fn test_summary_counts_entries_and_fields() {
+        let spoiler = SpoilerLog {
+            seed: 9,
+            changes: vec![change(0, "a"), change(0, "b"), change(3, "a")],
+        };
+        let summary = RandomizeSummary::new(&spoiler, ReachabilityReport::default());
+        assert_eq!(summary.seed, 9);
+        assert_eq!(summary.changed_fields, 3);
+        assert_eq!(summary.changed_entries, 2);
+        assert!(spoiler.to_string().contains("shop 3: a 1 -> 2"));
+
+        let code = spoiler.verification_string();
+        assert_eq!(code.len(), 8);
+        assert_eq!(code, spoiler.clone().verification_string());
+        let other = SpoilerLog {
+            seed: 10,
+            ..spoiler
+        };
+        assert_ne!(other.verification_string(), code);
+    }
+}
@@ -0,0 +1,135 @@
+//! Weapon on-hit effect randomization.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::entries::{Weapon, WeaponEffect};
+
+/// Options for [`shuffle_weapon_effects`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", default)]
+pub struct WeaponEffectOptions {
+    /// Share of weapons without an effect that keep having none, e.g. 0.75
+    /// to shuffle a quarter of them into the pool. Clamped to 0.0..=1.0.
+    pub keep_none_fraction: f32,
+}
+
+impl Default for WeaponEffectOptions {
+    fn default() -> Self {
+        Self {
+            keep_none_fraction: 1.0,
+        }
+    }
+}
+
+/// Shuffle on-hit effects between weapons.
+///
+/// Weapons with an effect always take part. Each weapon without one
+/// (`effect_id` -1) stays out of the shuffle with a chance of
+/// `keep_none_fraction`, otherwise its "no effect" is shuffled in too.
+/// Weapons whose effect ID isn't in `effects` are left alone, so every
+/// ID handed out is valid and the effects as a whole stay the same.
+pub fn shuffle_weapon_effects<R: Rng + ?Sized>(
+    weapons: &mut [Weapon],
+    effects: &[WeaponEffect],
+    options: &WeaponEffectOptions,
+    rng: &mut R,
+) {
+    let keep_none = options.keep_none_fraction.clamp(0.0, 1.0) as f64;
+    let is_valid = |id: i8| effects.iter().any(|effect| effect.id == id as u32);
+
+    // In weapon order so the result only depends on the seed
+    let mut pool: Vec<usize> = Vec::new();
+    for (i, weapon) in weapons.iter().enumerate() {
+        if weapon.effect_id < 0 {
+            if !rng.gen_bool(keep_none) {
+                pool.push(i);
+            }
+        } else if is_valid(weapon.effect_id) {
+            pool.push(i);
+        }
+    }
+
+    let mut ids: Vec<i8> = pool.iter().map(|&i| weapons[i].effect_id).collect();
+    ids.shuffle(rng);
+    for (i, id) in pool.into_iter().zip(ids) {
+        weapons[i].effect_id = id;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::rng_from_seed;
+
+    fn test_effects() -> Vec<WeaponEffect> {
+        (0..4)
+            .map(|id| WeaponEffect {
+                id,
+                name_jp: String::new(),
+                effect_id: 1,
+                state_id: id as i8,
+                state_miss: 0,
+            })
+            .collect()
+    }
+
+    fn test_weapons(effect_ids: &[i8]) -> Vec<Weapon> {
+        effect_ids
+            .iter()
+            .enumerate()
+            .map(|(i, &effect_id)| Weapon {
+                id: i as u32,
+                effect_id,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn sorted_ids(weapons: &[Weapon]) -> Vec<i8> {
+        let mut ids: Vec<i8> = weapons.iter().map(|w| w.effect_id).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn test_shuffle_preserves_effects() {
+        let effects = test_effects();
+        let original = [0, 1, 1, 2, 3, -1, -1, -1, 0, 2];
+        let mut weapons = test_weapons(&original);
+        let options = WeaponEffectOptions {
+            keep_none_fraction: 0.0,
+        };
+        let mut rng = rng_from_seed(5);
+        shuffle_weapon_effects(&mut weapons, &effects, &options, &mut rng);
+
+        assert_eq!(sorted_ids(&weapons), sorted_ids(&test_weapons(&original)));
+        for weapon in &weapons {
+            assert!(
+                weapon.effect_id == -1 || effects.iter().any(|e| e.id == weapon.effect_id as u32),
+                "weapon {} has invalid effect {}",
+                weapon.id,
+                weapon.effect_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_kept_none_and_invalid_effects_stay() {
+        let effects = test_effects();
+        let mut weapons = test_weapons(&[-1, 0, 1, 2, 9, -1]);
+        let mut rng = rng_from_seed(5);
+        shuffle_weapon_effects(
+            &mut weapons,
+            &effects,
+            &WeaponEffectOptions::default(),
+            &mut rng,
+        );
+
+        assert_eq!(weapons[0].effect_id, -1);
+        assert_eq!(weapons[4].effect_id, 9);
+        assert_eq!(weapons[5].effect_id, -1);
+        assert_eq!(sorted_ids(&weapons[1..4]), vec![0, 1, 2]);
+    }
+}
@@ -77,3 +77,73 @@ fn test_accessory_last_entry() {
     // It's a dummy entry in the reference data
     println!("Last accessory: ID={}, Name='{}'", last.id, last.name);
 }
+
+#[test]
+fn test_accessory_descriptions_with_configured_alignment() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let reference = game.read_accessories().unwrap();
+    let weapons = game.read_weapons().unwrap();
+
+    // The strings are stored 4-aligned, so reading them 8-aligned changes
+    // the block boundaries for accessories only
+    let mut offsets = game.offsets().clone();
+    offsets.dscr_alignment.accessory = 8;
+    game.set_offsets(offsets);
+    let accessories = game.read_accessories().unwrap();
+
+    assert_eq!(accessories.len(), reference.len());
+    for acc in &accessories {
+        assert_eq!(acc.description_size % 8, 0, "accessory {}", acc.id);
+    }
+    assert_ne!(
+        accessories
+            .iter()
+            .map(|a| a.description_pos)
+            .collect::<Vec<_>>(),
+        reference
+            .iter()
+            .map(|a| a.description_pos)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(game.read_weapons().unwrap(), weapons);
+}
+
+/// Copy `exported` without the column named `header`.
+fn without_column(exported: &[u8], header: &str) -> Vec<u8> {
+    let mut rdr = csv::Reader::from_reader(exported);
+    let headers = rdr.headers().unwrap().clone();
+    let dropped = headers.iter().position(|h| h == header).unwrap();
+    let keep = |record: &csv::StringRecord| -> Vec<String> {
+        record
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != dropped)
+            .map(|(_, cell)| cell.to_string())
+            .collect()
+    };
+
+    let mut wtr = csv::Writer::from_writer(Vec::new());
+    wtr.write_record(keep(&headers)).unwrap();
+    for record in rdr.records() {
+        wtr.write_record(keep(&record.unwrap())).unwrap();
+    }
+    wtr.into_inner().unwrap()
+}
+
+#[test]
+fn test_accessory_import_requires_every_column() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let accessories = game.read_accessories().unwrap();
+    let mut exported = Vec::new();
+    alx::csv::CsvExporter::export_accessories(&accessories, &mut exported).unwrap();
+
+    for header in ["Entry ID", "Buy"] {
+        let csv = without_column(&exported, header);
+        let err = alx::csv::CsvImporter::import_accessories(csv.as_slice()).unwrap_err();
+        assert!(err.to_string().contains(header), "{}", err);
+    }
+}
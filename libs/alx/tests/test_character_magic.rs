@@ -76,3 +76,25 @@ fn test_magic_elements() {
         }
     }
 }
+
+#[test]
+fn test_character_magic_ship_description_round_trip() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let original = game.read_character_magic().unwrap();
+    let mut magic = original.clone();
+    magic[0].ship_description = "Raises the ship's Quick".to_string();
+    magic[0].ship_description_pos = 0x1234;
+    magic[0].ship_description_size = 24;
+
+    let mut csv = Vec::new();
+    alx::csv::CsvExporter::export_character_magic(&magic, &mut csv).unwrap();
+    let imported =
+        alx::csv::CsvImporter::import_character_magic(csv.as_slice(), &original).unwrap();
+
+    assert_eq!(imported[0].ship_description, "Raises the ship's Quick");
+    assert_eq!(imported[0].ship_description_pos, 0x1234);
+    assert_eq!(imported[0].ship_description_size, 24);
+    assert_eq!(imported, magic);
+}
@@ -75,3 +75,43 @@ fn test_all_character_names() {
 
     println!("✓ All character names match!");
 }
+
+#[test]
+fn test_import_single_character_row_by_id() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let characters = game.read_characters().unwrap();
+    let item_db = game.build_item_database().unwrap();
+    let mut exported = Vec::new();
+    alx::csv::CsvExporter::export_characters(&characters, &item_db, &mut exported).unwrap();
+    let mut reader = csv::Reader::from_reader(exported.as_slice());
+    let header = reader.headers().unwrap().clone();
+
+    // Keep only Drachma's row (ID 3), cut after MAXHP (column 31) and raise it
+    let row = reader.records().nth(3).unwrap().unwrap();
+    assert_eq!(&row[0], "3");
+    let mut cells: Vec<String> = row.iter().take(32).map(String::from).collect();
+    cells[31] = (characters[3].max_hp + 100).to_string();
+
+    let mut writer = csv::WriterBuilder::new()
+        .flexible(true)
+        .from_writer(Vec::new());
+    writer.write_record(&header).unwrap();
+    writer.write_record(&cells).unwrap();
+    let partial = writer.into_inner().unwrap();
+
+    let imported =
+        alx::csv::CsvImporter::import_characters(partial.as_slice(), &characters).unwrap();
+
+    assert_eq!(imported.len(), characters.len());
+    assert_eq!(imported[3].max_hp, characters[3].max_hp + 100);
+    // Columns past the end of the row keep their values
+    assert_eq!(imported[3].power, characters[3].power);
+    assert_eq!(imported[3].magic_exp, characters[3].magic_exp);
+    for (i, (got, original)) in imported.iter().zip(&characters).enumerate() {
+        if i != 3 {
+            assert_eq!(got, original);
+        }
+    }
+}
@@ -59,3 +59,40 @@ fn test_crew_member_traits() {
 
     println!("✓ Crew member traits verified");
 }
+
+#[test]
+fn test_import_crew_member_position_by_name() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let crew = game.read_crew_members().unwrap();
+
+    // Export the crew and set Lawrence's position cells
+    let mut exported = Vec::new();
+    alx::csv::CsvExporter::export_crew_members(&crew, &mut exported).unwrap();
+    let with_position = |id: &str, name: &str| {
+        let mut reader = csv::Reader::from_reader(exported.as_slice());
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(reader.headers().unwrap()).unwrap();
+        let row = reader.records().next().unwrap().unwrap();
+        assert_eq!(&row[0], "0");
+        let mut cells: Vec<String> = row.iter().map(String::from).collect();
+        cells[2] = id.to_string();
+        cells[3] = name.to_string();
+        writer.write_record(&cells).unwrap();
+        writer.into_inner().unwrap()
+    };
+
+    let csv = with_position("", "Gunner");
+    let imported = alx::csv::CsvImporter::import_crew_members(csv.as_slice(), &crew).unwrap();
+    assert_eq!(imported[0].position_id, 2);
+
+    // The ID wins when both are given
+    let csv = with_position("1", "Gunner");
+    let imported = alx::csv::CsvImporter::import_crew_members(csv.as_slice(), &crew).unwrap();
+    assert_eq!(imported[0].position_id, 1);
+
+    let csv = with_position("", "Pirate");
+    let err = alx::csv::CsvImporter::import_crew_members(csv.as_slice(), &crew).unwrap_err();
+    assert!(err.to_string().contains("Pirate"), "{}", err);
+}
@@ -4,7 +4,9 @@ mod common;
 
 use std::collections::HashMap;
 use std::fs::File;
+use std::ops::{Deref, DerefMut};
 use std::path::Path;
+use std::sync::{MutexGuard, PoisonError};
 
 use alx::GameRoot;
 
@@ -86,8 +88,37 @@ impl RefRow {
     }
 }
 
+/// The game shared by every test in this file, locked for one test.
+///
+/// These tests only read, so one loaded game serves all of them instead of
+/// reopening the ISO for each.
+struct CachedGame(MutexGuard<'static, Option<GameRoot>>);
+
+impl Deref for CachedGame {
+    type Target = GameRoot;
+
+    fn deref(&self) -> &GameRoot {
+        self.0.as_ref().expect("ISO not loaded")
+    }
+}
+
+impl DerefMut for CachedGame {
+    fn deref_mut(&mut self) -> &mut GameRoot {
+        self.0.as_mut().expect("ISO not loaded")
+    }
+}
+
+/// Lock the shared game.
+fn cached_game() -> CachedGame {
+    // A failed test doesn't change the game, so a poisoned lock is still fine
+    let guard = common::get_cached_game()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    CachedGame(guard)
+}
+
 /// Load a reference CSV and the game, returning both.
-fn load_reference_and_game(csv_name: &str) -> (Vec<RefRow>, GameRoot) {
+fn load_reference_and_game(csv_name: &str) -> (Vec<RefRow>, CachedGame) {
     let path = Path::new(REFERENCE_CSV_DIR).join(csv_name);
     let file = File::open(&path).unwrap_or_else(|_| panic!("Failed to open {}", csv_name));
     let mut reader = csv::Reader::from_reader(file);
@@ -111,7 +142,7 @@ fn load_reference_and_game(csv_name: &str) -> (Vec<RefRow>, GameRoot) {
         })
         .collect();
 
-    let game = common::load_game();
+    let game = cached_game();
     (rows, game)
 }
 
@@ -889,7 +920,7 @@ fn test_csv_exporter_accessory_row_count() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let accessories = game.read_accessories().unwrap();
 
     let mut buffer = Vec::new();
@@ -906,7 +937,7 @@ fn test_csv_exporter_armor_row_count() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let armors = game.read_armors().unwrap();
 
     let mut buffer = Vec::new();
@@ -923,7 +954,7 @@ fn test_csv_exporter_weapon_row_count() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let weapons = game.read_weapons().unwrap();
     let weapon_effects = game.read_weapon_effects().unwrap();
 
@@ -941,7 +972,7 @@ fn test_csv_exporter_usable_item_row_count() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let items = game.read_usable_items().unwrap();
 
     let mut buffer = Vec::new();
@@ -958,7 +989,7 @@ fn test_csv_exporter_special_item_row_count() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let items = game.read_special_items().unwrap();
 
     let mut buffer = Vec::new();
@@ -975,7 +1006,7 @@ fn test_csv_exporter_character_row_count() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let characters = game.read_characters().unwrap();
     let item_db = game.build_item_database().unwrap();
 
@@ -993,7 +1024,7 @@ fn test_csv_exporter_shop_row_count() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let shops = game.read_shops().unwrap();
     let item_db = game.build_item_database().unwrap();
 
@@ -1011,7 +1042,7 @@ fn test_csv_exporter_treasure_chest_row_count() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let chests = game.read_treasure_chests().unwrap();
     let item_db = game.build_item_database().unwrap();
 
@@ -1034,7 +1065,7 @@ fn test_accessory_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_accessories().unwrap();
 
     let mut buffer = Vec::new();
@@ -1052,7 +1083,7 @@ fn test_weapon_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_weapons().unwrap();
     let weapon_effects = game.read_weapon_effects().unwrap();
 
@@ -1071,7 +1102,7 @@ fn test_armor_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_armors().unwrap();
 
     let mut buffer = Vec::new();
@@ -1089,7 +1120,7 @@ fn test_usable_item_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_usable_items().unwrap();
 
     let mut buffer = Vec::new();
@@ -1109,7 +1140,7 @@ fn test_special_item_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_special_items().unwrap();
 
     let mut buffer = Vec::new();
@@ -1129,7 +1160,7 @@ fn test_character_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_characters().unwrap();
     let item_db = game.build_item_database().unwrap();
 
@@ -1148,7 +1179,7 @@ fn test_character_magic_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_character_magic().unwrap();
 
     let mut buffer = Vec::new();
@@ -1176,7 +1207,7 @@ fn test_character_super_move_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_character_super_moves().unwrap();
 
     let mut buffer = Vec::new();
@@ -1194,7 +1225,7 @@ fn test_shop_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_shops().unwrap();
     let item_db = game.build_item_database().unwrap();
 
@@ -1215,7 +1246,7 @@ fn test_treasure_chest_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_treasure_chests().unwrap();
     let item_db = game.build_item_database().unwrap();
 
@@ -1234,7 +1265,7 @@ fn test_crew_member_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_crew_members().unwrap();
 
     let mut buffer = Vec::new();
@@ -1252,7 +1283,7 @@ fn test_playable_ship_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_playable_ships().unwrap();
 
     let mut buffer = Vec::new();
@@ -1270,7 +1301,7 @@ fn test_ship_cannon_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_ship_cannons().unwrap();
 
     let mut buffer = Vec::new();
@@ -1288,7 +1319,7 @@ fn test_ship_accessory_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_ship_accessories().unwrap();
 
     let mut buffer = Vec::new();
@@ -1306,7 +1337,7 @@ fn test_ship_item_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_ship_items().unwrap();
 
     let mut buffer = Vec::new();
@@ -1324,7 +1355,7 @@ fn test_enemy_ship_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_enemy_ships().unwrap();
 
     let mut buffer = Vec::new();
@@ -1342,7 +1373,7 @@ fn test_enemy_magic_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_enemy_magic().unwrap();
 
     let mut buffer = Vec::new();
@@ -1360,7 +1391,7 @@ fn test_enemy_super_move_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_enemy_super_moves().unwrap();
 
     let mut buffer = Vec::new();
@@ -1378,7 +1409,7 @@ fn test_swashbuckler_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_swashbucklers().unwrap();
 
     let mut buffer = Vec::new();
@@ -1396,7 +1427,7 @@ fn test_spirit_curve_csv_deep_compare() {
     skip_if_no_iso!();
     skip_if_no_reference!();
 
-    let mut game = common::load_game();
+    let mut game = cached_game();
     let data = game.read_spirit_curves().unwrap();
 
     let mut buffer = Vec::new();
@@ -1408,3 +1439,30 @@ fn test_spirit_curve_csv_deep_compare() {
 
     println!("✓ Spirit curve CSV cells match reference!");
 }
+
+#[test]
+fn test_export_kind_matches_direct_export() {
+    skip_if_no_iso!();
+
+    let mut game = cached_game();
+    let data = game.read_game_data().unwrap();
+    let item_db = game.build_item_database().unwrap();
+
+    let mut direct = Vec::new();
+    alx::csv::CsvExporter::export_shops(&data.shops, &mut direct, &item_db).unwrap();
+    let mut by_kind = Vec::new();
+    let count = alx::csv::CsvExporter::export_kind(
+        alx::entries::EntryKind::Shop,
+        &data,
+        &item_db,
+        &mut by_kind,
+    )
+    .unwrap();
+    assert_eq!(count, data.shops.len());
+    assert_eq!(by_kind, direct);
+
+    // Every kind exports from a snapshot
+    for kind in alx::entries::EntryKind::ALL {
+        alx::csv::CsvExporter::export_kind(kind, &data, &item_db, std::io::sink()).unwrap();
+    }
+}
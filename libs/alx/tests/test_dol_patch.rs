@@ -0,0 +1,38 @@
+//! Integration tests for raw DOL patches.
+
+mod common;
+
+use alx::patch::PatchList;
+
+#[test]
+fn test_apply_dol_patch() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let text = "# two patches\n00000100 DEADBEEF\n00000200 01 02 03\n";
+    let patches: PatchList = text.parse().unwrap();
+    game.apply_dol_patch(&patches).unwrap();
+
+    assert_eq!(
+        game.dol_slice(0x100..0x104).unwrap(),
+        &[0xde, 0xad, 0xbe, 0xef]
+    );
+    assert_eq!(game.dol_slice(0x200..0x203).unwrap(), &[1, 2, 3]);
+    assert_eq!(game.last_write_ranges(), &[0x100..0x104, 0x200..0x203]);
+}
+
+#[test]
+fn test_apply_dol_patch_out_of_bounds_is_atomic() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let dol_len = game.load_dol().unwrap().len();
+    let original = game.dol_slice(0x100..0x104).unwrap().to_vec();
+
+    let mut patches = PatchList::new();
+    patches.add(0x100, vec![0xff; 4]).unwrap();
+    patches.add(dol_len - 2, vec![0xff; 4]).unwrap();
+
+    assert!(game.apply_dol_patch(&patches).is_err());
+    assert_eq!(game.dol_slice(0x100..0x104).unwrap(), &original[..]);
+}
@@ -183,3 +183,139 @@ fn test_enemy_tasks() {
 
     println!("✓ Enemy tasks verified: {} tasks", tasks.len());
 }
+
+#[test]
+fn test_enemy_export_is_deterministic() {
+    skip_if_no_iso!();
+
+    let export = || {
+        let mut game = common::load_game();
+        let item_db = game.build_item_database().unwrap();
+        let (enemies, _) = game.read_enemies().unwrap();
+        let mut out = Vec::new();
+        alx::csv::CsvExporter::export_enemies(&enemies, &mut out, &item_db).unwrap();
+        out
+    };
+
+    // Each run builds fresh hash maps with new random seeds
+    let first = export();
+    for _ in 0..3 {
+        assert!(export() == first, "enemy.csv differs between exports");
+    }
+}
+
+#[test]
+fn test_enemies_by_file_matches_single_file_parse() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let item_db = game.build_item_database().unwrap();
+    let (per_file, _) = game.read_enemies_per_file().unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let paths = alx::csv::CsvExporter::export_enemies_by_file(
+        &per_file,
+        &dir.path().join("enemies"),
+        &item_db,
+    )
+    .unwrap();
+    assert!(!paths.is_empty(), "Should write at least one file");
+
+    // Parse one known ENP on its own and compare against its CSV
+    let name = game
+        .list_enp_files()
+        .unwrap()
+        .into_iter()
+        .find(|name| per_file.iter().any(|e| &e.filter == name))
+        .expect("Some ENP should contain enemies");
+    let raw = game.read_enp_file_raw(&name).unwrap();
+    let data = alx::io::decompress_aklz(&raw).unwrap();
+    let parsed = alx::io::parse_enp(&data, &name, game.version()).unwrap();
+    let mut expected = parsed.enemies.clone();
+    expected.sort_by_key(|e| e.id);
+    let mut from_file: Vec<_> = per_file
+        .iter()
+        .filter(|e| e.filter == name)
+        .cloned()
+        .collect();
+    from_file.sort_by_key(|e| e.id);
+    assert_eq!(
+        from_file, expected,
+        "{} enemies differ from a direct parse",
+        name
+    );
+    let expected: Vec<String> = expected.iter().map(|e| e.id.to_string()).collect();
+
+    let path = dir.path().join("enemies").join(format!("{}.csv", name));
+    assert!(paths.contains(&path), "Missing CSV for {}", name);
+    let mut rdr = csv::Reader::from_path(&path).unwrap();
+    let mut ids = Vec::new();
+    for record in rdr.records() {
+        let record = record.unwrap();
+        assert_eq!(&record[1], name, "Rows keep their own file as filter");
+        ids.push(record[0].to_string());
+    }
+    assert_eq!(ids, expected, "{} rows differ from a direct parse", name);
+}
+
+#[test]
+fn test_enemy_export_name_columns() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let item_db = game.build_item_database().unwrap();
+    let (enemies, _) = game.read_enemies().unwrap();
+
+    let mut out = Vec::new();
+    alx::csv::CsvExporter::export_enemies(&enemies, &mut out, &item_db).unwrap();
+
+    let mut rdr = csv::Reader::from_reader(out.as_slice());
+    let headers = rdr.headers().unwrap().clone();
+    let column = |name: &str| headers.iter().position(|h| h == name).unwrap();
+    let (id_col, filter_col) = (column("Entry ID"), column("[Filter]"));
+    let (jp_col, us_col) = (column("Entry JP Name"), column("[Entry US Name]"));
+
+    let mut checked = 0;
+    for record in rdr.records() {
+        let record = record.unwrap();
+        let id: u32 = record[id_col].parse().unwrap();
+        let us_name = alx::lookups::enemy_us_name(id);
+        assert_eq!(&record[us_col], us_name, "US name of enemy {}", id);
+        if us_name != "???" {
+            checked += 1;
+        }
+
+        // The JP column holds the stored name of an enemy with this ID
+        assert!(
+            enemies
+                .iter()
+                .any(|e| e.id == id && e.name_jp == record[jp_col]),
+            "JP name {:?} of enemy {} ({}) not found",
+            &record[jp_col],
+            id,
+            &record[filter_col]
+        );
+    }
+    assert!(checked > 0, "No rows had a mapped US name");
+}
+
+#[test]
+fn test_read_enemies_sorted_is_stable() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let (first, _) = game.read_enemies_sorted().unwrap();
+    let (second, _) = game.read_enemies_sorted().unwrap();
+    assert_eq!(first, second);
+
+    for pair in first.windows(2) {
+        assert!(
+            (pair[0].id, &pair[0].filter) <= (pair[1].id, &pair[1].filter),
+            "enemies out of order: {} {} before {} {}",
+            pair[0].id,
+            pair[0].filter,
+            pair[1].id,
+            pair[1].filter
+        );
+    }
+}
@@ -559,3 +559,19 @@ fn test_encounter_file_breakdown() {
         encounters.len()
     );
 }
+
+#[test]
+fn test_encounter_export_order_is_reproducible() {
+    skip_if_no_iso!();
+
+    let export = || {
+        let mut game = common::load_game();
+        let encounters = game.read_enemy_encounters().unwrap();
+        let mut csv_output = Vec::new();
+        CsvExporter::export_enemy_encounters(&encounters, &mut csv_output, &HashMap::new())
+            .unwrap();
+        csv_output
+    };
+
+    assert_eq!(export(), export());
+}
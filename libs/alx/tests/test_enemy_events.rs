@@ -0,0 +1,73 @@
+//! Integration tests for EnemyEvent entries (scripted battles from the EVP).
+
+mod common;
+
+use std::collections::{BTreeSet, HashMap};
+
+use alx::csv::CsvExporter;
+use alx::entries::{DEFEAT_CONDITIONS, ESCAPE_CONDITIONS};
+
+#[test]
+fn test_read_enemy_events() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let events = game.read_enemy_events().unwrap();
+    assert!(!events.is_empty(), "Should have found some enemy events");
+
+    // Every event comes from the EVP and has at least one enemy
+    for event in &events {
+        assert!(
+            event.filter.to_lowercase().ends_with(".evp"),
+            "Event {} has filter {}",
+            event.id,
+            event.filter
+        );
+        assert!(event.enemy_count() > 0, "Event {} has no enemies", event.id);
+    }
+
+    println!("✓ Read {} enemy events", events.len());
+}
+
+#[test]
+fn test_enemy_event_conditions_are_known() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let events = game.read_enemy_events().unwrap();
+
+    let defeat: BTreeSet<&str> = events.iter().map(|e| e.defeat_cond_name()).collect();
+    let escape: BTreeSet<&str> = events.iter().map(|e| e.escape_cond_name()).collect();
+    println!("Defeat conditions: {:?}", defeat);
+    println!("Escape conditions: {:?}", escape);
+
+    assert!(
+        defeat.contains(DEFEAT_CONDITIONS[0]),
+        "Some event should use {:?}",
+        DEFEAT_CONDITIONS[0]
+    );
+    assert!(
+        defeat.iter().all(|name| DEFEAT_CONDITIONS.contains(name)),
+        "Unexpected defeat condition in {:?}",
+        defeat
+    );
+    assert!(
+        escape.iter().all(|name| ESCAPE_CONDITIONS.contains(name)),
+        "Unexpected escape condition in {:?}",
+        escape
+    );
+}
+
+#[test]
+fn test_export_enemy_events() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let events = game.read_enemy_events().unwrap();
+
+    let mut out = Vec::new();
+    CsvExporter::export_enemy_events(&events, &mut out, &HashMap::new()).unwrap();
+
+    let mut rdr = csv::Reader::from_reader(out.as_slice());
+    assert_eq!(rdr.records().count(), events.len());
+}
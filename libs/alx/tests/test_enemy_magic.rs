@@ -35,3 +35,20 @@ fn test_enemy_magic_stats() {
 
     println!("✓ Enemy magic stats verified");
 }
+
+#[test]
+fn test_enemy_task_name_maps() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let (magic_names, super_move_names) = game.enemy_task_name_maps().unwrap();
+
+    assert_eq!(magic_names.len(), 36);
+    assert_eq!(magic_names.get(&0).map(String::as_str), Some("Increm"));
+    let super_moves = game.read_enemy_super_moves().unwrap();
+    assert_eq!(super_move_names.len(), super_moves.len());
+    assert_eq!(
+        super_move_names.get(&super_moves[0].id).map(String::as_str),
+        Some("Volcanic Blast")
+    );
+}
@@ -5,7 +5,10 @@
 
 mod common;
 
-use alx::io::{bake_enp_segments, decompress_aklz, A099A_BAKED_FILENAME, A099A_SEGMENTS};
+use alx::io::{
+    bake_enp_segments, decompress_aklz, dump_enp_editable, dump_enp_segments_editable, is_aklz,
+    CompressionMode, EnemyFileKind, A099A_BAKED_FILENAME, A099A_SEGMENTS,
+};
 
 /// Parse segment info from a multi-segment ENP file header.
 /// Returns Vec of (segment_name, position, size) tuples.
@@ -293,3 +296,237 @@ fn test_a099a_ep_header_structure() {
     println!("  Segments: {}", num_segments);
     println!("  Header size: {} bytes", expected_header_size);
 }
+
+#[test]
+fn test_list_enp_files() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let files = game.list_enp_files().expect("Failed to list ENP files");
+
+    // Sorted and deduplicated
+    let mut sorted = files.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(files, sorted);
+
+    for segment in A099A_SEGMENTS {
+        assert!(
+            files.iter().any(|f| f == segment),
+            "Missing a099a segment {}",
+            segment
+        );
+    }
+    assert!(files.iter().any(|f| f.ends_with("_ep.enp")));
+
+    // Listed by the same file kinds the enemy readers use
+    for file in &files {
+        assert!(
+            EnemyFileKind::classify(file).is_some_and(EnemyFileKind::is_enp),
+            "{} is not an ENP file",
+            file
+        );
+    }
+}
+
+#[test]
+fn test_read_all_enp_raw() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let names = game.list_enp_files().unwrap();
+    let files = game.read_all_enp_raw().unwrap();
+    let read: Vec<&String> = files.iter().map(|(name, _)| name).collect();
+    assert_eq!(read, names.iter().collect::<Vec<_>>());
+
+    let (_, data) = files
+        .iter()
+        .find(|(name, _)| name == "a101b_ep.enp")
+        .expect("a101b_ep.enp not read");
+    assert!(!data.is_empty());
+    assert!(!is_aklz(data), "Contents should be decompressed");
+}
+
+#[test]
+fn test_a099a_ep_dumps_each_segment() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let item_db = game.build_item_database().unwrap();
+    let raw_data = game
+        .read_enp_file_raw(A099A_BAKED_FILENAME)
+        .expect("Failed to read a099a_ep.enp");
+    let data = decompress_aklz(&raw_data).expect("Failed to decompress a099a_ep.enp");
+
+    // The whole-file dump can't see into the segments
+    let whole = dump_enp_editable(&data, A099A_BAKED_FILENAME, game.version(), &item_db).unwrap();
+    assert!(whole.enemies.is_empty());
+
+    let dumps =
+        dump_enp_segments_editable(&data, A099A_BAKED_FILENAME, game.version(), &item_db).unwrap();
+    let with_enemies: Vec<_> = dumps.iter().filter(|d| !d.enemies.is_empty()).collect();
+    assert!(
+        with_enemies.len() > 1,
+        "Expected several segments with enemies, got {}",
+        with_enemies.len()
+    );
+    for dump in &dumps {
+        assert!(
+            A099A_SEGMENTS.contains(&dump.filename.as_str()),
+            "Unexpected segment name {}",
+            dump.filename
+        );
+    }
+
+    // Single-segment files give one dump under their own name
+    let single = dump_enp_segments_editable(
+        &decompress_aklz(&game.read_enp_file_raw(A099A_SEGMENTS[0]).unwrap()).unwrap(),
+        A099A_SEGMENTS[0],
+        game.version(),
+        &item_db,
+    )
+    .unwrap();
+    assert_eq!(single.len(), 1);
+    assert_eq!(single[0].filename, A099A_SEGMENTS[0]);
+
+    println!(
+        "✓ Dumped {} a099a segments with enemies",
+        with_enemies.len()
+    );
+}
+
+#[test]
+fn test_enp_dump_meta_matches_contents() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let item_db = game.build_item_database().unwrap();
+    for filename in ["a101b_ep.enp", A099A_BAKED_FILENAME] {
+        let data = decompress_aklz(&game.read_enp_file_raw(filename).unwrap()).unwrap();
+        let dumps = dump_enp_segments_editable(&data, filename, game.version(), &item_db).unwrap();
+        for dump in &dumps {
+            assert_eq!(
+                dump.meta.enemy_count,
+                dump.enemies.len(),
+                "{}",
+                dump.filename
+            );
+            assert_eq!(
+                dump.meta.encounter_count,
+                dump.encounters.len(),
+                "{}",
+                dump.filename
+            );
+            assert_eq!(dump.meta.segment_count, dumps.len());
+            assert!(dump.meta.decompressed_size > 0);
+        }
+    }
+
+    // Older dumps without the block still load
+    let json = r#"{"filename": "a101b_ep.enp", "enemies": [], "encounters": []}"#;
+    let def: alx::io::EnpDefinition = serde_json::from_str(json).unwrap();
+    assert_eq!(def.meta, alx::io::EnpMeta::default());
+}
+
+#[test]
+fn test_write_enp_compression_modes() {
+    skip_if_no_writable_iso!();
+
+    let mut game = common::load_writable_game();
+    let filename = "a101b_ep.enp";
+    let original_raw = game.read_enp_file_raw(filename).unwrap();
+    let data = decompress_aklz(&original_raw).unwrap();
+
+    // Uncompressed data may not fit the slot; if not, nothing is written
+    match game.write_enp_file(filename, &data, CompressionMode::None) {
+        Ok(()) => {
+            let raw = game.read_enp_file_raw(filename).unwrap();
+            assert!(!is_aklz(&raw));
+            assert_eq!(raw, data);
+        }
+        Err(e) => {
+            assert!(e.to_string().contains("fit"), "{}", e);
+            assert_eq!(game.read_enp_file_raw(filename).unwrap(), original_raw);
+        }
+    }
+
+    game.write_enp_file(filename, &data, CompressionMode::Force)
+        .unwrap();
+    let raw = game.read_enp_file_raw(filename).unwrap();
+    assert!(is_aklz(&raw));
+    assert_eq!(decompress_aklz(&raw).unwrap(), data);
+
+    // Auto follows the file currently on disc, which is compressed again
+    game.write_enp_file(filename, &data, CompressionMode::Auto)
+        .unwrap();
+    let raw = game.read_enp_file_raw(filename).unwrap();
+    assert_eq!(is_aklz(&raw), is_aklz(&original_raw));
+    assert_eq!(decompress_aklz(&raw).unwrap(), data);
+}
+
+#[test]
+fn test_enemy_database_for_file_is_cached() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let filename = "a101b_ep.enp";
+    assert!(!game.is_enp_cached(filename));
+
+    let first = game.build_enemy_database_for_file(filename).unwrap();
+    assert!(game.is_enp_cached(filename));
+    let second = game.build_enemy_database_for_file(filename).unwrap();
+    assert!(!second.is_empty());
+    assert_eq!(first.len(), second.len());
+    for name in first.names() {
+        assert_eq!(
+            first.get(name).unwrap().data,
+            second.get(name).unwrap().data,
+            "{}",
+            name
+        );
+    }
+
+    game.clear_enp_cache();
+    assert!(!game.is_enp_cached(filename));
+    assert!(game
+        .build_enemy_database_for_file("missing_ep.enp")
+        .is_err());
+    assert!(!game.is_enp_cached("missing_ep.enp"));
+}
+
+#[test]
+fn test_encounter_only_json_reimports() {
+    skip_if_no_iso!();
+
+    use alx::io::{build_enp, find_missing_enemy_references, EncounterListDefinition};
+
+    let mut game = common::load_game();
+    let item_db = game.build_item_database().unwrap();
+    let filename = "a101b_ep.enp";
+    let data = decompress_aklz(&game.read_enp_file_raw(filename).unwrap()).unwrap();
+    let original = dump_enp_editable(&data, filename, game.version(), &item_db).unwrap();
+    assert!(original.encounters.len() >= 2);
+
+    // The encounter-only JSON has no enemy stats
+    let json = serde_json::to_value(original.encounters_only()).unwrap();
+    assert!(json.get("enemies").is_none());
+
+    // Swap the first two encounters and re-import
+    let mut list: EncounterListDefinition = serde_json::from_value(json).unwrap();
+    list.encounters.swap(0, 1);
+    let mut def = original.clone();
+    def.apply_encounters(list.clone()).unwrap();
+
+    let file_db = game.build_enemy_database_for_file(filename).unwrap();
+    assert!(find_missing_enemy_references(&def, &file_db, None).is_none());
+    let built = build_enp(&def, &file_db, None, &item_db).unwrap();
+    let rebuilt = dump_enp_editable(&built, filename, game.version(), &item_db).unwrap();
+
+    assert_eq!(rebuilt.enemies.len(), original.enemies.len());
+    assert_eq!(rebuilt.encounters.len(), list.encounters.len());
+    for (got, want) in rebuilt.encounters.iter().zip(&list.encounters) {
+        assert_eq!(got.initiative, want.initiative);
+        assert_eq!(got.magic_exp, want.magic_exp);
+        assert_eq!(got.enemies, want.enemies);
+    }
+}
@@ -0,0 +1,140 @@
+//! Integration tests for per-type entry counts.
+
+mod common;
+
+use alx::GameRoot;
+
+/// Assert that every count matches the length of the matching full read.
+fn assert_counts_match_reads(game: &mut GameRoot) {
+    let counts = game.entry_counts().expect("Failed to count entries");
+
+    let reads = [
+        ("accessories", game.read_accessories().unwrap().len()),
+        ("armors", game.read_armors().unwrap().len()),
+        ("weapons", game.read_weapons().unwrap().len()),
+        ("weapon_effects", game.read_weapon_effects().unwrap().len()),
+        ("usable_items", game.read_usable_items().unwrap().len()),
+        ("special_items", game.read_special_items().unwrap().len()),
+        ("characters", game.read_characters().unwrap().len()),
+        (
+            "character_magic",
+            game.read_character_magic().unwrap().len(),
+        ),
+        (
+            "character_super_moves",
+            game.read_character_super_moves().unwrap().len(),
+        ),
+        ("shops", game.read_shops().unwrap().len()),
+        (
+            "treasure_chests",
+            game.read_treasure_chests().unwrap().len(),
+        ),
+        ("crew_members", game.read_crew_members().unwrap().len()),
+        ("playable_ships", game.read_playable_ships().unwrap().len()),
+        ("ship_cannons", game.read_ship_cannons().unwrap().len()),
+        (
+            "ship_accessories",
+            game.read_ship_accessories().unwrap().len(),
+        ),
+        ("ship_items", game.read_ship_items().unwrap().len()),
+        ("enemy_ships", game.read_enemy_ships().unwrap().len()),
+        ("enemy_magic", game.read_enemy_magic().unwrap().len()),
+        (
+            "enemy_super_moves",
+            game.read_enemy_super_moves().unwrap().len(),
+        ),
+        ("swashbucklers", game.read_swashbucklers().unwrap().len()),
+        ("spirit_curves", game.read_spirit_curves().unwrap().len()),
+        ("exp_boosts", game.read_exp_boosts().unwrap().len()),
+        ("exp_curves", game.read_exp_curves().unwrap().len()),
+        (
+            "magic_exp_curves",
+            game.read_magic_exp_curves().unwrap().len(),
+        ),
+    ];
+    for (name, len) in reads {
+        assert_eq!(counts[name], len, "{} count differs from read", name);
+    }
+
+    let (enemies, tasks) = game.read_enemies().unwrap();
+    assert_eq!(counts["enemies"], enemies.len());
+    assert_eq!(counts["enemy_tasks"], tasks.len());
+}
+
+#[test]
+fn test_entry_counts_match_full_reads() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    assert_counts_match_reads(&mut game);
+}
+
+#[test]
+fn test_entry_counts_match_reads_of_short_tables() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let mut short = game.offsets().clone();
+    for range in [
+        &mut short.accessory_data,
+        &mut short.armor_data,
+        &mut short.weapon_data,
+        &mut short.weapon_effect_data,
+        &mut short.usable_item_data,
+        &mut short.special_item_data,
+        &mut short.character_data,
+        &mut short.character_magic_data,
+        &mut short.character_super_move_data,
+        &mut short.shop_data,
+        &mut short.treasure_chest_data,
+        &mut short.crew_member_data,
+        &mut short.playable_ship_data,
+        &mut short.ship_cannon_data,
+        &mut short.ship_accessory_data,
+        &mut short.ship_item_data,
+        &mut short.enemy_ship_data,
+        &mut short.enemy_magic_data,
+        &mut short.enemy_super_move_data,
+        &mut short.swashbuckler_data,
+        &mut short.spirit_curve_data,
+        &mut short.exp_curve_data,
+        &mut short.magic_exp_curve_data,
+    ] {
+        range.end = range.start + range.len() / 2;
+    }
+    if let Some(range) = short.exp_boost_data.as_mut() {
+        range.end = range.start + range.len() / 2;
+    }
+    game.set_offsets(short);
+
+    assert_counts_match_reads(&mut game);
+}
+
+#[test]
+fn test_entry_counts_cached() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let first = game.entry_counts().unwrap();
+    let second = game.entry_counts().unwrap();
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_entry_counts_follow_offsets() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let offsets = game.offsets().clone();
+    let first = game.entry_counts().unwrap();
+
+    // A shorter table counts fewer entries
+    let mut short = offsets.clone();
+    short.swashbuckler_data.end = short.swashbuckler_data.start + 34 * 2;
+    game.set_offsets(short);
+    assert_eq!(game.entry_counts().unwrap()["swashbucklers"], 2);
+
+    game.set_offsets(offsets);
+    assert_eq!(game.entry_counts().unwrap(), first);
+}
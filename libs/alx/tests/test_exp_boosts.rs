@@ -40,3 +40,23 @@ fn test_exp_boost_values() {
 
     println!("✓ Exp boost values verified");
 }
+
+#[test]
+fn test_exp_boosts_without_a_table() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let boosts = game.read_exp_boosts().unwrap();
+    let dol = game.load_dol().unwrap().to_vec();
+
+    let mut offsets = game.offsets().clone();
+    offsets.exp_boost_data = None;
+    game.set_offsets(offsets);
+
+    // Builds without the table read no boosts and ignore writes
+    assert!(game.read_exp_boosts().unwrap().is_empty());
+    game.write_exp_boosts(&[]).unwrap();
+    game.write_exp_boosts(&boosts).unwrap();
+    assert!(!game.has_unsaved_changes());
+    assert_eq!(game.load_dol().unwrap(), dol.as_slice());
+}
@@ -0,0 +1,45 @@
+//! Integration tests for ExpCurve and MagicExpCurve entries.
+
+mod common;
+
+use alx::Error;
+
+#[test]
+fn test_read_exp_curves() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let curves = game.read_exp_curves().unwrap();
+    assert_eq!(curves.len(), 6, "Expected 6 exp curves");
+
+    let magic_curves = game.read_magic_exp_curves().unwrap();
+    assert_eq!(magic_curves.len(), 6, "Expected 6 magic exp curves");
+}
+
+#[test]
+fn test_missing_level_file_is_feature_unavailable() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let mut offsets = game.offsets().clone();
+    offsets.level_file = "battle/missing.lmt".to_string();
+    game.set_offsets(offsets);
+
+    match game.read_exp_curves() {
+        Err(Error::FeatureUnavailable { feature, .. }) => assert_eq!(feature, "exp_curves"),
+        other => panic!(
+            "Expected FeatureUnavailable, got {:?}",
+            other.map(|c| c.len())
+        ),
+    }
+
+    match game.write_magic_exp_curves(&[]) {
+        Err(Error::FeatureUnavailable { feature, .. }) => {
+            assert_eq!(feature, "magic_exp_curves")
+        }
+        other => panic!("Expected FeatureUnavailable, got {:?}", other),
+    }
+
+    // DOL-backed data is unaffected
+    assert!(!game.read_spirit_curves().unwrap().is_empty());
+}
@@ -2,7 +2,13 @@
 
 mod common;
 
-use alx::game::{Platform, Region};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use alx::game::dump_quality::dol_crc32;
+use alx::game::{DumpQuality, GameRoot, Platform, Region, KNOWN_DOL_CRCS};
+use alx::io::CompressionMode;
+use alx::Error;
 
 #[test]
 fn test_open_iso() {
@@ -32,3 +38,154 @@ fn test_game_version_detection() {
     assert!(!version.is_gc_jp());
     assert!(!version.is_gc_eu());
 }
+
+#[test]
+fn test_open_rejects_tiny_file() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(&[0u8; 0x100]).unwrap();
+
+    match GameRoot::open(file.path()) {
+        Err(Error::TruncatedIso {
+            expected_min,
+            actual,
+        }) => {
+            assert_eq!(actual, 0x100);
+            assert!(expected_min > actual);
+        }
+        other => panic!("Expected TruncatedIso, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_open_rejects_truncated_iso() {
+    skip_if_no_iso!();
+
+    // Keep the header and FST but drop the bulk of the file data
+    let mut source = File::open(common::ISO_PATH).unwrap();
+    let mut header = [0u8; 12];
+    source.seek(SeekFrom::Start(0x420)).unwrap();
+    source.read_exact(&mut header).unwrap();
+    let fst_offset = u32::from_be_bytes(header[4..8].try_into().unwrap()) as usize;
+    let fs_size = u32::from_be_bytes(header[8..12].try_into().unwrap()) as usize;
+
+    let mut head = vec![0u8; fst_offset + fs_size];
+    source.seek(SeekFrom::Start(0)).unwrap();
+    source.read_exact(&mut head).unwrap();
+
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(&head).unwrap();
+
+    match GameRoot::open(file.path()) {
+        Err(Error::TruncatedIso {
+            expected_min,
+            actual,
+        }) => {
+            assert_eq!(actual, head.len() as u64);
+            assert!(expected_min > actual);
+        }
+        other => panic!("Expected TruncatedIso, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_open_readonly_refuses_writes() {
+    skip_if_no_iso!();
+
+    let mut game = GameRoot::open_readonly(std::path::Path::new(common::ISO_PATH)).unwrap();
+    assert!(game.is_read_only());
+
+    // Reads work as normal
+    let weapons = game.read_weapons().unwrap();
+    assert!(!weapons.is_empty());
+
+    // Writes fail with the read-only variant
+    assert!(matches!(game.write_weapons(&weapons), Err(Error::ReadOnly)));
+    assert!(matches!(game.save_dol(), Err(Error::ReadOnly)));
+    assert!(matches!(
+        game.write_enp_file("a101b_ep.enp", &[], CompressionMode::Auto),
+        Err(Error::ReadOnly)
+    ));
+}
+
+#[test]
+fn test_vanilla_dump_quality_is_known() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let crc = dol_crc32(game.load_dol().unwrap());
+    assert!(
+        KNOWN_DOL_CRCS.contains(&(crc, Region::Us)),
+        "Test ISO DOL CRC {:08X} is not listed in KNOWN_DOL_CRCS as a US build",
+        crc
+    );
+    assert_eq!(game.dump_quality(), DumpQuality::Known(Region::Us));
+}
+
+#[test]
+fn test_modified_dol_is_unknown() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let dol = game.load_dol().unwrap();
+    let offset = dol.len() - 4;
+    let flipped = dol[offset..].iter().map(|b| !b).collect();
+    let mut patches = alx::patch::PatchList::new();
+    patches.add(offset, flipped).unwrap();
+    game.apply_dol_patch(&patches).unwrap();
+
+    assert_eq!(game.dump_quality(), DumpQuality::Unknown);
+}
+
+#[test]
+fn test_open_fst_dir_matches_iso() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let weapons = game.read_weapons().unwrap();
+
+    // Lay out the pieces the DOL readers need as gc_fst extracts them
+    let dir = tempfile::tempdir().unwrap();
+    let systemdata = dir.path().join("&&systemdata");
+    std::fs::create_dir_all(&systemdata).unwrap();
+    let mut header = vec![0u8; 0x440];
+    File::open(common::ISO_PATH)
+        .unwrap()
+        .read_exact(&mut header)
+        .unwrap();
+    std::fs::write(systemdata.join("ISO.hdr"), header).unwrap();
+    std::fs::write(systemdata.join("Start.dol"), game.load_dol().unwrap()).unwrap();
+
+    let mut extracted = GameRoot::open_fst_dir(dir.path()).unwrap();
+    assert_eq!(extracted.version(), game.version());
+    assert_eq!(extracted.read_weapons().unwrap().len(), weapons.len());
+}
+
+#[test]
+fn test_from_bytes_matches_open() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let mut in_memory = GameRoot::from_reader(File::open(common::ISO_PATH).unwrap()).unwrap();
+
+    assert_eq!(in_memory.version(), game.version());
+    assert!(in_memory.is_read_only());
+    assert_eq!(in_memory.load_dol().unwrap(), game.load_dol().unwrap());
+    assert_eq!(
+        in_memory.read_weapons().unwrap(),
+        game.read_weapons().unwrap()
+    );
+    assert_eq!(
+        in_memory.read_exp_curves().unwrap(),
+        game.read_exp_curves().unwrap()
+    );
+    assert_eq!(
+        in_memory.read_all_enp_raw().unwrap(),
+        game.read_all_enp_raw().unwrap()
+    );
+
+    let weapons = in_memory.read_weapons().unwrap();
+    assert!(matches!(
+        in_memory.write_weapons(&weapons),
+        Err(Error::ReadOnly)
+    ));
+}
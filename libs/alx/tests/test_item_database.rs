@@ -143,3 +143,42 @@ fn test_format_shop_items() {
 
     println!("✓ Shop item formatting works!");
 }
+
+#[test]
+fn test_item_name_matches_database() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let db = game.build_item_database().unwrap();
+    for id in [0, 80, 160, 240, 320, -1, 512] {
+        assert_eq!(game.item_name(id).unwrap(), db.name_or_default(id));
+    }
+}
+
+#[test]
+fn test_game_data_all_items() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let data = game.read_game_data().unwrap();
+    let expected = data.weapons.len()
+        + data.armors.len()
+        + data.accessories.len()
+        + data.usable_items.len()
+        + data.special_items.len()
+        + data.ship_cannons.len()
+        + data.ship_accessories.len()
+        + data.ship_items.len();
+    assert_eq!(data.all_items().count(), expected);
+
+    let ship_items = data
+        .all_items()
+        .filter(|item| item.category == ItemCategory::ShipItem)
+        .count();
+    assert_eq!(ship_items, data.ship_items.len());
+
+    let cutlass = data.item(0).unwrap();
+    assert_eq!(cutlass.name, "Cutlass");
+    assert_eq!(cutlass.category, ItemCategory::Weapon);
+    assert!(data.item(-1).is_none());
+}
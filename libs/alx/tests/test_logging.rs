@@ -0,0 +1,65 @@
+//! Integration tests for library log events.
+
+mod common;
+
+use std::sync::Mutex;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Logger that records every event so tests can inspect them.
+struct CapturingLogger {
+    events: Mutex<Vec<(Level, String)>>,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.events
+            .lock()
+            .unwrap()
+            .push((record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger {
+    events: Mutex::new(Vec::new()),
+};
+
+#[test]
+fn test_enemy_export_emits_log_events() {
+    skip_if_no_iso!();
+
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Debug);
+
+    let mut game = common::load_game();
+    let item_db = game.build_item_database().unwrap();
+    let (enemies, _) = game.read_enemies().unwrap();
+    let mut out = Vec::new();
+    alx::csv::CsvExporter::export_enemies(&enemies, &mut out, &item_db).unwrap();
+
+    let events = LOGGER.events.lock().unwrap();
+    let has = |level: Level, text: &str| {
+        events
+            .iter()
+            .any(|(l, message)| *l == level && message.contains(text))
+    };
+
+    assert!(
+        has(Level::Info, "Opened"),
+        "missing open event: {:?}",
+        events
+    );
+    assert!(has(Level::Debug, "epevent.evp"), "missing EVP parse event");
+    assert!(has(Level::Debug, "_ep.enp"), "missing ENP parse event");
+    assert!(has(Level::Debug, "Merged"), "missing dedup event");
+    assert!(
+        has(Level::Debug, &format!("from {} enemies", enemies.len())),
+        "missing export event"
+    );
+}
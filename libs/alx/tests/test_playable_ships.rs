@@ -73,3 +73,26 @@ fn test_playable_ship_equipment() {
 
     println!("✓ Ship equipment verified");
 }
+
+#[test]
+fn test_playable_ship_growth_round_trip() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let original = game.read_playable_ships().unwrap();
+    let mut ships = original.clone();
+    ships[0].value = 12345;
+    ships[0].max_hp_growth = 2500;
+    ships[0].defense_growth = 7;
+    ships[0].dodge_growth = 3;
+
+    let mut csv = Vec::new();
+    alx::csv::CsvExporter::export_playable_ships(&ships, &mut csv).unwrap();
+    let imported = alx::csv::CsvImporter::import_playable_ships(csv.as_slice(), &original).unwrap();
+
+    assert_eq!(imported[0].value, 12345);
+    assert_eq!(imported[0].max_hp_growth, 2500);
+    assert_eq!(imported[0].defense_growth, 7);
+    assert_eq!(imported[0].dodge_growth, 3);
+    assert_eq!(imported, ships);
+}
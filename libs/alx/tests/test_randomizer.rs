@@ -0,0 +1,307 @@
+//! Integration tests for the randomization passes.
+
+mod common;
+
+use alx::entries::{Enemy, MagicExpCurve};
+use alx::randomizer::{
+    self, chest_category, key_item_ids, randomize_chests, randomize_enemy_drops,
+    randomize_magic_exp_curves, randomize_shops, reachability_check, rng_from_seed,
+    validate_references, ChestOptions, DropOptions, MagicExpOptions, RandomizerConfig, ShopOptions,
+};
+use alx::{ItemCategory, ItemDatabase};
+
+/// All (item ID, amount) drops that can be shuffled, sorted.
+fn shuffleable_drops(enemies: &[Enemy]) -> Vec<(i16, i16)> {
+    let mut drops: Vec<(i16, i16)> = enemies
+        .iter()
+        .flat_map(|e| e.item_drops.iter())
+        .filter(|d| (0..0x200).contains(&d.item_id))
+        .map(|d| (d.item_id, d.amount))
+        .collect();
+    drops.sort();
+    drops
+}
+
+/// Assert that every element's magic EXP levels never go down.
+fn assert_monotonic_levels(curves: &[MagicExpCurve]) {
+    for curve in curves {
+        for levels in [
+            curve.green_exp,
+            curve.red_exp,
+            curve.purple_exp,
+            curve.blue_exp,
+            curve.yellow_exp,
+            curve.silver_exp,
+        ] {
+            assert!(
+                levels.windows(2).all(|w| w[0] <= w[1]),
+                "{} has non-monotonic levels {:?}",
+                curve.character_name,
+                levels
+            );
+        }
+    }
+}
+
+/// Average buy price of the drops held by a set of enemies.
+fn average_drop_price(enemies: &[&Enemy], item_db: &ItemDatabase) -> f64 {
+    let prices: Vec<f64> = enemies
+        .iter()
+        .flat_map(|e| e.item_drops.iter())
+        .filter(|d| (0..0x200).contains(&d.item_id))
+        .map(|d| item_db.buy_price(d.item_id as i32).unwrap_or(0) as f64)
+        .collect();
+    prices.iter().sum::<f64>() / prices.len().max(1) as f64
+}
+
+#[test]
+fn test_randomize_enemy_drops_keeps_the_pool() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let item_db = game.build_item_database().unwrap();
+    let (original, _) = game.read_enemies().unwrap();
+
+    let mut enemies = original.clone();
+    let mut rng = rng_from_seed(1234);
+    randomize_enemy_drops(&mut enemies, &item_db, &DropOptions::default(), &mut rng);
+
+    assert_eq!(shuffleable_drops(&enemies), shuffleable_drops(&original));
+    assert_ne!(enemies, original, "Drops should have moved");
+
+    // Same seed, same result
+    let mut again = original.clone();
+    let mut rng = rng_from_seed(1234);
+    randomize_enemy_drops(&mut again, &item_db, &DropOptions::default(), &mut rng);
+    assert_eq!(again, enemies);
+}
+
+#[test]
+fn test_full_bias_gives_tough_enemies_better_drops() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let item_db = game.build_item_database().unwrap();
+    let (mut enemies, _) = game.read_enemies().unwrap();
+
+    let mut rng = rng_from_seed(42);
+    randomize_enemy_drops(&mut enemies, &item_db, &DropOptions { bias: 1.0 }, &mut rng);
+
+    let mut with_drops: Vec<&Enemy> = enemies
+        .iter()
+        .filter(|e| e.item_drops.iter().any(|d| (0..0x200).contains(&d.item_id)))
+        .collect();
+    with_drops.sort_by_key(|e| (e.level, e.max_hp));
+    let (weak, tough) = with_drops.split_at(with_drops.len() / 2);
+
+    let weak_avg = average_drop_price(weak, &item_db);
+    let tough_avg = average_drop_price(tough, &item_db);
+    println!(
+        "Average drop price: weak {:.0}, tough {:.0}",
+        weak_avg, tough_avg
+    );
+    assert!(
+        tough_avg > weak_avg,
+        "Tough enemies should get pricier drops ({:.0} <= {:.0})",
+        tough_avg,
+        weak_avg
+    );
+}
+
+#[test]
+fn test_randomized_shops_stay_buyable() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let item_db = game.build_item_database().unwrap();
+    let original = game.read_shops().unwrap();
+    let mut shops = original.clone();
+
+    let mut rng = rng_from_seed(7);
+    randomize_shops(&mut shops, &item_db, &ShopOptions::default(), &mut rng);
+
+    for (shop, before) in shops.iter().zip(&original) {
+        for id in shop.items() {
+            // Key items are kept whatever their price
+            if ItemCategory::from_id(id as i32) == ItemCategory::SpecialItem {
+                assert!(
+                    before.items().contains(&id),
+                    "Shop {} gained key item {}",
+                    shop.id,
+                    id
+                );
+                continue;
+            }
+            let price = item_db.buy_price(id as i32).unwrap_or(0);
+            assert!(price > 0, "Shop {} sells unbuyable item {}", shop.id, id);
+        }
+        for id in before.items() {
+            if ItemCategory::from_id(id as i32) == ItemCategory::SpecialItem {
+                assert!(
+                    shop.items().contains(&id),
+                    "Shop {} lost key item {}",
+                    shop.id,
+                    id
+                );
+            }
+        }
+    }
+    game.write_shops(&shops).unwrap();
+}
+
+#[test]
+fn test_drop_shuffle_keeps_key_items_reachable() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let item_db = game.build_item_database().unwrap();
+    let mut game_data = game.read_game_data().unwrap();
+    let key_items = key_item_ids(&game_data);
+    assert!(reachability_check(&game_data, &key_items).is_ok());
+
+    // Shuffling drops only moves items around, so nothing can go missing
+    let mut rng = rng_from_seed(5);
+    randomize_enemy_drops(
+        &mut game_data.enemies,
+        &item_db,
+        &DropOptions::default(),
+        &mut rng,
+    );
+    let report = reachability_check(&game_data, &key_items);
+    assert!(report.is_ok(), "{}", report);
+}
+
+#[test]
+fn test_extra_pass_does_not_change_other_passes() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let vanilla = game.read_game_data().unwrap();
+
+    // Drops run first, so adding them would shift the later passes if
+    // they shared one RNG stream
+    let mut without_drops = RandomizerConfig {
+        seed: Some(2024),
+        shops: Some(ShopOptions::default()),
+        treasure_chests: Some(ChestOptions::default()),
+        ..Default::default()
+    };
+    let mut with_drops = RandomizerConfig {
+        enemy_drops: Some(DropOptions { bias: 0.5 }),
+        ..without_drops.clone()
+    };
+
+    let mut first = vanilla.clone();
+    randomizer::apply(&mut first, &mut without_drops);
+    let mut second = vanilla.clone();
+    randomizer::apply(&mut second, &mut with_drops);
+
+    assert_eq!(first.shops, second.shops);
+    assert_eq!(first.treasure_chests, second.treasure_chests);
+    assert_ne!(second.shops, vanilla.shops);
+    assert_eq!(first.enemies, vanilla.enemies);
+    assert_ne!(second.enemies, vanilla.enemies);
+}
+
+#[test]
+fn test_unset_seed_is_recorded_and_reproducible() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let vanilla = game.read_game_data().unwrap();
+
+    let mut config = RandomizerConfig {
+        seed: None,
+        ..RandomizerConfig::chaos(0)
+    };
+    let mut first = vanilla.clone();
+    randomizer::apply(&mut first, &mut config);
+    assert!(config.seed.is_some(), "apply records the seed it picked");
+
+    // Rerunning with the recorded seed gives the same result
+    let mut rerun = config.clone();
+    let mut second = vanilla.clone();
+    randomizer::apply(&mut second, &mut rerun);
+    assert_eq!(first.shops, second.shops);
+    assert_eq!(first.treasure_chests, second.treasure_chests);
+    assert_eq!(first.enemies, second.enemies);
+}
+
+#[test]
+fn test_chest_shuffle_keeps_categories() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let item_db = game.build_item_database().unwrap();
+    let original = game.read_treasure_chests().unwrap();
+
+    let mut chests = original.clone();
+    let options = ChestOptions {
+        keep_category: true,
+    };
+    randomize_chests(&mut chests, &item_db, &options, &mut rng_from_seed(77));
+    assert_ne!(chests, original, "Chest contents should have moved");
+
+    for (before, after) in original.iter().zip(&chests) {
+        assert_eq!(
+            chest_category(before, &item_db),
+            chest_category(after, &item_db),
+            "Chest {} changed category",
+            before.id
+        );
+    }
+}
+
+#[test]
+fn test_magic_exp_curves_write_back_monotonic() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let mut curves = game.read_magic_exp_curves().unwrap();
+    let mut rng = rng_from_seed(31);
+    randomize_magic_exp_curves(&mut curves, &MagicExpOptions::default(), &mut rng);
+    game.write_magic_exp_curves(&curves).unwrap();
+
+    let reread = game.read_magic_exp_curves().unwrap();
+    assert_eq!(reread, curves);
+    assert_monotonic_levels(&reread);
+}
+
+#[test]
+fn test_chaos_run_saves_consistent_game() {
+    skip_if_no_writable_iso!();
+
+    let path = common::ensure_writable_iso().unwrap();
+    let mut game = common::load_writable_game();
+    let vanilla = game.read_game_data().unwrap();
+
+    let mut randomized = vanilla.clone();
+    let report = randomizer::apply(&mut randomized, &mut RandomizerConfig::chaos(8080));
+    assert!(report.is_ok(), "{}", report);
+    game.write_game_data(&randomized).unwrap();
+    assert!(!game.has_written_iso(), "Writes stay in memory until saved");
+    game.save_dol().unwrap();
+    game.save_level().unwrap();
+    assert!(game.has_written_iso());
+
+    let mut reopened = alx::GameRoot::open(&path).unwrap();
+    let saved = reopened.read_game_data().unwrap();
+    let item_db = reopened.build_item_database().unwrap();
+
+    // Put the copy back before asserting so a failure doesn't leak into
+    // other tests
+    reopened.write_game_data(&vanilla).unwrap();
+    reopened.save_dol().unwrap();
+    reopened.save_level().unwrap();
+
+    assert_eq!(saved.shops, randomized.shops);
+    assert_eq!(saved.treasure_chests, randomized.treasure_chests);
+    assert_eq!(saved.magic_exp_curves, randomized.magic_exp_curves);
+
+    let references = validate_references(&saved, &item_db);
+    assert!(references.is_ok(), "{}", references);
+    let reachability = reachability_check(&saved, &key_item_ids(&vanilla));
+    assert!(reachability.is_ok(), "{}", reachability);
+
+    assert_monotonic_levels(&saved.magic_exp_curves);
+}
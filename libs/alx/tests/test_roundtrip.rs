@@ -6,10 +6,11 @@
 mod common;
 
 use alx::entries::{
-    Accessory, Armor, Character, CharacterMagic, CharacterSuperMove, CrewMember, PlayableShip,
-    ShipAccessory, ShipCannon, ShipItem, Shop, SpecialItem, Swashbuckler, TreasureChest,
-    UsableItem, Weapon,
+    Accessory, Armor, Character, CharacterMagic, CharacterSuperMove, CrewMember, EntryKind,
+    PlayableShip, ShipAccessory, ShipCannon, ShipItem, Shop, SpecialItem, Swashbuckler,
+    TreasureChest, UsableItem, Weapon,
 };
+use alx::game::GameData;
 
 // =============================================================================
 // Patch Roundtrip Tests (read from ISO, patch back, compare)
@@ -109,3 +110,31 @@ patch_roundtrip_test!(
     treasure_chest_data,
     no_version
 );
+
+// =============================================================================
+// Per-kind reads
+// =============================================================================
+
+#[test]
+fn test_read_kind_matches_game_data() {
+    skip_if_no_iso!();
+    let mut game = common::load_game();
+    let full = game.read_game_data().unwrap();
+
+    let mut data = GameData::default();
+    for kind in EntryKind::ALL {
+        if kind.is_enemy_file_data() {
+            assert!(game.read_kind(kind, &mut data).is_err(), "{}", kind);
+        } else {
+            game.read_kind(kind, &mut data).unwrap();
+        }
+    }
+
+    // Only the DOL and level file tables with an entry kind are read
+    data.weapon_effects = full.weapon_effects.clone();
+    data.enemies = full.enemies.clone();
+    data.enemy_tasks = full.enemy_tasks.clone();
+    data.enemy_encounters = full.enemy_encounters.clone();
+    data.enemy_events = full.enemy_events.clone();
+    assert_eq!(data, full);
+}
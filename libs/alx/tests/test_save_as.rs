@@ -0,0 +1,82 @@
+//! Integration tests for saving to a new ISO.
+
+mod common;
+
+use alx::GameRoot;
+
+#[test]
+fn test_save_as_produces_loadable_iso() {
+    skip_if_no_iso!();
+
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("saved.iso");
+
+    let mut game = common::load_game();
+    let mut weapons = game.read_weapons().unwrap();
+    weapons[0].attack = weapons[0].attack.wrapping_add(1);
+    game.write_weapons(&weapons).unwrap();
+    game.save_as(&dest).unwrap();
+
+    assert_eq!(game.iso().path(), dest.canonicalize().unwrap());
+    assert!(!dir.path().join("saved.iso.tmp").exists());
+
+    // The saved ISO loads and has the change
+    let mut saved = GameRoot::open(&dest).expect("Saved ISO should be loadable");
+    assert_eq!(saved.version().product_id, "GEAE8P");
+    let saved_weapons = saved.read_weapons().unwrap();
+    assert_eq!(saved_weapons[0].attack, weapons[0].attack);
+
+    // The source ISO is unchanged
+    let mut source = common::load_game();
+    let source_weapons = source.read_weapons().unwrap();
+    assert_ne!(source_weapons[0].attack, weapons[0].attack);
+}
+
+#[test]
+fn test_save_as_randomized() {
+    skip_if_no_iso!();
+
+    use alx::randomizer::RandomizerConfig;
+
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("randomized.iso");
+    let config = RandomizerConfig::chaos(1234);
+
+    // Enemy drops can't be saved yet. The source only needs to be readable.
+    let mut game = GameRoot::open_readonly(std::path::Path::new(common::ISO_PATH)).unwrap();
+    let with_drops = RandomizerConfig {
+        enemy_drops: Some(Default::default()),
+        ..config.clone()
+    };
+    let err = game.save_as_randomized(&with_drops, &dest).unwrap_err();
+    assert!(matches!(err, alx::Error::FeatureUnavailable { .. }));
+    assert!(!dest.exists());
+
+    let vanilla = game.read_game_data().unwrap();
+    let (planned, planned_reachability) = alx::randomizer::plan(&vanilla, &config).unwrap();
+    assert!(!dest.exists(), "Planning must not write anything");
+
+    let (spoiler, summary) = game.save_as_randomized(&config, &dest).unwrap();
+    assert_eq!(planned, spoiler, "Preview should match the saved run");
+    assert_eq!(planned_reachability, summary.reachability);
+    assert!(!spoiler.is_empty());
+    assert_eq!(spoiler.seed, 1234);
+    assert_eq!(summary.seed, 1234);
+    assert_eq!(summary.changed_fields, spoiler.changes.len());
+
+    // The source game is left as it was
+    assert!(!game.has_unsaved_changes());
+    assert!(!game.has_written_iso());
+    assert_ne!(game.iso().path(), dest.canonicalize().unwrap());
+    assert_eq!(game.read_game_data().unwrap(), vanilla);
+
+    // The saved ISO loads and has the randomized shops
+    let mut saved = GameRoot::open(&dest).expect("Randomized ISO should be loadable");
+    let mut source = common::load_game();
+    let shops = saved.read_shops().unwrap();
+    assert_ne!(shops, source.read_shops().unwrap());
+    assert!(spoiler
+        .changes
+        .iter()
+        .any(|change| change.kind == alx::entries::EntryKind::Shop));
+}
@@ -0,0 +1,115 @@
+//! Integration tests for the JSON representation of entry types.
+
+mod common;
+
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Serialize a value to JSON and back, asserting nothing was lost.
+fn assert_roundtrip<T: Serialize + DeserializeOwned + PartialEq + Debug>(label: &str, value: &T) {
+    let json = serde_json::to_string(value)
+        .unwrap_or_else(|e| panic!("Failed to serialize {}: {}", label, e));
+    let back: T = serde_json::from_str(&json)
+        .unwrap_or_else(|e| panic!("Failed to deserialize {}: {}", label, e));
+    assert_eq!(&back, value, "{} did not roundtrip through JSON", label);
+}
+
+/// Roundtrip the first entry of a list, which must not be empty.
+fn assert_first_roundtrips<T: Serialize + DeserializeOwned + PartialEq + Debug>(
+    label: &str,
+    values: &[T],
+) {
+    let first = values
+        .first()
+        .unwrap_or_else(|| panic!("No {} to roundtrip", label));
+    assert_roundtrip(label, first);
+}
+
+#[test]
+fn test_all_entry_types_roundtrip_through_json() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+
+    assert_first_roundtrips("accessory", &game.read_accessories().unwrap());
+    assert_first_roundtrips("armor", &game.read_armors().unwrap());
+    assert_first_roundtrips("weapon", &game.read_weapons().unwrap());
+    assert_first_roundtrips("weapon effect", &game.read_weapon_effects().unwrap());
+    assert_first_roundtrips("usable item", &game.read_usable_items().unwrap());
+    assert_first_roundtrips("special item", &game.read_special_items().unwrap());
+    assert_first_roundtrips("character", &game.read_characters().unwrap());
+    assert_first_roundtrips("character magic", &game.read_character_magic().unwrap());
+    assert_first_roundtrips(
+        "character super move",
+        &game.read_character_super_moves().unwrap(),
+    );
+    assert_first_roundtrips("shop", &game.read_shops().unwrap());
+    assert_first_roundtrips("treasure chest", &game.read_treasure_chests().unwrap());
+    assert_first_roundtrips("crew member", &game.read_crew_members().unwrap());
+    assert_first_roundtrips("playable ship", &game.read_playable_ships().unwrap());
+    assert_first_roundtrips("ship cannon", &game.read_ship_cannons().unwrap());
+    assert_first_roundtrips("ship accessory", &game.read_ship_accessories().unwrap());
+    assert_first_roundtrips("ship item", &game.read_ship_items().unwrap());
+    assert_first_roundtrips("enemy ship", &game.read_enemy_ships().unwrap());
+    assert_first_roundtrips("enemy magic", &game.read_enemy_magic().unwrap());
+    assert_first_roundtrips("enemy super move", &game.read_enemy_super_moves().unwrap());
+    assert_first_roundtrips("swashbuckler", &game.read_swashbucklers().unwrap());
+    assert_first_roundtrips("spirit curve", &game.read_spirit_curves().unwrap());
+    assert_first_roundtrips("exp boost", &game.read_exp_boosts().unwrap());
+    assert_first_roundtrips("exp curve", &game.read_exp_curves().unwrap());
+    assert_first_roundtrips("magic exp curve", &game.read_magic_exp_curves().unwrap());
+
+    let (enemies, tasks) = game.read_enemies().unwrap();
+    assert_first_roundtrips("enemy", &enemies);
+    assert_first_roundtrips("enemy task", &tasks);
+
+    // Slot names are skipped in JSON; the readers leave them empty so the
+    // raw entries still compare equal
+    assert_first_roundtrips("enemy encounter", &game.read_enemy_encounters().unwrap());
+    assert_first_roundtrips("enemy event", &game.read_enemy_events().unwrap());
+}
+
+#[test]
+fn test_entry_json_uses_snake_case_fields() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let weapon = &game.read_weapons().unwrap()[0];
+    let value = serde_json::to_value(weapon).unwrap();
+
+    let object = value
+        .as_object()
+        .expect("Weapon should serialize as an object");
+    assert!(object.contains_key("character_id"));
+    assert!(object.contains_key("effect_id"));
+}
+
+#[test]
+fn test_weapon_effect_json_matches_description() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let effects = game.read_weapon_effects().unwrap();
+    let effect = effects
+        .iter()
+        .find(|effect| effect.state_name().is_some())
+        .expect("Some weapon effect should inflict a state");
+    let value = serde_json::to_value(effect).unwrap();
+
+    let state_id = value["state_id"].as_i64().unwrap() as i8;
+    let state_miss = value["state_miss"].as_i64().unwrap();
+    assert_eq!(
+        effect.description(),
+        format!(
+            "{} by {}%",
+            alx::lookups::state_name(state_id),
+            100 - state_miss
+        )
+    );
+
+    // Editors get weapon effects with the rest of the game data
+    let data = serde_json::to_value(alx::game::GameData::default()).unwrap();
+    assert!(data.get("weapon_effects").is_some());
+}
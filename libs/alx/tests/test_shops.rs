@@ -75,3 +75,40 @@ fn test_shop_item_counts() {
     println!("  Medium (11-20): {}", medium);
     println!("  Large (21+): {}", large);
 }
+
+#[test]
+fn test_write_shops_rejects_too_many_items() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let mut shops = game.read_shops().unwrap();
+    let original = shops[0].item_ids.clone();
+
+    // A 49th item has no slot; writing it must fail rather than drop it
+    shops[0]
+        .item_ids
+        .resize(alx::entries::Shop::MAX_ITEMS + 1, 0);
+    let result = game.write_shops(&shops);
+    assert!(matches!(result, Err(alx::Error::ValidationError(_))));
+
+    // Nothing was patched
+    assert_eq!(game.read_shops().unwrap()[0].item_ids, original);
+}
+
+#[test]
+fn test_write_shops_pads_short_item_list() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let mut shops = game.read_shops().unwrap();
+    let max = alx::entries::Shop::MAX_ITEMS;
+
+    // Fill every slot, then drop the last one
+    shops[0].item_ids = vec![0; max - 1];
+    game.write_shops(&shops).unwrap();
+
+    let written = &game.read_shops().unwrap()[0].item_ids;
+    assert_eq!(written.len(), max);
+    assert_eq!(written[..max - 1], vec![0; max - 1][..]);
+    assert_eq!(written[max - 1], -1);
+}
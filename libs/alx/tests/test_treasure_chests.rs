@@ -81,3 +81,64 @@ fn test_treasure_chest_moonberry() {
     // Moonberries are important collectibles
     assert!(!moonberry_chests.is_empty(), "Should have Moonberry chests");
 }
+
+#[test]
+fn test_write_treasure_chests_rejects_out_of_range_amount() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let mut chests = game.read_treasure_chests().unwrap();
+    // Vanilla chests are all in range
+    for chest in &chests {
+        chest.validate_for_write().unwrap();
+    }
+
+    // First chest holds Sacri Crystals
+    chests[0].item_amount = 1000;
+    match game.write_treasure_chests(&chests) {
+        Err(alx::Error::ValidationError(message)) => assert!(
+            message.contains("amount must be between 1 and 99"),
+            "unexpected message: {}",
+            message
+        ),
+        other => panic!("Expected ValidationError, got {:?}", other),
+    }
+    assert!(game.last_write_ranges().is_empty());
+
+    assert_eq!(chests[0].clamp_amount(), 99);
+    game.write_treasure_chests(&chests).unwrap();
+    assert_eq!(game.read_treasure_chests().unwrap()[0].item_amount, 99);
+}
+
+#[test]
+fn test_treasure_chest_import_reads_columns_by_header() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let chests = game.read_treasure_chests().unwrap();
+    let item_db = game.build_item_database().unwrap();
+    let mut exported = Vec::new();
+    alx::csv::CsvExporter::export_treasure_chests(&chests, &mut exported, &item_db).unwrap();
+
+    // Reversed columns with a "Notes" column in the middle
+    let mut rdr = csv::Reader::from_reader(exported.as_slice());
+    let mut edited = csv::Writer::from_writer(Vec::new());
+    let mut headers: Vec<String> = rdr
+        .headers()
+        .unwrap()
+        .iter()
+        .rev()
+        .map(String::from)
+        .collect();
+    headers.insert(1, "Notes".to_string());
+    edited.write_record(&headers).unwrap();
+    for record in rdr.records() {
+        let mut row: Vec<String> = record.unwrap().iter().rev().map(String::from).collect();
+        row.insert(1, "42".to_string());
+        edited.write_record(&row).unwrap();
+    }
+    let edited = edited.into_inner().unwrap();
+
+    let imported = alx::csv::CsvImporter::import_treasure_chests(edited.as_slice()).unwrap();
+    assert_eq!(imported, chests);
+}
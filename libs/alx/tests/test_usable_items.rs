@@ -81,3 +81,90 @@ fn test_usable_item_effects() {
     assert!(hp_recover > 0, "Should have HP recovery items");
     assert!(mp_recover > 0, "Should have MP recovery items");
 }
+
+#[test]
+fn test_usable_item_consume_percent_round_trip() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let original = game.read_usable_items().unwrap();
+    let mut items = original.clone();
+    items[0].consume_percent = 42;
+    items[1].consume_percent = 0;
+
+    let mut csv = Vec::new();
+    alx::csv::CsvExporter::export_usable_items(&items, &mut csv).unwrap();
+    let imported = alx::csv::CsvImporter::import_usable_items(csv.as_slice(), &original).unwrap();
+
+    assert_eq!(imported[0].consume_percent, 42);
+    assert_eq!(imported[1].consume_percent, 0);
+    assert_eq!(imported, items);
+}
+
+#[test]
+fn test_usable_item_import_matches_export_columns() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let items = game.read_usable_items().unwrap();
+    let mut exported = Vec::new();
+    alx::csv::CsvExporter::export_usable_items(&items, &mut exported).unwrap();
+
+    // Import over blanked entries, so every field has to come from its own column
+    let blank: Vec<_> = items
+        .iter()
+        .map(|item| alx::entries::UsableItem {
+            id: item.id,
+            name: item.name.clone(),
+            description: item.description.clone(),
+            description_pos: item.description_pos,
+            description_size: item.description_size,
+            ..Default::default()
+        })
+        .collect();
+    let imported = alx::csv::CsvImporter::import_usable_items(exported.as_slice(), &blank).unwrap();
+    assert_eq!(imported, items);
+
+    // Reversed columns still import by header name
+    let mut rdr = csv::Reader::from_reader(exported.as_slice());
+    let mut reversed = csv::Writer::from_writer(Vec::new());
+    let headers = rdr.headers().unwrap().clone();
+    reversed.write_record(headers.iter().rev()).unwrap();
+    for record in rdr.records() {
+        reversed.write_record(record.unwrap().iter().rev()).unwrap();
+    }
+    let reversed = reversed.into_inner().unwrap();
+    let imported = alx::csv::CsvImporter::import_usable_items(reversed.as_slice(), &blank).unwrap();
+    assert_eq!(imported, items);
+}
+
+#[test]
+fn test_usable_item_import_rejects_bad_flags() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let items = game.read_usable_items().unwrap();
+    let mut exported = Vec::new();
+    alx::csv::CsvExporter::export_usable_items(&items, &mut exported).unwrap();
+
+    let mut rdr = csv::Reader::from_reader(exported.as_slice());
+    let headers = rdr.headers().unwrap().clone();
+    let flags_col = headers.iter().position(|h| h == "Occasion Flags").unwrap();
+    let mut edited = csv::Writer::from_writer(Vec::new());
+    edited.write_record(&headers).unwrap();
+    for (i, record) in rdr.records().enumerate() {
+        let mut cells: Vec<String> = record.unwrap().iter().map(str::to_string).collect();
+        if i == 0 {
+            cells[flags_col] = "0b2".to_string();
+        }
+        edited.write_record(&cells).unwrap();
+    }
+    let edited = edited.into_inner().unwrap();
+
+    let result = alx::csv::CsvImporter::import_usable_items(edited.as_slice(), &items);
+    assert!(
+        matches!(result, Err(alx::Error::ValidationError(_))),
+        "Invalid flags should be rejected, got {:?}",
+        result.map(|_| ())
+    );
+}
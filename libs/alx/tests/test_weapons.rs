@@ -100,3 +100,147 @@ fn test_weapon_effects() {
         "Some weapons should have effects"
     );
 }
+
+#[test]
+fn test_write_weapons_rejects_out_of_range_id() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let mut weapons = game.read_weapons().unwrap();
+
+    // An ID past the weapon table would otherwise be patched over the next table
+    weapons[0].id = alx::game::id_ranges::WEAPON.end;
+    let result = game.write_weapons(&weapons);
+    assert!(matches!(result, Err(alx::Error::ValidationError(_))));
+}
+
+#[test]
+fn test_write_weapons_rejects_wrong_row_count() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let weapons = game.read_weapons().unwrap();
+    let weapon_effects = game.read_weapon_effects().unwrap();
+    let mut csv = Vec::new();
+    alx::csv::CsvExporter::export_weapons(&weapons, &mut csv, &weapon_effects).unwrap();
+
+    // Drop the last row
+    let text = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    let truncated = lines[..lines.len() - 1].join("\n");
+    let imported = alx::csv::CsvImporter::import_weapons(truncated.as_bytes()).unwrap();
+    assert_eq!(imported.len(), weapons.len() - 1);
+
+    match game.write_weapons(&imported) {
+        Err(alx::Error::ValidationError(message)) => {
+            assert_eq!(message, "Expected 80 weapons, got 79")
+        }
+        other => panic!("Expected ValidationError, got {:?}", other),
+    }
+    assert!(game.last_write_ranges().is_empty());
+}
+
+#[test]
+fn test_write_ranges_recorded() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let accessories = game.read_accessories().unwrap();
+    let weapons = game.read_weapons().unwrap();
+    assert!(game.last_write_ranges().is_empty());
+
+    game.write_accessories(&accessories).unwrap();
+    game.write_weapons(&weapons).unwrap();
+
+    let ranges = game.last_write_ranges().to_vec();
+    assert_eq!(ranges.len(), 2);
+    assert_eq!(ranges[0], game.offsets().accessory_data);
+    assert_eq!(ranges[1], game.offsets().weapon_data);
+    assert!(ranges[0].end <= ranges[1].start || ranges[1].end <= ranges[0].start);
+
+    game.clear_write_ranges();
+    assert!(game.last_write_ranges().is_empty());
+}
+
+#[test]
+fn test_weapon_character_names() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let weapons = game.read_weapons().unwrap();
+
+    let expected = ["Vyse", "Aika", "Fina", "Drachma", "Enrique", "Gilder"];
+    for (id, name) in expected.into_iter().enumerate() {
+        let weapon = weapons
+            .iter()
+            .find(|w| w.character_id == id as i8)
+            .unwrap_or_else(|| panic!("No weapon for character {}", id));
+        assert_eq!(weapon.character_name(), name, "weapon {}", weapon.id);
+    }
+    assert_eq!(weapons[0].character_name(), "Vyse");
+
+    // The table matches the names stored in the game
+    for character in game.read_characters().unwrap() {
+        assert_eq!(
+            alx::lookups::character_name(character.id as i8),
+            character.name
+        );
+    }
+}
+
+#[test]
+fn test_reset_weapons_to_vanilla() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let original = game.read_weapons().unwrap();
+    let accessories = game.read_accessories().unwrap();
+
+    let mut weapons = original.clone();
+    weapons[0].attack = weapons[0].attack.wrapping_add(10);
+    weapons[1].buy_price = 1;
+    game.write_weapons(&weapons).unwrap();
+    let mut edited_accessories = accessories.clone();
+    edited_accessories[0].buy_price = 1;
+    game.write_accessories(&edited_accessories).unwrap();
+
+    game.reset_to_vanilla(alx::entries::EntryKind::Weapon)
+        .unwrap();
+    assert_eq!(game.read_weapons().unwrap(), original);
+    // Other types keep their edits
+    assert_eq!(game.read_accessories().unwrap(), edited_accessories);
+
+    assert!(matches!(
+        game.reset_to_vanilla(alx::entries::EntryKind::Enemy),
+        Err(alx::Error::FeatureUnavailable { .. })
+    ));
+}
+
+#[test]
+fn test_weapon_import_ignores_extra_columns() {
+    skip_if_no_iso!();
+
+    let mut game = common::load_game();
+    let weapons = game.read_weapons().unwrap();
+    let effects = game.read_weapon_effects().unwrap();
+    let mut exported = Vec::new();
+    alx::csv::CsvExporter::export_weapons(&weapons, &mut exported, &effects).unwrap();
+
+    // A "Notes" column at the end and a helper column at the front
+    let mut rdr = csv::Reader::from_reader(exported.as_slice());
+    let mut annotated = csv::Writer::from_writer(Vec::new());
+    let mut headers = vec!["Helper".to_string()];
+    headers.extend(rdr.headers().unwrap().iter().map(str::to_string));
+    headers.push("Notes".to_string());
+    annotated.write_record(&headers).unwrap();
+    for record in rdr.records() {
+        let mut row = vec!["=1+1".to_string()];
+        row.extend(record.unwrap().iter().map(str::to_string));
+        row.push("buy this one".to_string());
+        annotated.write_record(&row).unwrap();
+    }
+    let annotated = annotated.into_inner().unwrap();
+
+    let imported = alx::csv::CsvImporter::import_weapons(annotated.as_slice()).unwrap();
+    assert_eq!(imported, weapons);
+}